@@ -0,0 +1,213 @@
+//! Bulk import of hand history archives
+//!
+//! Files are parsed on a pool of threads and the parsed hands are inserted by a single writer,
+//! in batches of one transaction each. Files are recorded with their hands, so that an
+//! interrupted import picks up where it stopped. Hands whose chips do not add up are imported
+//! all the same and reported.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::sync_channel;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use diesel::SqliteConnection;
+
+use holdem_suite_db::models::ImportedFile;
+use holdem_suite_db::{
+    establish_connection, get_imported_files, hero_result, import_batch, insert_summary,
+};
+use holdem_suite_parser::parser::Hand;
+use holdem_suite_parser::results::PlayerResult;
+use holdem_suite_parser::summary_parser::{parse_summary, TournamentSummary};
+
+use crate::read_hands;
+
+/// Time between two progress reports
+const REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Content of a file, read by a worker
+struct ParsedFile {
+    file: ImportedFile,
+    /// Hands with the result of hero, which takes an equity computation after an all-in
+    hands: Vec<(Hand, PlayerResult)>,
+    summary: Option<TournamentSummary>,
+}
+
+/// Lists the files to import, looking for hand histories and summaries in directories
+fn list_files(paths: Vec<PathBuf>, files: &mut Vec<ImportedFile>) -> Result<()> {
+    for path in paths {
+        let metadata = fs::metadata(&path)?;
+        if metadata.is_dir() {
+            let mut entries = fs::read_dir(&path)?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<PathBuf>>>()?;
+            entries.retain(|entry| {
+                entry.is_dir()
+                    || matches!(
+                        entry.extension().and_then(|extension| extension.to_str()),
+                        Some("txt" | "json")
+                    )
+            });
+            entries.sort();
+            list_files(entries, files)?;
+        } else {
+            files.push(ImportedFile {
+                path: fs::canonicalize(&path)?.to_string_lossy().into_owned(),
+                size: metadata.len() as i64,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn parse_file(file: ImportedFile) -> Result<ParsedFile> {
+    let path = Path::new(&file.path);
+    let data = fs::read_to_string(path)?;
+    if file.path.contains("summary") {
+        let summary = parse_summary(&data)?;
+        return Ok(ParsedFile {
+            file,
+            hands: vec![],
+            summary: Some(summary),
+        });
+    }
+    let hands = read_hands(&data, |error| {
+        eprintln!("Skipped hand in {}: {}", path.display(), error)
+    });
+    for hand in &hands {
+        let discrepancies = hand.validate();
+        if !discrepancies.is_empty() {
+            let discrepancies: Vec<String> = discrepancies.iter().map(|d| d.to_string()).collect();
+            eprintln!(
+                "Inconsistent hand #{} in {}: {}",
+                hand.hand_info.hand_id,
+                path.display(),
+                discrepancies.join("; ")
+            );
+        }
+    }
+    let hands = hands
+        .into_iter()
+        .map(|hand| {
+            let result = hero_result(&hand);
+            (hand, result)
+        })
+        .collect();
+    Ok(ParsedFile {
+        file,
+        hands,
+        summary: None,
+    })
+}
+
+/// Hands and files waiting to be inserted
+#[derive(Default)]
+struct Batch {
+    hands: Vec<(Hand, PlayerResult)>,
+    files: Vec<ImportedFile>,
+}
+
+impl Batch {
+    fn write(&mut self, connection: &mut SqliteConnection, progress: &mut Progress) -> Result<()> {
+        let batch = std::mem::take(self);
+        progress.nb_hands += import_batch(connection, batch.hands, &batch.files)?;
+        progress.nb_files += batch.files.len();
+        Ok(())
+    }
+}
+
+struct Progress {
+    start: Instant,
+    last_report: Instant,
+    nb_files: usize,
+    nb_hands: u32,
+}
+
+impl Progress {
+    fn report(&mut self, total: usize) {
+        let elapsed = self.start.elapsed();
+        println!(
+            "Imported {}/{} files, {} hands in {:.0?} ({:.0} hands/s)",
+            self.nb_files,
+            total,
+            self.nb_hands,
+            elapsed,
+            self.nb_hands as f64 / elapsed.as_secs_f64()
+        );
+        self.last_report = Instant::now();
+    }
+}
+
+/// Imports files and directories of hand histories and summaries with `jobs` parsing threads,
+/// skipping the files imported before unless they have grown since
+pub fn import(
+    paths: Vec<PathBuf>,
+    database_url: &str,
+    jobs: usize,
+    batch_size: usize,
+) -> Result<()> {
+    let connection = &mut establish_connection(database_url);
+    let mut files = vec![];
+    list_files(paths, &mut files)?;
+    let imported = get_imported_files(connection)?;
+    let nb_listed = files.len();
+    files.retain(|file| imported.get(&file.path) != Some(&file.size));
+    if files.len() < nb_listed {
+        println!("Skipping {} files imported before", nb_listed - files.len());
+    }
+
+    let total = files.len();
+    let queue = Mutex::new(files.into_iter());
+    let (sender, receiver) = sync_channel::<Result<ParsedFile>>(jobs * 2);
+    let start = Instant::now();
+    let mut progress = Progress {
+        start,
+        last_report: start,
+        nb_files: 0,
+        nb_hands: 0,
+    };
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let Some(file) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let path = file.path.to_owned();
+                let parsed = parse_file(file).map_err(|e| e.context(path));
+                // the writer stopped on an error
+                if sender.send(parsed).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut batch = Batch::default();
+        for parsed in receiver {
+            match parsed {
+                Ok(parsed) => {
+                    if let Some(summary) = parsed.summary {
+                        insert_summary(connection, summary)?;
+                    }
+                    batch.hands.extend(parsed.hands);
+                    batch.files.push(parsed.file);
+                }
+                Err(e) => eprintln!("{:#}", e),
+            }
+            if batch.hands.len() >= batch_size {
+                batch.write(connection, &mut progress)?;
+            }
+            if progress.last_report.elapsed() >= REPORT_INTERVAL {
+                progress.report(total);
+            }
+        }
+        batch.write(connection, &mut progress)
+    })?;
+    progress.report(total);
+    Ok(())
+}
@@ -1,15 +1,24 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use notify::EventKind;
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 
-use holdem_suite_db::{establish_connection, insert_hands, insert_summary};
-use holdem_suite_parser::parser::parse_hands;
-use holdem_suite_parser::summary_parser;
+use holdem_suite_db::{
+    establish_connection, get_parsed_hand, insert_hands, insert_summary, update_hand_results,
+};
+use holdem_suite_parser::equity::{parse_cards, Equity, EquityCalculator, Holding};
+use holdem_suite_parser::incremental::IncrementalParser;
+use holdem_suite_parser::openhh::{self, OpenHhWriter};
+use holdem_suite_parser::parser::{parse_hands_recovering, Hand, PokerType};
+use holdem_suite_parser::summary_parser::parse_summary;
+use holdem_suite_parser::writer::{PokerStarsWriter, SiteWriter};
+
+mod import;
 
 #[derive(Parser)]
 struct Cli {
@@ -20,10 +29,67 @@ struct Cli {
 #[derive(Debug, Subcommand)]
 enum Commands {
     #[command(arg_required_else_help = true)]
-    Parse { path: Vec<PathBuf> },
+    Parse {
+        path: Vec<PathBuf>,
+        /// Prints the parsed hands and summaries as JSON, one per line, instead of storing them
+        #[arg(long)]
+        json: bool,
+    },
 
     #[command(arg_required_else_help = true)]
     Watch { path: PathBuf },
+
+    /// Imports files and directories of hand histories on several threads, skipping the files
+    /// imported before so that an interrupted import can be resumed
+    #[command(arg_required_else_help = true)]
+    Import {
+        path: Vec<PathBuf>,
+        /// Number of parsing threads, one per CPU by default
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Number of hands inserted per transaction
+        #[arg(long, default_value_t = 5000)]
+        batch: usize,
+    },
+
+    /// Writes hands from files or from the database in another format
+    #[command(arg_required_else_help = true)]
+    Export {
+        path: Vec<PathBuf>,
+        /// Id of a stored hand, can be repeated
+        #[arg(long)]
+        hand: Vec<String>,
+        #[arg(long, value_enum, default_value_t = Format::Pokerstars)]
+        format: Format,
+    },
+
+    /// Computes the equity of hands against hands or ranges, or of the players of a stored hand
+    /// when the last chips went in
+    Equity {
+        /// Hole cards, as "AhKd", or a range, as "QQ+,AKs", for each player
+        holdings: Vec<String>,
+        /// Cards already dealt, as "Ah 9h 2c"
+        #[arg(long, default_value = "")]
+        board: String,
+        /// Id of a stored hand, instead of the holdings
+        #[arg(long, conflicts_with_all = ["holdings", "board"])]
+        hand: Option<String>,
+        /// Number of deals sampled when there are too many to enumerate
+        #[arg(long, default_value_t = 100_000)]
+        samples: u64,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+
+    /// Computes the results of hero in the hands stored before they were kept
+    UpdateResults,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Pokerstars,
+    /// Open Hand History JSON
+    Openhh,
 }
 
 fn parse(path: Vec<PathBuf>) {
@@ -36,47 +102,209 @@ fn parse(path: Vec<PathBuf>) {
     println!("Parsed {} files in {:?}", count, start.elapsed());
 }
 
-fn parse_file(path: PathBuf) -> Result<()> {
-    let db_path = match std::env::var("DATABASE_URL") {
+fn print_json(path: Vec<PathBuf>) -> Result<()> {
+    for path in path {
+        let data = fs::read_to_string(&path)?;
+        if path.to_str().unwrap().contains("summary") {
+            println!("{}", serde_json::to_string(&parse_summary(&data)?)?);
+        } else {
+            for hand in read_hands(&data, |error| eprintln!("Skipped hand: {}", error)) {
+                println!("{}", serde_json::to_string(&hand)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn database_url() -> String {
+    match std::env::var("DATABASE_URL") {
         Ok(val) => val,
         Err(_) => String::from("sqlite:///home/clemux/dev/holdem-suite/tracker.db"),
-    };
-    let connection = &mut establish_connection(&db_path);
+    }
+}
+
+fn parse_file(path: PathBuf) -> Result<()> {
+    let connection = &mut establish_connection(&database_url());
     println!("{}", path.display());
     if path.clone().to_str().unwrap().contains("summary") {
         let data = fs::read_to_string(path).expect("Unable to read file");
-        let parse_result = summary_parser::TournamentSummary::parse(&data);
-        let (_, summary) = parse_result.unwrap();
-        insert_summary(connection, summary);
+        match parse_summary(&data) {
+            Ok(summary) => {
+                insert_summary(connection, summary)?;
+            }
+            Err(e) => println!("{}", e),
+        }
     } else {
         let data = fs::read_to_string(path).expect("Unable to read file");
-        let parse_result = parse_hands(&data);
         let start = Instant::now();
-        match parse_result {
-            Ok((_, hands)) => {
-                let nb_hands = insert_hands(connection, hands)?;
-                println!("Parsed {} hands in {:?}", nb_hands, start.elapsed());
-            }
-            Err(e) => println!("{}", e),
+        let hands = read_hands(&data, |error| println!("Skipped hand: {}", error));
+        let nb_hands = insert_hands(connection, hands)?;
+        println!("Parsed {} hands in {:?}", nb_hands, start.elapsed());
+    }
+    Ok(())
+}
+
+/// Reads a text hand history or an OpenHH file, reporting the hands that are skipped
+fn read_hands(data: &str, report_skipped: impl Fn(String)) -> Vec<Hand> {
+    if openhh::is_openhh(data) {
+        let (hands, report) = openhh::parse_hands_recovering(data);
+        for skipped in report.skipped {
+            report_skipped(skipped.error.to_string());
         }
+        return hands;
     }
+    let (hands, report) = parse_hands_recovering(data);
+    for skipped in report.skipped {
+        report_skipped(skipped.error.to_string());
+    }
+    hands
+}
+
+fn export(path: Vec<PathBuf>, hand_ids: Vec<String>, format: Format) -> Result<()> {
+    let mut hands = vec![];
+    for path in path {
+        let data = fs::read_to_string(&path)?;
+        hands.extend(read_hands(&data, |error| {
+            eprintln!("Skipped hand: {}", error)
+        }));
+    }
+    if !hand_ids.is_empty() {
+        let connection = &mut establish_connection(&database_url());
+        for hand_id in hand_ids {
+            hands.push(get_parsed_hand(connection, &hand_id)?);
+        }
+    }
+    let writer: &dyn SiteWriter = match format {
+        Format::Pokerstars => &PokerStarsWriter,
+        Format::Openhh => &OpenHhWriter,
+    };
+    print!("{}", writer.write_hands(&hands));
+    Ok(())
+}
+
+fn print_equity(names: &[String], equity: &Equity) {
+    for (name, equity) in names.iter().zip(&equity.equities) {
+        println!("{}: {:.2}%", name, equity * 100.0);
+    }
+    match equity.exhaustive {
+        true => println!("Enumerated {} deals", equity.deals),
+        false => println!("Sampled {} deals", equity.deals),
+    }
+}
+
+fn equity(
+    holdings: Vec<String>,
+    board: String,
+    hand_id: Option<String>,
+    calculator: EquityCalculator,
+) -> Result<()> {
+    if let Some(hand_id) = hand_id {
+        let connection = &mut establish_connection(&database_url());
+        let hand = get_parsed_hand(connection, &hand_id)?;
+        let Some(equity) = hand.showdown_equity(&calculator)? else {
+            println!(
+                "The cards of the players left in hand #{} are not known",
+                hand_id
+            );
+            return Ok(());
+        };
+        let board: Vec<String> = hand
+            .board_at(equity.street)
+            .iter()
+            .map(|card| card.to_string())
+            .collect();
+        let street_type = &hand.streets[equity.street].street_type;
+        match board.is_empty() {
+            true => println!("Last chips in on the {}", street_type),
+            false => println!("Last chips in on the {} [{}]", street_type, board.join(" ")),
+        }
+        let names: Vec<String> = equity
+            .players
+            .iter()
+            .map(|&index| {
+                let cards = hand.known_cards(index).unwrap_or_default();
+                let cards: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+                format!("{} [{}]", hand.seats[index].player_name, cards.join(" "))
+            })
+            .collect();
+        print_equity(&names, &equity.equity);
+        return Ok(());
+    }
+
+    let parsed = holdings
+        .iter()
+        .map(|holding| holding.parse())
+        .collect::<Result<Vec<Holding>, _>>()?;
+    let poker_type = match parsed.iter().find_map(|holding| match holding {
+        Holding::Cards(cards) => Some(cards.len()),
+        Holding::Range(_) => None,
+    }) {
+        Some(4) => PokerType::OmahaPotLimit,
+        Some(5) => PokerType::Omaha5PotLimit,
+        _ => PokerType::HoldemNoLimit,
+    };
+    let equity = calculator.equity(&poker_type, &parsed, &parse_cards(&board)?)?;
+    print_equity(&holdings, &equity);
+    Ok(())
+}
+
+fn update_results() -> Result<()> {
+    let connection = &mut establish_connection(&database_url());
+    let start = Instant::now();
+    let nb_hands = update_hand_results(connection, |hand_id, error| {
+        eprintln!("Skipped hand #{}: {}", hand_id, error)
+    })?;
+    println!(
+        "Computed the results of {} hands in {:?}",
+        nb_hands,
+        start.elapsed()
+    );
+    Ok(())
+}
+
+fn parse_new_hands(path: PathBuf, parser: &mut IncrementalParser) -> Result<()> {
+    let connection = &mut establish_connection(&database_url());
+    let start = Instant::now();
+    let (hands, report) = parser.read(&path)?;
+    for skipped in report.skipped {
+        println!("Skipped hand: {}", skipped.error);
+    }
+    let nb_hands = insert_hands(connection, hands)?;
+    println!("Parsed {} new hands in {:?}", nb_hands, start.elapsed());
     Ok(())
 }
 
+fn parse_modified_file(
+    path: PathBuf,
+    parsers: &mut HashMap<PathBuf, IncrementalParser>,
+) -> Result<()> {
+    if path.to_str().unwrap().contains("summary") {
+        parse_file(path)
+    } else {
+        let parser = parsers.entry(path.clone()).or_default();
+        parse_new_hands(path, parser)
+    }
+}
+
 fn watch<P: AsRef<Path>>(path: P) {
     let (tx, rx) = std::sync::mpsc::channel();
     let mut watcher = RecommendedWatcher::new(tx, Config::default()).unwrap();
     let _ = watcher.watch(path.as_ref(), RecursiveMode::Recursive);
+    let mut parsers = HashMap::new();
     for res in rx {
         match res {
             Ok(event) => match event.kind {
                 EventKind::Create(_) => {
                     println!("created file {:?}", event.paths);
-                    parse_file(event.paths[0].clone());
+                    if let Err(e) = parse_modified_file(event.paths[0].clone(), &mut parsers) {
+                        println!("{}", e);
+                    }
                 }
                 EventKind::Modify(_) => {
                     println!("modified file {:?}", event.paths);
-                    parse_file(event.paths[0].clone());
+                    if let Err(e) = parse_modified_file(event.paths[0].clone(), &mut parsers) {
+                        println!("{}", e);
+                    }
                 }
                 _ => {}
             },
@@ -88,7 +316,46 @@ fn watch<P: AsRef<Path>>(path: P) {
 fn main() {
     let command = Cli::parse();
     match command.command {
-        Commands::Parse { path } => parse(path),
+        Commands::Parse { path, json: false } => parse(path),
+        Commands::Parse { path, json: true } => {
+            if let Err(e) = print_json(path) {
+                eprintln!("{}", e);
+            }
+        }
         Commands::Watch { path } => watch(path),
+        Commands::Import { path, jobs, batch } => {
+            let jobs = jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(1, |jobs| jobs.get())
+            });
+            if let Err(e) = import::import(path, &database_url(), jobs, batch) {
+                eprintln!("{}", e);
+            }
+        }
+        Commands::Export { path, hand, format } => {
+            if let Err(e) = export(path, hand, format) {
+                eprintln!("{}", e);
+            }
+        }
+        Commands::Equity {
+            holdings,
+            board,
+            hand,
+            samples,
+            seed,
+        } => {
+            let calculator = EquityCalculator {
+                samples,
+                seed,
+                ..Default::default()
+            };
+            if let Err(e) = equity(holdings, board, hand, calculator) {
+                eprintln!("{}", e);
+            }
+        }
+        Commands::UpdateResults => {
+            if let Err(e) = update_results() {
+                eprintln!("{}", e);
+            }
+        }
     }
 }
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE seats DROP COLUMN card5;
+ALTER TABLE seats DROP COLUMN card4;
+ALTER TABLE seats DROP COLUMN card3;
+ALTER TABLE hands DROP COLUMN hole_card_5;
+ALTER TABLE hands DROP COLUMN hole_card_4;
+ALTER TABLE hands DROP COLUMN hole_card_3;
+ALTER TABLE hands DROP COLUMN poker_type;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE hands ADD COLUMN poker_type TEXT NOT NULL DEFAULT 'holdem-no-limit';
+ALTER TABLE hands ADD COLUMN hole_card_3 VARCHAR(2) NULL;
+ALTER TABLE hands ADD COLUMN hole_card_4 VARCHAR(2) NULL;
+ALTER TABLE hands ADD COLUMN hole_card_5 VARCHAR(2) NULL;
+ALTER TABLE seats ADD COLUMN card3 TEXT NULL;
+ALTER TABLE seats ADD COLUMN card4 TEXT NULL;
+ALTER TABLE seats ADD COLUMN card5 TEXT NULL;
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE hands DROP COLUMN pool_name;
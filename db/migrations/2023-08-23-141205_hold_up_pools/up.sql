@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE hands ADD COLUMN pool_name TEXT NULL;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE summaries DROP COLUMN multiplier;
+ALTER TABLE summaries DROP COLUMN prizepool;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE summaries ADD COLUMN prizepool DOUBLE NULL;
+ALTER TABLE summaries ADD COLUMN multiplier DOUBLE NULL;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE summaries DROP COLUMN tickets;
+ALTER TABLE summaries DROP COLUMN bounties_won;
+ALTER TABLE summaries DROP COLUMN total_cost;
+ALTER TABLE summaries DROP COLUMN addons;
+ALTER TABLE summaries DROP COLUMN rebuys;
+ALTER TABLE summaries DROP COLUMN nb_entries;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE summaries ADD COLUMN nb_entries INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE summaries ADD COLUMN rebuys INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE summaries ADD COLUMN addons INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE summaries ADD COLUMN total_cost DOUBLE NULL;
+ALTER TABLE summaries ADD COLUMN bounties_won DOUBLE NULL;
+ALTER TABLE summaries ADD COLUMN tickets TEXT NULL;
+-- Summaries stored before the costs were kept paid a single buy-in, whose rake is unknown
+UPDATE summaries SET total_cost = buyin;
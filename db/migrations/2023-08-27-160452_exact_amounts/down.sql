@@ -0,0 +1,104 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE hands_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    hole_card_1 VARCHAR(2) NOT NULL,
+    hole_card_2 VARCHAR(2) NOT NULL,
+    tournament_id INT NULL,
+    cash_game_name TEXT NULL,
+    datetime TEXT NOT NULL,
+    button INTEGER NOT NULL,
+    max_players INTEGER NOT NULL,
+    hero TEXT NOT NULL,
+    ante DOUBLE NULL,
+    small_blind DOUBLE NOT NULL,
+    big_blind DOUBLE NOT NULL,
+    pot DOUBLE NOT NULL,
+    rake DOUBLE NULL,
+    flop1 VARCHAR(2) NULL,
+    flop2 VARCHAR(2) NULL,
+    flop3 VARCHAR(2) NULL,
+    turn VARCHAR(2) NULL,
+    river VARCHAR(2) NULL,
+    poker_type TEXT NOT NULL DEFAULT 'holdem-no-limit',
+    hole_card_3 VARCHAR(2) NULL,
+    hole_card_4 VARCHAR(2) NULL,
+    hole_card_5 VARCHAR(2) NULL,
+    pool_name TEXT NULL
+);
+INSERT INTO hands_new
+SELECT id, hole_card_1, hole_card_2, tournament_id, cash_game_name, datetime, button,
+       max_players, hero, ante / 100.0,
+       small_blind / 100.0, big_blind / 100.0,
+       pot / 100.0, rake / 100.0, flop1, flop2,
+       flop3, turn, river, poker_type, hole_card_3, hole_card_4, hole_card_5, pool_name
+FROM hands;
+DROP TABLE hands;
+ALTER TABLE hands_new RENAME TO hands;
+
+CREATE TABLE actions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    hand_id TEXT NOT NULL REFERENCES hands(id),
+    player_name TEXT NOT NULL,
+    action_type TEXT NOT NULL,
+    amount DOUBLE NULL,
+    is_all_in INTEGER NOT NULL,
+    street TEXT NOT NULL
+);
+INSERT INTO actions_new
+SELECT id, hand_id, player_name, action_type, amount / 100.0, is_all_in,
+       street
+FROM actions;
+DROP TABLE actions;
+ALTER TABLE actions_new RENAME TO actions;
+
+CREATE TABLE seats_new
+(
+    hand_id     TEXT    NOT NULL REFERENCES hands (id),
+    player_name TEXT    NOT NULL,
+    seat_number INTEGER NOT NULL,
+    stack DOUBLE NOT NULL,
+    bounty DOUBLE NULL,
+    card1 TEXT NULL,
+    card2 TEXT NULL,
+    card3 TEXT NULL,
+    card4 TEXT NULL,
+    card5 TEXT NULL,
+    PRIMARY KEY (hand_id, seat_number)
+);
+INSERT INTO seats_new
+SELECT hand_id, player_name, seat_number, stack / 100.0,
+       bounty / 100.0, card1, card2, card3, card4, card5
+FROM seats;
+DROP TABLE seats;
+ALTER TABLE seats_new RENAME TO seats;
+
+CREATE TABLE summaries_new (
+    id integer PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    buyin DOUBLE NOT NULL,
+    date DATETIME NOT NULL,
+    play_time TEXT NOT NULL,
+    entries INTEGER NOT NULL,
+    mode TEXT NOT NULL,
+    tournament_type TEXT NOT NULL,
+    speed TEXT NOT NULL,
+    finish_place INTEGER NOT NULL,
+    won DOUBLE NULL,
+    prizepool DOUBLE NULL,
+    multiplier DOUBLE NULL,
+    nb_entries INTEGER NOT NULL DEFAULT 1,
+    rebuys INTEGER NOT NULL DEFAULT 0,
+    addons INTEGER NOT NULL DEFAULT 0,
+    total_cost DOUBLE NULL,
+    bounties_won DOUBLE NULL,
+    tickets TEXT NULL
+);
+INSERT INTO summaries_new
+SELECT id, name, buyin / 100.0, date, play_time, entries, mode,
+       tournament_type, speed, finish_place, won / 100.0,
+       prizepool / 100.0, multiplier, nb_entries, rebuys, addons,
+       total_cost / 100.0, bounties_won / 100.0,
+       tickets
+FROM summaries;
+DROP TABLE summaries;
+ALTER TABLE summaries_new RENAME TO summaries;
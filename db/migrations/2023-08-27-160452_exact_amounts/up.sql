@@ -0,0 +1,105 @@
+-- Your SQL goes here
+-- Amounts are stored as integer hundredths: chips stay whole and euros are exact cents
+CREATE TABLE hands_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    hole_card_1 VARCHAR(2) NOT NULL,
+    hole_card_2 VARCHAR(2) NOT NULL,
+    tournament_id INT NULL,
+    cash_game_name TEXT NULL,
+    datetime TEXT NOT NULL,
+    button INTEGER NOT NULL,
+    max_players INTEGER NOT NULL,
+    hero TEXT NOT NULL,
+    ante BIGINT NULL,
+    small_blind BIGINT NOT NULL,
+    big_blind BIGINT NOT NULL,
+    pot BIGINT NOT NULL,
+    rake BIGINT NULL,
+    flop1 VARCHAR(2) NULL,
+    flop2 VARCHAR(2) NULL,
+    flop3 VARCHAR(2) NULL,
+    turn VARCHAR(2) NULL,
+    river VARCHAR(2) NULL,
+    poker_type TEXT NOT NULL DEFAULT 'holdem-no-limit',
+    hole_card_3 VARCHAR(2) NULL,
+    hole_card_4 VARCHAR(2) NULL,
+    hole_card_5 VARCHAR(2) NULL,
+    pool_name TEXT NULL
+);
+INSERT INTO hands_new
+SELECT id, hole_card_1, hole_card_2, tournament_id, cash_game_name, datetime, button,
+       max_players, hero, CAST(ROUND(ante * 100) AS INTEGER),
+       CAST(ROUND(small_blind * 100) AS INTEGER), CAST(ROUND(big_blind * 100) AS INTEGER),
+       CAST(ROUND(pot * 100) AS INTEGER), CAST(ROUND(rake * 100) AS INTEGER), flop1, flop2,
+       flop3, turn, river, poker_type, hole_card_3, hole_card_4, hole_card_5, pool_name
+FROM hands;
+DROP TABLE hands;
+ALTER TABLE hands_new RENAME TO hands;
+
+CREATE TABLE actions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    hand_id TEXT NOT NULL REFERENCES hands(id),
+    player_name TEXT NOT NULL,
+    action_type TEXT NOT NULL,
+    amount BIGINT NULL,
+    is_all_in INTEGER NOT NULL,
+    street TEXT NOT NULL
+);
+INSERT INTO actions_new
+SELECT id, hand_id, player_name, action_type, CAST(ROUND(amount * 100) AS INTEGER), is_all_in,
+       street
+FROM actions;
+DROP TABLE actions;
+ALTER TABLE actions_new RENAME TO actions;
+
+CREATE TABLE seats_new
+(
+    hand_id     TEXT    NOT NULL REFERENCES hands (id),
+    player_name TEXT    NOT NULL,
+    seat_number INTEGER NOT NULL,
+    stack BIGINT NOT NULL,
+    bounty BIGINT NULL,
+    card1 TEXT NULL,
+    card2 TEXT NULL,
+    card3 TEXT NULL,
+    card4 TEXT NULL,
+    card5 TEXT NULL,
+    PRIMARY KEY (hand_id, seat_number)
+);
+INSERT INTO seats_new
+SELECT hand_id, player_name, seat_number, CAST(ROUND(stack * 100) AS INTEGER),
+       CAST(ROUND(bounty * 100) AS INTEGER), card1, card2, card3, card4, card5
+FROM seats;
+DROP TABLE seats;
+ALTER TABLE seats_new RENAME TO seats;
+
+CREATE TABLE summaries_new (
+    id integer PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    buyin BIGINT NOT NULL,
+    date DATETIME NOT NULL,
+    play_time TEXT NOT NULL,
+    entries INTEGER NOT NULL,
+    mode TEXT NOT NULL,
+    tournament_type TEXT NOT NULL,
+    speed TEXT NOT NULL,
+    finish_place INTEGER NOT NULL,
+    won BIGINT NULL,
+    prizepool BIGINT NULL,
+    multiplier DOUBLE NULL,
+    nb_entries INTEGER NOT NULL DEFAULT 1,
+    rebuys INTEGER NOT NULL DEFAULT 0,
+    addons INTEGER NOT NULL DEFAULT 0,
+    total_cost BIGINT NULL,
+    bounties_won BIGINT NULL,
+    tickets TEXT NULL
+);
+INSERT INTO summaries_new
+SELECT id, name, CAST(ROUND(buyin * 100) AS INTEGER), date, play_time, entries, mode,
+       tournament_type, speed, finish_place, CAST(ROUND(won * 100) AS INTEGER),
+       CAST(ROUND(prizepool * 100) AS INTEGER), multiplier, nb_entries, rebuys, addons,
+       CAST(ROUND(total_cost * 100) AS INTEGER), CAST(ROUND(bounties_won * 100) AS INTEGER),
+       tickets
+FROM summaries;
+DROP TABLE summaries;
+ALTER TABLE summaries_new RENAME TO summaries;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE hands DROP COLUMN play_money;
+ALTER TABLE hands DROP COLUMN table_number;
+ALTER TABLE hands DROP COLUMN tournament_level;
+ALTER TABLE hands DROP COLUMN tournament_rake;
+ALTER TABLE hands DROP COLUMN tournament_buy_in;
+ALTER TABLE hands DROP COLUMN tournament_name;
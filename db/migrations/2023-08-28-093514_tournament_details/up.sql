@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE hands ADD COLUMN tournament_name TEXT NULL;
+ALTER TABLE hands ADD COLUMN tournament_buy_in BIGINT NULL;
+ALTER TABLE hands ADD COLUMN tournament_rake BIGINT NULL;
+ALTER TABLE hands ADD COLUMN tournament_level INTEGER NULL;
+ALTER TABLE hands ADD COLUMN table_number INTEGER NULL;
+ALTER TABLE hands ADD COLUMN play_money INTEGER NOT NULL DEFAULT 0;
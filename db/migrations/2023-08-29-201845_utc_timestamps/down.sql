@@ -0,0 +1,41 @@
+-- This file should undo anything in `up.sql`
+CREATE TABLE hands_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    hole_card_1 VARCHAR(2) NOT NULL,
+    hole_card_2 VARCHAR(2) NOT NULL,
+    tournament_id INT NULL,
+    cash_game_name TEXT NULL,
+    datetime TEXT NOT NULL,
+    button INTEGER NOT NULL,
+    max_players INTEGER NOT NULL,
+    hero TEXT NOT NULL,
+    ante BIGINT NULL,
+    small_blind BIGINT NOT NULL,
+    big_blind BIGINT NOT NULL,
+    pot BIGINT NOT NULL,
+    rake BIGINT NULL,
+    flop1 VARCHAR(2) NULL,
+    flop2 VARCHAR(2) NULL,
+    flop3 VARCHAR(2) NULL,
+    turn VARCHAR(2) NULL,
+    river VARCHAR(2) NULL,
+    poker_type TEXT NOT NULL DEFAULT 'holdem-no-limit',
+    hole_card_3 VARCHAR(2) NULL,
+    hole_card_4 VARCHAR(2) NULL,
+    hole_card_5 VARCHAR(2) NULL,
+    pool_name TEXT NULL,
+    tournament_name TEXT NULL,
+    tournament_buy_in BIGINT NULL,
+    tournament_rake BIGINT NULL,
+    tournament_level INTEGER NULL,
+    table_number INTEGER NULL,
+    play_money INTEGER NOT NULL DEFAULT 0
+);
+INSERT INTO hands_new
+SELECT id, hole_card_1, hole_card_2, tournament_id, cash_game_name, datetime || ' UTC', button,
+       max_players, hero, ante, small_blind, big_blind, pot, rake, flop1, flop2, flop3, turn,
+       river, poker_type, hole_card_3, hole_card_4, hole_card_5, pool_name, tournament_name,
+       tournament_buy_in, tournament_rake, tournament_level, table_number, play_money
+FROM hands;
+DROP TABLE hands;
+ALTER TABLE hands_new RENAME TO hands;
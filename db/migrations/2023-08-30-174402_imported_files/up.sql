@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Files of the bulk imports, with the size they had, so that unchanged files are skipped
+CREATE TABLE imported_files (
+    path TEXT PRIMARY KEY NOT NULL,
+    size BIGINT NOT NULL
+);
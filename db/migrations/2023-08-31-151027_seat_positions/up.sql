@@ -0,0 +1,53 @@
+-- Your SQL goes here
+ALTER TABLE seats ADD COLUMN position TEXT NOT NULL DEFAULT '';
+
+-- Positions of the hands stored before, as computed by the parser: empty seats are skipped,
+-- a dead button leaves no one on the button and the button posts the small blind heads-up
+WITH ordered AS (
+    SELECT
+        seats.hand_id,
+        seats.seat_number,
+        ROW_NUMBER() OVER (
+            PARTITION BY seats.hand_id
+            ORDER BY seats.seat_number <= hands.button, seats.seat_number
+        ) AS rank,
+        COUNT(*) OVER (PARTITION BY seats.hand_id) AS nb_seats,
+        MAX(seats.seat_number = hands.button) OVER (PARTITION BY seats.hand_id) AS has_button
+    FROM seats
+    INNER JOIN hands ON hands.id = seats.hand_id
+),
+ranked AS (
+    SELECT
+        hand_id,
+        seat_number,
+        rank,
+        nb_seats - has_button AS nb_players,
+        has_button,
+        -- players between the blinds and the button named from the cutoff backwards
+        MIN(nb_seats - has_button - 3, 3) AS nb_late
+    FROM ordered
+)
+UPDATE seats SET position = (
+    SELECT
+        CASE
+            WHEN ranked.has_button AND ranked.rank > ranked.nb_players THEN 'BTN'
+            WHEN ranked.has_button AND ranked.nb_players = 1 THEN 'BB'
+            WHEN ranked.rank = 1 THEN 'SB'
+            WHEN ranked.rank = 2 THEN 'BB'
+            WHEN ranked.nb_players - ranked.rank < ranked.nb_late THEN
+                CASE ranked.nb_players - ranked.rank
+                    WHEN 0 THEN 'CO'
+                    WHEN 1 THEN 'HJ'
+                    ELSE 'LJ'
+                END
+            ELSE
+                CASE ranked.rank
+                    WHEN 3 THEN 'UTG'
+                    WHEN 4 THEN 'UTG+1'
+                    WHEN 5 THEN 'UTG+2'
+                    ELSE 'UTG+3'
+                END
+        END
+    FROM ranked
+    WHERE ranked.hand_id = seats.hand_id AND ranked.seat_number = seats.seat_number
+);
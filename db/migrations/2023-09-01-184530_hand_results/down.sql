@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE hands DROP COLUMN ev_net_result;
+ALTER TABLE hands DROP COLUMN net_result;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE hands ADD COLUMN net_result BIGINT NULL;
+ALTER TABLE hands ADD COLUMN ev_net_result BIGINT NULL;
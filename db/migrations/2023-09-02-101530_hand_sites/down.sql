@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE hands DROP COLUMN currency;
+ALTER TABLE hands DROP COLUMN site;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE hands ADD COLUMN site TEXT NOT NULL DEFAULT 'Winamax';
+ALTER TABLE hands ADD COLUMN currency TEXT NOT NULL DEFAULT 'EUR';
+-- ids are numbers on PokerStars and start with letters on GGPoker
+UPDATE hands SET site = 'PokerStars', currency = 'USD' WHERE id NOT GLOB '*[^0-9]*';
+UPDATE hands SET site = 'GGPoker', currency = 'USD' WHERE id GLOB '[A-Z]*';
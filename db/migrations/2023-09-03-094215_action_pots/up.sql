@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- 0 for the main pot and the number of the side pot otherwise, older collects being main pots
+ALTER TABLE actions ADD COLUMN pot INTEGER;
+UPDATE actions SET pot = 0 WHERE action_type = 'collect';
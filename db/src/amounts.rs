@@ -0,0 +1,23 @@
+//! Amounts are stored as integer hundredths and sent to the frontend in chips or euros
+
+use holdem_suite_parser::amount::Amount;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(cents: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(Amount::from_cents(*cents).to_f64())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    Ok((f64::deserialize(deserializer)? * 100.0).round() as i64)
+}
+
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cents: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error> {
+        match cents {
+            Some(cents) => super::serialize(cents, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+}
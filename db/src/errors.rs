@@ -4,4 +4,6 @@ use thiserror::Error;
 pub enum DatabaseError {
     #[error(transparent)]
     DieselError(#[from] diesel::result::Error),
+    #[error("invalid stored value: {0}")]
+    InvalidValue(String),
 }
@@ -1,19 +1,30 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use diesel::prelude::*;
 use diesel::result::Error;
+use diesel::sqlite::Sqlite;
 use diesel::SqliteConnection;
 use serde::{Deserialize, Serialize};
 
+use holdem_suite_parser::amount::Amount;
+use holdem_suite_parser::equity::EquityCalculator;
 use holdem_suite_parser::parser;
-use holdem_suite_parser::parser::ActionType;
+use holdem_suite_parser::parser::{ActionType, PostType};
+use holdem_suite_parser::results::PlayerResult;
 use holdem_suite_parser::summary_parser;
+use holdem_suite_parser::timeline::HandTimeline;
+use holdem_suite_parser::writer::SiteWriter;
 
 use crate::errors::DatabaseError;
-use crate::models::{Action, Hand, NewAction, Seat, Summary};
+use crate::models::{Action, Hand, ImportedFile, NewAction, Seat, Summary};
 use crate::schema::*;
 
+pub mod amounts;
 pub mod errors;
 pub mod models;
 pub mod schema;
+pub mod timestamps;
 
 pub fn establish_connection(database_url: &str) -> SqliteConnection {
     SqliteConnection::establish(database_url)
@@ -24,18 +35,29 @@ pub fn insert_summary(
     conn: &mut SqliteConnection,
     summary: summary_parser::TournamentSummary,
 ) -> Result<usize, DatabaseError> {
+    let nb_entries = summary.nb_entries() as i32;
+    let total_cost = summary.total_cost();
+    let tickets = (!summary.tickets.is_empty()).then(|| summary.tickets.join("\n"));
     let new_summary = Summary {
         id: summary.id as i32,
         name: summary.name,
-        buyin: summary.buy_in.buy_in,
-        date: summary.date.naive_local(),
+        buyin: summary.buy_in.buy_in.cents(),
+        date: summary.date.naive_utc(),
         play_time: summary.play_time,
         entries: summary.entries as i32,
         mode: summary.mode,
         tournament_type: summary.tournament_type.to_string(),
         speed: summary.speed,
         finish_place: summary.finish_place as i32,
-        won: summary.won,
+        won: summary.won.map(Amount::cents),
+        prizepool: Some(summary.prizepool.cents()),
+        multiplier: summary.expresso.and_then(|expresso| expresso.multiplier),
+        nb_entries,
+        rebuys: summary.rebuys as i32,
+        addons: summary.addons as i32,
+        total_cost: Some(total_cost.cents()),
+        bounties_won: summary.bounties_won.map(Amount::cents),
+        tickets,
     };
     Ok(diesel::insert_into(summaries::table)
         .values(&new_summary)
@@ -49,25 +71,142 @@ pub fn get_summaries(conn: &mut SqliteConnection) -> Result<Vec<Summary>, Databa
         .load(conn)?)
 }
 
+/// Expresso results for one prize multiplier
+#[derive(Serialize, Deserialize, Debug, Clone, PartialOrd, PartialEq)]
+pub struct ExpressoResults {
+    pub multiplier: f64,
+    pub tournaments: u32,
+    pub first_places: u32,
+    /// Buy-ins paid, rake included
+    #[serde(with = "crate::amounts")]
+    pub buy_ins: i64,
+    #[serde(with = "crate::amounts")]
+    pub winnings: i64,
+    /// Winnings of a player as good as their opponents, an equal share of each prizepool
+    #[serde(with = "crate::amounts")]
+    pub expected_winnings: i64,
+}
+
+pub fn get_expresso_results(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<ExpressoResults>, DatabaseError> {
+    let summaries = summaries::dsl::summaries
+        .filter(summaries::tournament_type.eq("expresso"))
+        .filter(summaries::multiplier.is_not_null())
+        .order(summaries::multiplier)
+        .select(Summary::as_select())
+        .load(conn)?;
+    let mut results: Vec<ExpressoResults> = vec![];
+    for summary in summaries {
+        let multiplier = summary.multiplier.unwrap_or_default();
+        if results.last().map(|results| results.multiplier) != Some(multiplier) {
+            results.push(ExpressoResults {
+                multiplier,
+                tournaments: 0,
+                first_places: 0,
+                buy_ins: 0,
+                winnings: 0,
+                expected_winnings: 0,
+            });
+        }
+        let results = results.last_mut().unwrap();
+        results.tournaments += 1;
+        results.first_places += (summary.finish_place == 1) as u32;
+        results.buy_ins += summary.total_cost.unwrap_or(summary.buyin);
+        results.winnings += summary.won.unwrap_or_default();
+        let prizepool = summary.prizepool.unwrap_or_default() as f64;
+        results.expected_winnings += (prizepool / summary.entries.max(1) as f64).round() as i64;
+    }
+    Ok(results)
+}
+
 fn get_board_card(hand: &parser::Hand, n: usize) -> Option<String> {
-    hand.summary.board.as_ref()?.cards[n]
-        .as_ref()
+    hand.board_at(hand.streets.len().saturating_sub(1))
+        .get(n)
         .map(|card| card.to_string())
 }
 
+fn get_hole_card(hole_cards: &parser::HoleCards, n: usize) -> Option<String> {
+    hole_cards.cards.get(n).map(|card| card.to_string())
+}
+
+/// Actions inserted by a single statement
+const ACTIONS_PER_INSERT: usize = 1000;
+
+/// Hand ids looked up by a single statement
+const IDS_PER_SELECT: usize = 1000;
+
+/// Result of hero, their all-in adjusted result being the actual one when the equity cannot be
+/// computed
+///
+/// Boards are sampled with a fixed seed, so that a hand always gets the same result.
+pub fn hero_result(hand: &parser::Hand) -> PlayerResult {
+    let calculator = EquityCalculator {
+        samples: 20_000,
+        ..Default::default()
+    };
+    let Some(index) = hand.hero_index() else {
+        return PlayerResult {
+            net: Amount::ZERO,
+            ev_net: Amount::ZERO,
+        };
+    };
+    hand.result(index, &calculator).unwrap_or_else(|_| {
+        let net = HandTimeline::new(hand).settlement(hand).net(index);
+        PlayerResult { net, ev_net: net }
+    })
+}
+
+/// Inserts the hands that are not stored yet, computing the results of hero before the
+/// transaction so that the database stays free for other writers meanwhile
 pub fn insert_hands(
     conn: &mut SqliteConnection,
     hands_vec: Vec<parser::Hand>,
+) -> Result<u32, DatabaseError> {
+    let mut stored = HashSet::new();
+    let hand_ids: Vec<&str> = hands_vec
+        .iter()
+        .map(|hand| hand.hand_info.hand_id.as_str())
+        .collect();
+    // a single statement would go over the number of parameters allowed by SQLite
+    for hand_ids in hand_ids.chunks(IDS_PER_SELECT) {
+        stored.extend(
+            hands::table
+                .filter(hands::id.eq_any(hand_ids))
+                .select(hands::id)
+                .load::<String>(conn)?,
+        );
+    }
+    let hands = hands_vec
+        .into_iter()
+        .filter(|hand| !stored.contains(&hand.hand_info.hand_id))
+        .map(|hand| {
+            let result = hero_result(&hand);
+            (hand, result)
+        })
+        .collect();
+    insert_hands_with_results(conn, hands)
+}
+
+/// Inserts hands along with the result of hero, computed beforehand
+fn insert_hands_with_results(
+    conn: &mut SqliteConnection,
+    hands_vec: Vec<(parser::Hand, PlayerResult)>,
 ) -> Result<u32, DatabaseError> {
     let mut new_actions: Vec<NewAction> = vec![];
     let mut nb_hands = 0;
     conn.transaction::<_, Error, _>(|conn| {
-        for hand in &hands_vec {
+        for (hand, result) in &hands_vec {
+            let hero_cards = &hand.dealt_cards.hole_cards;
+            let tournament = match &hand.hand_info.game_info {
+                parser::GameInfo::Tournament(info) => Some(info),
+                _ => None,
+            };
             let inserted = diesel::insert_or_ignore_into(hands::table)
                 .values(Hand {
                     id: hand.hand_info.hand_id.to_owned(),
-                    hole_card_1: hand.dealt_cards.hole_cards.card1.to_string(),
-                    hole_card_2: hand.dealt_cards.hole_cards.card2.to_string(),
+                    hole_card_1: get_hole_card(hero_cards, 0).unwrap_or_default(),
+                    hole_card_2: get_hole_card(hero_cards, 1).unwrap_or_default(),
                     tournament_id: match &hand.table_info.table_name {
                         parser::TableName::Tournament(_, tournament_id_, _) => {
                             Some(*tournament_id_ as i32)
@@ -78,20 +217,43 @@ pub fn insert_hands(
                         parser::TableName::CashGame(name) => Some(name.to_owned()),
                         _ => None,
                     },
-                    datetime: hand.hand_info.datetime.to_string(),
+                    datetime: hand.hand_info.datetime.naive_utc(),
                     max_players: hand.table_info.max_players as i32,
                     button: hand.table_info.button as i32,
                     hero: hand.dealt_cards.player_name.to_owned(),
-                    ante: hand.hand_info.blinds.ante,
-                    small_blind: hand.hand_info.blinds.small_blind,
-                    big_blind: hand.hand_info.blinds.big_blind,
-                    pot: hand.summary.pot,
-                    rake: hand.summary.rake,
+                    ante: hand.hand_info.blinds.ante.map(Amount::cents),
+                    small_blind: hand.hand_info.blinds.small_blind.cents(),
+                    big_blind: hand.hand_info.blinds.big_blind.cents(),
+                    pot: hand.summary.pot.cents(),
+                    rake: hand.summary.rake.map(Amount::cents),
                     flop1: get_board_card(hand, 0).to_owned(),
                     flop2: get_board_card(hand, 1).to_owned(),
                     flop3: get_board_card(hand, 2).to_owned(),
                     turn: get_board_card(hand, 3).to_owned(),
                     river: get_board_card(hand, 4).to_owned(),
+                    poker_type: hand.hand_info.poker_type.to_string(),
+                    hole_card_3: get_hole_card(hero_cards, 2),
+                    hole_card_4: get_hole_card(hero_cards, 3),
+                    hole_card_5: get_hole_card(hero_cards, 4),
+                    pool_name: match &hand.hand_info.game_info {
+                        parser::GameInfo::HoldUp(pool) => Some(pool.to_owned()),
+                        _ => None,
+                    },
+                    tournament_name: tournament.map(|info| info.name.to_owned()),
+                    tournament_buy_in: tournament.map(|info| info.buy_in.cents()),
+                    tournament_rake: tournament.map(|info| info.rake.cents()),
+                    tournament_level: tournament.map(|info| info.level as i32),
+                    table_number: match &hand.table_info.table_name {
+                        parser::TableName::Tournament(_, _, table_number) => {
+                            Some(*table_number as i32)
+                        }
+                        _ => None,
+                    },
+                    play_money: (hand.table_info.currency == parser::MoneyType::PlayMoney) as i32,
+                    net_result: Some(result.net.cents()),
+                    ev_net_result: Some(result.ev_net.cents()),
+                    site: hand.hand_info.site.to_owned(),
+                    currency: hand.hand_info.currency_code.to_owned(),
                 })
                 .execute(conn)
                 .expect("Error saving new hands");
@@ -99,30 +261,27 @@ pub fn insert_hands(
                 continue;
             }
             nb_hands += 1;
-            for seat in hand.seats.iter() {
-                let summary_player = hand
+            for (seat, position) in hand.seats.iter().zip(hand.positions()) {
+                let shown_cards = hand
                     .summary
                     .players
                     .iter()
-                    .find(|player| player.name == seat.player_name);
-                let (card1, card2) = match summary_player {
-                    Some(player) => match player.hole_cards {
-                        Some(ref cards) => {
-                            (Some(cards.card1.to_string()), Some(cards.card2.to_string()))
-                        }
-                        None => (None, None),
-                    },
-                    None => (None, None),
-                };
+                    .find(|player| player.name == seat.player_name)
+                    .and_then(|player| player.hole_cards.as_ref());
+                let shown_card = |n| shown_cards.and_then(|cards| get_hole_card(cards, n));
                 diesel::insert_or_ignore_into(seats::table)
                     .values(Seat {
                         hand_id: hand.hand_info.hand_id.to_owned(),
                         player_name: seat.player_name.to_owned(),
                         seat_number: seat.seat_number as i32,
-                        stack: seat.stack,
-                        bounty: seat.bounty,
-                        card1,
-                        card2,
+                        stack: seat.stack.cents(),
+                        bounty: seat.bounty.map(Amount::cents),
+                        card1: shown_card(0),
+                        card2: shown_card(1),
+                        card3: shown_card(2),
+                        card4: shown_card(3),
+                        card5: shown_card(4),
+                        position: position.to_string(),
                     })
                     .execute(conn)
                     .expect("Error saving seat");
@@ -131,33 +290,82 @@ pub fn insert_hands(
                 street
                     .actions
                     .iter()
-                    .filter(|action| action.action != ActionType::Shows)
+                    .filter(|action| !matches!(action.action, ActionType::Shows { .. }))
                     .for_each(|action| {
                         new_actions.push(NewAction {
                             hand_id: hand.hand_info.hand_id.to_owned(),
                             player_name: action.player_name.to_owned(),
                             action_type: action.action.to_string(),
                             amount: match action.action {
-                                ActionType::Bet { amount } => Some(amount),
-                                ActionType::Call { amount } => Some(amount),
-                                ActionType::Raise { amount, .. } => Some(amount),
+                                ActionType::Bet { amount } => Some(amount.cents()),
+                                ActionType::Call { amount } => Some(amount.cents()),
+                                ActionType::Raise { amount, .. } => Some(amount.cents()),
+                                ActionType::Collect { amount, .. } => Some(amount.cents()),
+                                ActionType::UncalledBet { amount } => Some(amount.cents()),
+                                ActionType::Post(
+                                    PostType::Ante(amount)
+                                    | PostType::SmallBlind(amount)
+                                    | PostType::BigBlind(amount)
+                                    | PostType::Dead(amount),
+                                ) => Some(amount.cents()),
+                                ActionType::CashoutRisk { amount } => Some(amount.cents()),
+                                ActionType::Cashout { amount } => Some(amount.cents()),
                                 _ => None,
                             },
                             is_all_in: action.is_all_in as i32,
                             street: street.street_type.to_string(),
+                            pot: match &action.action {
+                                ActionType::Collect { pot, .. } => Some(match pot {
+                                    parser::Pot::Main => 0,
+                                    parser::Pot::Side(number) => *number as i32,
+                                }),
+                                _ => None,
+                            },
                         })
                     });
             }
         }
-        diesel::insert_or_ignore_into(actions::table)
-            .values(&new_actions)
-            .execute(conn)
-            .expect("Error saving new hands");
+        // a single statement would go over the number of parameters allowed by SQLite
+        for actions in new_actions.chunks(ACTIONS_PER_INSERT) {
+            diesel::insert_or_ignore_into(actions::table)
+                .values(actions)
+                .execute(conn)
+                .expect("Error saving new hands");
+        }
         Ok(())
     })?;
     Ok(nb_hands)
 }
 
+/// Files already imported, with the size they had
+pub fn get_imported_files(
+    conn: &mut SqliteConnection,
+) -> Result<HashMap<String, i64>, DatabaseError> {
+    Ok(imported_files::table
+        .select((imported_files::path, imported_files::size))
+        .load::<(String, i64)>(conn)?
+        .into_iter()
+        .collect())
+}
+
+/// Inserts the hands of a batch of files and records the files in the same transaction, so
+/// that an interrupted import resumes with the files of the batch
+///
+/// The results of hero are computed beforehand, by the threads parsing the files.
+pub fn import_batch(
+    conn: &mut SqliteConnection,
+    hands: Vec<(parser::Hand, PlayerResult)>,
+    files: &[ImportedFile],
+) -> Result<u32, DatabaseError> {
+    conn.transaction(|conn| {
+        let nb_hands = insert_hands_with_results(conn, hands)?;
+        diesel::replace_into(imported_files::table)
+            .values(files)
+            .execute(conn)?;
+        Ok(nb_hands)
+    })
+}
+
 pub fn get_seats(conn: &mut SqliteConnection, hand_id: &str) -> Result<Vec<Seat>, DatabaseError> {
     let seats = seats::dsl::seats
         .filter(seats::dsl::hand_id.eq(hand_id))
@@ -166,6 +374,296 @@ pub fn get_seats(conn: &mut SqliteConnection, hand_id: &str) -> Result<Vec<Seat>
     Ok(seats)
 }
 
+fn parse_cards<'a>(
+    names: impl IntoIterator<Item = Option<&'a String>>,
+) -> Result<Vec<parser::Card>, DatabaseError> {
+    names
+        .into_iter()
+        .flatten()
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            name.parse()
+                .map_err(|_| DatabaseError::InvalidValue(name.to_owned()))
+        })
+        .collect()
+}
+
+/// Converts a stored action, keeping track of the highest bet of the street to rebuild raises
+fn parse_action(
+    action: &Action,
+    highest_bet: &mut Amount,
+) -> Result<parser::Action, DatabaseError> {
+    let amount = Amount::from_cents(action.amount.unwrap_or_default());
+    let action_type = match action.action_type.as_str() {
+        "bet" => {
+            *highest_bet = amount;
+            ActionType::Bet { amount }
+        }
+        "call" => ActionType::Call { amount },
+        "raise" => {
+            let to_call = amount - *highest_bet;
+            *highest_bet = amount;
+            ActionType::Raise { to_call, amount }
+        }
+        "check" => ActionType::Check,
+        "fold" => ActionType::Fold,
+        "collect" => ActionType::Collect {
+            amount,
+            pot: match action.pot {
+                None | Some(0) => parser::Pot::Main,
+                Some(number) => parser::Pot::Side(number as u32),
+            },
+        },
+        "uncalled-bet" => ActionType::UncalledBet { amount },
+        "cashout-risk" => ActionType::CashoutRisk { amount },
+        "cashout" => ActionType::Cashout { amount },
+        "post-ante" => ActionType::Post(PostType::Ante(amount)),
+        "post-small-blind" => ActionType::Post(PostType::SmallBlind(amount)),
+        "post-big-blind" => ActionType::Post(PostType::BigBlind(amount)),
+        "post-dead" => ActionType::Post(PostType::Dead(amount)),
+        action_type => return Err(DatabaseError::InvalidValue(action_type.to_owned())),
+    };
+    Ok(parser::Action {
+        player_name: action.player_name.to_owned(),
+        action: action_type,
+        is_all_in: action.is_all_in != 0,
+    })
+}
+
+/// Rebuilds a parsed hand from its stored rows, to write it as a hand history
+///
+/// Every seat is listed in the summary. What is not stored is left out: shown hands have no
+/// category and hands run twice have a single board.
+pub fn get_parsed_hand(
+    conn: &mut SqliteConnection,
+    hand_id: &str,
+) -> Result<parser::Hand, DatabaseError> {
+    let hand: Hand = hands::table
+        .find(hand_id)
+        .select(Hand::as_select())
+        .first(conn)?;
+    let mut seats = get_seats(conn, hand_id)?;
+    seats.sort_by_key(|seat| seat.seat_number);
+    let actions: Vec<Action> = actions::table
+        .filter(actions::hand_id.eq(hand_id))
+        .order(actions::id)
+        .select(Action::as_select())
+        .load(conn)?;
+
+    let invalid = |value: &str| DatabaseError::InvalidValue(value.to_owned());
+    let poker_type = [
+        parser::PokerType::HoldemNoLimit,
+        parser::PokerType::OmahaPotLimit,
+        parser::PokerType::Omaha5PotLimit,
+    ]
+    .into_iter()
+    .find(|poker_type| poker_type.to_string() == hand.poker_type)
+    .ok_or_else(|| invalid(&hand.poker_type))?;
+    let datetime = hand.datetime.and_utc();
+    let (game_info, table_name) = match hand.tournament_id {
+        Some(tournament_id) => {
+            let name = hand
+                .tournament_name
+                .clone()
+                .unwrap_or_else(|| tournament_id.to_string());
+            let info = parser::TournamentInfo {
+                name: name.clone(),
+                buy_in: Amount::from_cents(hand.tournament_buy_in.unwrap_or_default()),
+                rake: Amount::from_cents(hand.tournament_rake.unwrap_or_default()),
+                // the level of hands stored before it was kept is unknown
+                level: hand.tournament_level.unwrap_or(1) as u32,
+            };
+            (
+                parser::GameInfo::Tournament(info),
+                parser::TableName::Tournament(
+                    name,
+                    tournament_id as u32,
+                    hand.table_number.unwrap_or_default() as u32,
+                ),
+            )
+        }
+        None => (
+            match &hand.pool_name {
+                Some(pool) => parser::GameInfo::HoldUp(pool.to_owned()),
+                None => parser::GameInfo::CashGame,
+            },
+            parser::TableName::CashGame(hand.cash_game_name.clone().unwrap_or_default()),
+        ),
+    };
+
+    let mut shown_cards = vec![];
+    for seat in &seats {
+        let cards = parse_cards(
+            [
+                &seat.card1,
+                &seat.card2,
+                &seat.card3,
+                &seat.card4,
+                &seat.card5,
+            ]
+            .map(Option::as_ref),
+        )?;
+        shown_cards.push((!cards.is_empty()).then_some(parser::HoleCards { cards }));
+    }
+    let board = parse_cards(
+        [
+            &hand.flop1,
+            &hand.flop2,
+            &hand.flop3,
+            &hand.turn,
+            &hand.river,
+        ]
+        .map(Option::as_ref),
+    )?;
+    let mut streets = vec![];
+    for (street_type, cards) in [
+        (parser::StreetType::Preflop, 0..0),
+        (parser::StreetType::Flop, 0..3),
+        (parser::StreetType::Turn, 3..4),
+        (parser::StreetType::River, 4..5),
+        (parser::StreetType::Showdown, 5..5),
+    ] {
+        let name = street_type.to_string();
+        let street_actions: Vec<&Action> = actions
+            .iter()
+            .filter(|action| action.street == name)
+            .collect();
+        let reached = match street_type {
+            parser::StreetType::Preflop => true,
+            parser::StreetType::Showdown => {
+                !street_actions.is_empty() || shown_cards.iter().any(Option::is_some)
+            }
+            _ => board.len() >= cards.end,
+        };
+        if !reached {
+            continue;
+        }
+        let mut street = parser::Street {
+            cards: board.get(cards).unwrap_or_default().to_vec(),
+            actions: vec![],
+            street_type,
+        };
+        let mut highest_bet = match street.street_type {
+            parser::StreetType::Preflop => Amount::from_cents(hand.big_blind),
+            _ => Amount::ZERO,
+        };
+        if street.street_type == parser::StreetType::Showdown {
+            for (seat, cards) in seats.iter().zip(&shown_cards) {
+                if let Some(cards) = cards {
+                    street.actions.push(parser::Action {
+                        player_name: seat.player_name.to_owned(),
+                        action: ActionType::Shows {
+                            cards: cards.clone(),
+                        },
+                        is_all_in: false,
+                    });
+                }
+            }
+        }
+        for action in street_actions {
+            street.actions.push(parse_action(action, &mut highest_bet)?);
+        }
+        streets.push(street);
+    }
+
+    let players = seats
+        .iter()
+        .zip(shown_cards)
+        .map(|(seat, hole_cards)| {
+            let won: Amount = actions
+                .iter()
+                .filter(|action| {
+                    action.player_name == seat.player_name && action.action_type == "collect"
+                })
+                .map(|action| Amount::from_cents(action.amount.unwrap_or_default()))
+                .sum();
+            parser::SummaryPlayer {
+                name: seat.player_name.to_owned(),
+                seat: seat.seat_number as u32,
+                hole_cards,
+                result: match won > Amount::ZERO {
+                    true => parser::SummaryResult::Won(won),
+                    false => parser::SummaryResult::Lost,
+                },
+                hand_category: None,
+            }
+        })
+        .collect();
+    let boards = match board.is_empty() {
+        true => vec![],
+        false => {
+            let mut cards: Vec<Option<parser::Card>> = board.iter().cloned().map(Some).collect();
+            cards.resize_with(5, || None);
+            vec![parser::Board { cards }]
+        }
+    };
+    Ok(parser::Hand {
+        hand_info: parser::HandInfo {
+            game_info,
+            hand_id: hand.id.to_owned(),
+            poker_type,
+            blinds: parser::Blinds {
+                ante: hand.ante.map(Amount::from_cents),
+                small_blind: Amount::from_cents(hand.small_blind),
+                big_blind: Amount::from_cents(hand.big_blind),
+            },
+            datetime,
+            site: hand.site.to_owned(),
+            currency_code: hand.currency.to_owned(),
+        },
+        table_info: parser::TableInfo {
+            table_name,
+            max_players: hand.max_players as u32,
+            currency: match hand.play_money {
+                0 => parser::MoneyType::RealMoney,
+                _ => parser::MoneyType::PlayMoney,
+            },
+            button: hand.button as u32,
+        },
+        seats: seats
+            .iter()
+            .map(|seat| parser::Seat {
+                seat_number: seat.seat_number as u32,
+                player_name: seat.player_name.to_owned(),
+                stack: Amount::from_cents(seat.stack),
+                bounty: seat.bounty.map(Amount::from_cents),
+            })
+            .collect(),
+        dealt_cards: parser::DealtToHero {
+            player_name: hand.hero.to_owned(),
+            hole_cards: parser::HoleCards {
+                cards: parse_cards([
+                    Some(&hand.hole_card_1),
+                    Some(&hand.hole_card_2),
+                    hand.hole_card_3.as_ref(),
+                    hand.hole_card_4.as_ref(),
+                    hand.hole_card_5.as_ref(),
+                ])?,
+            },
+        },
+        streets,
+        summary: parser::Summary {
+            pot: Amount::from_cents(hand.pot),
+            rake: hand.rake.map(Amount::from_cents),
+            players,
+            boards,
+        },
+    })
+}
+
+/// Writes stored hands as a hand history of the site of `writer`
+pub fn export_hands(
+    conn: &mut SqliteConnection,
+    hand_ids: &[String],
+    writer: &dyn SiteWriter,
+) -> Result<String, DatabaseError> {
+    let hands = hand_ids
+        .iter()
+        .map(|hand_id| get_parsed_hand(conn, hand_id))
+        .collect::<Result<Vec<parser::Hand>, DatabaseError>>()?;
+    Ok(writer.write_hands(&hands))
+}
+
 pub fn get_hands(conn: &mut SqliteConnection) -> Result<Vec<Hand>, DatabaseError> {
     Ok(hands::dsl::hands
         .select(Hand::as_select())
@@ -173,6 +671,182 @@ pub fn get_hands(conn: &mut SqliteConnection) -> Result<Vec<Hand>, DatabaseError
         .load(conn)?)
 }
 
+/// Longest break between two hands of the same session, in minutes
+pub const SESSION_BREAK: i64 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    /// Day on which the session started, in the timezone of the user
+    pub day: NaiveDate,
+    pub start: DateTime<Utc>,
+    /// Date of the last hand
+    pub end: DateTime<Utc>,
+    pub nb_hands: u32,
+    /// Result of hero in the real money cash games of the session
+    #[serde(with = "crate::amounts")]
+    pub net: i64,
+    /// Result of hero in the real money cash games, with the pots of their all-ins shared by
+    /// equity
+    #[serde(with = "crate::amounts")]
+    pub ev_net: i64,
+}
+
+/// Groups the hands into sessions, dated in `timezone`
+///
+/// Breaks are measured between UTC instants, so that a session going past midnight or through
+/// a daylight saving time change stays in one piece.
+pub fn get_sessions<Tz: TimeZone>(
+    conn: &mut SqliteConnection,
+    timezone: &Tz,
+) -> Result<Vec<Session>, DatabaseError> {
+    let hands: Vec<(NaiveDateTime, bool, Option<i64>, Option<i64>)> = hands::dsl::hands
+        .select((
+            hands::datetime,
+            hands::tournament_id.is_null().and(hands::play_money.eq(0)),
+            hands::net_result,
+            hands::ev_net_result,
+        ))
+        .order(hands::datetime.asc())
+        .load(conn)?;
+    let mut sessions: Vec<Session> = vec![];
+    for (datetime, cash_game, net, ev_net) in hands {
+        let datetime = datetime.and_utc();
+        let session = match sessions.last_mut() {
+            Some(session) if datetime - session.end <= Duration::minutes(SESSION_BREAK) => {
+                session.end = datetime;
+                session.nb_hands += 1;
+                session
+            }
+            _ => {
+                sessions.push(Session {
+                    day: datetime.with_timezone(timezone).date_naive(),
+                    start: datetime,
+                    end: datetime,
+                    nb_hands: 1,
+                    net: 0,
+                    ev_net: 0,
+                });
+                sessions.last_mut().unwrap()
+            }
+        };
+        if cash_game {
+            session.net += net.unwrap_or_default();
+            session.ev_net += ev_net.unwrap_or_default();
+        }
+    }
+    Ok(sessions)
+}
+
+/// Hands whose results are computed in one transaction by `update_hand_results`
+const RESULTS_PER_TRANSACTION: usize = 500;
+
+/// Computes the results of hero in the hands stored before they were kept, rebuilding them from
+/// their stored rows in transactions of `RESULTS_PER_TRANSACTION` hands
+///
+/// Hands that cannot be rebuilt keep no result and are reported to `skipped`.
+pub fn update_hand_results(
+    conn: &mut SqliteConnection,
+    mut skipped: impl FnMut(&str, DatabaseError),
+) -> Result<u32, DatabaseError> {
+    let hand_ids: Vec<String> = hands::table
+        .filter(hands::net_result.is_null())
+        .select(hands::id)
+        .load(conn)?;
+    let mut nb_hands = 0;
+    for hand_ids in hand_ids.chunks(RESULTS_PER_TRANSACTION) {
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            for hand_id in hand_ids {
+                let hand = match get_parsed_hand(conn, hand_id) {
+                    Ok(hand) => hand,
+                    Err(error) => {
+                        skipped(hand_id, error);
+                        continue;
+                    }
+                };
+                let result = hero_result(&hand);
+                diesel::update(hands::table.find(hand_id))
+                    .set((
+                        hands::net_result.eq(result.net.cents()),
+                        hands::ev_net_result.eq(result.ev_net.cents()),
+                    ))
+                    .execute(conn)?;
+                nb_hands += 1;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(nb_hands)
+}
+
+/// Result of hero in a hand, in money or in tournament chips
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HandResult {
+    pub hand_id: String,
+    pub datetime: DateTime<Utc>,
+    #[serde(with = "crate::amounts")]
+    pub net: i64,
+    /// Result with the pots of the all-ins of hero shared by equity
+    #[serde(with = "crate::amounts")]
+    pub ev_net: i64,
+}
+
+/// Results of hero hand by hand, oldest first, in the real money cash games played in
+/// `currency`, amounts of different currencies not adding up
+///
+/// Hands stored before the results were kept count for nothing until `update_hand_results`.
+pub fn get_cash_game_results(
+    conn: &mut SqliteConnection,
+    currency: &str,
+) -> Result<Vec<HandResult>, DatabaseError> {
+    load_results(
+        conn,
+        hands::table
+            .filter(hands::tournament_id.is_null())
+            .filter(hands::play_money.eq(0))
+            .filter(hands::currency.eq(currency.to_owned()))
+            .into_boxed(),
+    )
+}
+
+/// Results of hero hand by hand, oldest first, in the tournament `tournament_id`
+///
+/// Hands stored before the results were kept count for nothing until `update_hand_results`.
+pub fn get_tournament_results(
+    conn: &mut SqliteConnection,
+    tournament_id: i32,
+) -> Result<Vec<HandResult>, DatabaseError> {
+    load_results(
+        conn,
+        hands::table
+            .filter(hands::tournament_id.eq(tournament_id))
+            .into_boxed(),
+    )
+}
+
+fn load_results(
+    conn: &mut SqliteConnection,
+    query: hands::BoxedQuery<'_, Sqlite>,
+) -> Result<Vec<HandResult>, DatabaseError> {
+    let results: Vec<(String, NaiveDateTime, Option<i64>, Option<i64>)> = query
+        .select((
+            hands::id,
+            hands::datetime,
+            hands::net_result,
+            hands::ev_net_result,
+        ))
+        .order(hands::datetime.asc())
+        .load(conn)?;
+    Ok(results
+        .into_iter()
+        .map(|(hand_id, datetime, net, ev_net)| HandResult {
+            hand_id,
+            datetime: datetime.and_utc(),
+            net: net.unwrap_or_default(),
+            ev_net: ev_net.unwrap_or_default(),
+        })
+        .collect())
+}
+
 pub fn get_hands_for_tournament(
     conn: &mut SqliteConnection,
     id: i32,
@@ -205,6 +879,81 @@ pub fn get_hands_for_player(
     Ok(hand_actions)
 }
 
+/// A fast-fold player pool at given stakes, whose hands are grouped regardless of the table
+#[derive(Serialize, Deserialize, Debug, Clone, PartialOrd, PartialEq)]
+pub struct Pool {
+    pub name: String,
+    pub poker_type: String,
+    #[serde(with = "crate::amounts")]
+    pub small_blind: i64,
+    #[serde(with = "crate::amounts")]
+    pub big_blind: i64,
+}
+
+pub fn get_pools(conn: &mut SqliteConnection) -> Result<Vec<Pool>, DatabaseError> {
+    let pools: Vec<(Option<String>, String, i64, i64)> = hands::table
+        .filter(hands::pool_name.is_not_null())
+        .select((
+            hands::pool_name,
+            hands::poker_type,
+            hands::small_blind,
+            hands::big_blind,
+        ))
+        .distinct()
+        .order((hands::pool_name, hands::big_blind))
+        .load(conn)?;
+    Ok(pools
+        .into_iter()
+        .filter_map(|(name, poker_type, small_blind, big_blind)| {
+            Some(Pool {
+                name: name?,
+                poker_type,
+                small_blind,
+                big_blind,
+            })
+        })
+        .collect())
+}
+
+pub fn get_hands_for_pool(
+    conn: &mut SqliteConnection,
+    pool: &Pool,
+) -> Result<Vec<Hand>, DatabaseError> {
+    Ok(hands::dsl::hands
+        .filter(hands::pool_name.eq(&pool.name))
+        .filter(hands::poker_type.eq(&pool.poker_type))
+        .filter(hands::small_blind.eq(pool.small_blind))
+        .filter(hands::big_blind.eq(pool.big_blind))
+        .select(Hand::as_select())
+        .order(hands::datetime.desc())
+        .load(conn)?)
+}
+
+pub fn get_hands_for_player_in_pool(
+    conn: &mut SqliteConnection,
+    player_name: &str,
+    pool: &Pool,
+) -> Result<Vec<(Hand, Vec<Action>)>, DatabaseError> {
+    let hands = hands::dsl::hands
+        .inner_join(seats::dsl::seats)
+        .filter(seats::dsl::player_name.eq(player_name))
+        .filter(hands::pool_name.eq(&pool.name))
+        .filter(hands::poker_type.eq(&pool.poker_type))
+        .filter(hands::small_blind.eq(pool.small_blind))
+        .filter(hands::big_blind.eq(pool.big_blind))
+        .select(Hand::as_select())
+        .load(conn)?;
+    let actions = Action::belonging_to(&hands)
+        .select(Action::as_select())
+        .load(conn)?;
+    Ok(actions
+        .grouped_by(&hands)
+        .into_iter()
+        .zip(hands)
+        .map(|(actions, hand)| (hand, actions))
+        .collect())
+}
+
 pub fn get_latest_hand(
     conn: &mut SqliteConnection,
     tournament_id: Option<u32>,
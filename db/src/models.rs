@@ -9,14 +9,29 @@ pub struct Summary {
     pub id: i32,
     pub name: String,
     pub finish_place: i32,
-    pub buyin: f64,
+    #[serde(serialize_with = "crate::amounts::serialize")]
+    pub buyin: i64,
+    #[serde(serialize_with = "crate::timestamps::serialize")]
     pub date: NaiveDateTime,
     pub play_time: String,
     pub entries: i32,
     pub mode: String,
     pub tournament_type: String,
     pub speed: String,
-    pub won: Option<f64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub won: Option<i64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub prizepool: Option<i64>,
+    pub multiplier: Option<f64>,
+    pub nb_entries: i32,
+    pub rebuys: i32,
+    pub addons: i32,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub total_cost: Option<i64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub bounties_won: Option<i64>,
+    /// Names of the tickets won, one per line
+    pub tickets: Option<String>,
 }
 
 #[derive(Identifiable, Insertable, Queryable, Selectable, Debug, Serialize, Clone)]
@@ -29,20 +44,48 @@ pub struct Hand {
     pub hole_card_2: String,
     pub tournament_id: Option<i32>,
     pub cash_game_name: Option<String>,
-    pub datetime: String,
+    #[serde(serialize_with = "crate::timestamps::serialize")]
+    pub datetime: NaiveDateTime,
     pub button: i32,
     pub max_players: i32,
     pub hero: String,
-    pub ante: Option<f64>,
-    pub small_blind: f64,
-    pub big_blind: f64,
-    pub pot: f64,
-    pub rake: Option<f64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub ante: Option<i64>,
+    #[serde(serialize_with = "crate::amounts::serialize")]
+    pub small_blind: i64,
+    #[serde(serialize_with = "crate::amounts::serialize")]
+    pub big_blind: i64,
+    #[serde(serialize_with = "crate::amounts::serialize")]
+    pub pot: i64,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub rake: Option<i64>,
     pub flop1: Option<String>,
     pub flop2: Option<String>,
     pub flop3: Option<String>,
     pub turn: Option<String>,
     pub river: Option<String>,
+    pub poker_type: String,
+    pub hole_card_3: Option<String>,
+    pub hole_card_4: Option<String>,
+    pub hole_card_5: Option<String>,
+    pub pool_name: Option<String>,
+    pub tournament_name: Option<String>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub tournament_buy_in: Option<i64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub tournament_rake: Option<i64>,
+    pub tournament_level: Option<i32>,
+    pub table_number: Option<i32>,
+    pub play_money: i32,
+    /// Chips or money won by hero, `None` for hands stored before results were kept
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub net_result: Option<i64>,
+    /// Result of hero with the pots of their all-ins shared by equity
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub ev_net_result: Option<i64>,
+    pub site: String,
+    /// Code of the currency of the real money amounts, as in "EUR"
+    pub currency: String,
 }
 
 #[derive(Identifiable, Queryable, Selectable, Associations, Debug, Serialize)]
@@ -55,9 +98,12 @@ pub struct Action {
     pub hand_id: String,
     pub player_name: String,
     pub action_type: String,
-    pub amount: Option<f64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub amount: Option<i64>,
     pub is_all_in: i32,
     pub street: String,
+    /// Pot of a collect, 0 for the main pot and the number of a side pot otherwise
+    pub pot: Option<i32>,
 }
 
 #[derive(Insertable, Debug, Serialize)]
@@ -68,9 +114,12 @@ pub struct NewAction {
     pub hand_id: String,
     pub player_name: String,
     pub action_type: String,
-    pub amount: Option<f64>,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub amount: Option<i64>,
     pub is_all_in: i32,
     pub street: String,
+    /// Pot of a collect, 0 for the main pot and the number of a side pot otherwise
+    pub pot: Option<i32>,
 }
 
 #[derive(Identifiable, Insertable, Queryable, Selectable, Associations, Debug, Serialize)]
@@ -83,8 +132,24 @@ pub struct Seat {
     pub hand_id: String,
     pub player_name: String,
     pub seat_number: i32,
-    pub stack: f64,
-    pub bounty: Option<f64>,
+    #[serde(serialize_with = "crate::amounts::serialize")]
+    pub stack: i64,
+    #[serde(serialize_with = "crate::amounts::option::serialize")]
+    pub bounty: Option<i64>,
     pub card1: Option<String>,
     pub card2: Option<String>,
+    pub card3: Option<String>,
+    pub card4: Option<String>,
+    pub card5: Option<String>,
+    /// Position at the table, as "BTN", "SB", "BB", "UTG" or "CO"
+    pub position: String,
+}
+
+/// File read by a bulk import, skipped by the next imports until its size changes
+#[derive(Insertable, Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = crate::schema::imported_files)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ImportedFile {
+    pub path: String,
+    pub size: i64,
 }
@@ -6,9 +6,10 @@ diesel::table! {
         hand_id -> Text,
         player_name -> Text,
         action_type -> Text,
-        amount -> Nullable<Double>,
+        amount -> Nullable<BigInt>,
         is_all_in -> Integer,
         street -> Text,
+        pot -> Nullable<Integer>,
     }
 }
 
@@ -19,20 +20,42 @@ diesel::table! {
         hole_card_2 -> Text,
         tournament_id -> Nullable<Integer>,
         cash_game_name -> Nullable<Text>,
-        datetime -> Text,
+        datetime -> Timestamp,
         button -> Integer,
         max_players -> Integer,
         hero -> Text,
-        ante -> Nullable<Double>,
-        small_blind -> Double,
-        big_blind -> Double,
-        pot -> Double,
-        rake -> Nullable<Double>,
+        ante -> Nullable<BigInt>,
+        small_blind -> BigInt,
+        big_blind -> BigInt,
+        pot -> BigInt,
+        rake -> Nullable<BigInt>,
         flop1 -> Nullable<Text>,
         flop2 -> Nullable<Text>,
         flop3 -> Nullable<Text>,
         turn -> Nullable<Text>,
         river -> Nullable<Text>,
+        poker_type -> Text,
+        hole_card_3 -> Nullable<Text>,
+        hole_card_4 -> Nullable<Text>,
+        hole_card_5 -> Nullable<Text>,
+        pool_name -> Nullable<Text>,
+        tournament_name -> Nullable<Text>,
+        tournament_buy_in -> Nullable<BigInt>,
+        tournament_rake -> Nullable<BigInt>,
+        tournament_level -> Nullable<Integer>,
+        table_number -> Nullable<Integer>,
+        play_money -> Integer,
+        net_result -> Nullable<BigInt>,
+        ev_net_result -> Nullable<BigInt>,
+        site -> Text,
+        currency -> Text,
+    }
+}
+
+diesel::table! {
+    imported_files (path) {
+        path -> Text,
+        size -> BigInt,
     }
 }
 
@@ -41,10 +64,14 @@ diesel::table! {
         hand_id -> Text,
         player_name -> Text,
         seat_number -> Integer,
-        stack -> Double,
-        bounty -> Nullable<Double>,
+        stack -> BigInt,
+        bounty -> Nullable<BigInt>,
         card1 -> Nullable<Text>,
         card2 -> Nullable<Text>,
+        card3 -> Nullable<Text>,
+        card4 -> Nullable<Text>,
+        card5 -> Nullable<Text>,
+        position -> Text,
     }
 }
 
@@ -52,7 +79,7 @@ diesel::table! {
     summaries (id) {
         id -> Integer,
         name -> Text,
-        buyin -> Double,
+        buyin -> BigInt,
         date -> Timestamp,
         play_time -> Text,
         entries -> Integer,
@@ -60,7 +87,15 @@ diesel::table! {
         tournament_type -> Text,
         speed -> Text,
         finish_place -> Integer,
-        won -> Nullable<Double>,
+        won -> Nullable<BigInt>,
+        prizepool -> Nullable<BigInt>,
+        multiplier -> Nullable<Double>,
+        nb_entries -> Integer,
+        rebuys -> Integer,
+        addons -> Integer,
+        total_cost -> Nullable<BigInt>,
+        bounties_won -> Nullable<BigInt>,
+        tickets -> Nullable<Text>,
     }
 }
 
@@ -70,6 +105,7 @@ diesel::joinable!(seats -> hands (hand_id));
 diesel::allow_tables_to_appear_in_same_query!(
     actions,
     hands,
+    imported_files,
     seats,
     summaries,
 );
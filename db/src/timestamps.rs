@@ -0,0 +1,15 @@
+//! Dates are stored as naive UTC timestamps and sent to the frontend as RFC 3339 instants
+
+use chrono::{NaiveDateTime, SecondsFormat};
+use serde::Serializer;
+
+pub fn serialize<S: Serializer>(
+    datetime: &NaiveDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(
+        &datetime
+            .and_utc()
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+}
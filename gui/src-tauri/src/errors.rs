@@ -1,4 +1,5 @@
 use holdem_suite_db::errors::DatabaseError;
+use holdem_suite_parser::openhh::OpenHhError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ApplicationError {
@@ -40,7 +41,12 @@ pub enum ApplicationError {
     // database errors
     #[error(transparent)]
     Database(#[from] DatabaseError),
+    // parser errors
+    #[error(transparent)]
+    OpenHh(#[from] OpenHhError),
     // other errors
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     #[error("Error loading players for table")]
     LoadPlayersForTable,
     #[error("Error getting table max players")]
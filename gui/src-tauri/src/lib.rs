@@ -17,8 +17,10 @@ use serde::{Deserialize, Serialize};
 
 use holdem_suite_db::models::Action;
 use holdem_suite_db::{get_latest_hand, insert_hands, insert_summary};
-use holdem_suite_parser::parser::parse_hands;
-use holdem_suite_parser::summary_parser::TournamentSummary;
+use holdem_suite_parser::incremental::IncrementalParser;
+use holdem_suite_parser::openhh;
+use holdem_suite_parser::parser::parse_hands_recovering;
+use holdem_suite_parser::summary_parser::parse_summary;
 
 use crate::errors::ApplicationError;
 
@@ -132,14 +134,14 @@ pub fn parse_file(
     let path_str = path_cloned.to_str().unwrap();
     if path.clone().to_str().unwrap().contains("summary") {
         let data = fs::read_to_string(path).expect("Unable to read file");
-        let parse_result = TournamentSummary::parse(&data);
+        let parse_result = parse_summary(&data);
         match parse_result {
-            Ok((_, summary)) => {
+            Ok(summary) => {
                 let _ = insert_summary(connection, summary)?;
                 Ok(0)
             }
-            Err(_) => {
-                println!("Error parsing {}", path_str);
+            Err(e) => {
+                println!("Error parsing {}: {}", path_str, e);
                 Ok(0)
             }
         }
@@ -147,18 +149,51 @@ pub fn parse_file(
         println!("Parsing {}", path_str);
         let data = fs::read_to_string(path).expect("Unable to read file");
         let start = Instant::now();
-        let parse_result = parse_hands(&data);
-        match parse_result {
-            Ok((_, hands)) => {
-                let nb_hands = insert_hands(connection, hands)?;
-                println!("Parsed {} hands in {:?}", nb_hands, start.elapsed());
-                Ok(nb_hands)
+        let hands = if openhh::is_openhh(&data) {
+            let (hands, report) = openhh::parse_hands_recovering(&data);
+            for skipped in report.skipped {
+                println!("Skipped hand in {}: {}", path_str, skipped.error);
             }
-            Err(_) => {
-                println!("Error parsing {}", path_str);
-                Ok(0)
+            hands
+        } else {
+            let (hands, report) = parse_hands_recovering(&data);
+            for skipped in report.skipped {
+                println!("Skipped hand in {}: {}", path_str, skipped.error);
             }
-        }
+            hands
+        };
+        let nb_hands = insert_hands(connection, hands)?;
+        println!("Parsed {} hands in {:?}", nb_hands, start.elapsed());
+        Ok(nb_hands)
+    }
+}
+
+pub fn parse_new_hands(
+    path: PathBuf,
+    parser: &mut IncrementalParser,
+    connection: &mut SqliteConnection,
+) -> Result<u32, ApplicationError> {
+    let start = Instant::now();
+    let (hands, report) = parser.read(&path)?;
+    for skipped in report.skipped {
+        println!("Skipped hand in {}: {}", path.display(), skipped.error);
+    }
+    let nb_hands = insert_hands(connection, hands)?;
+    println!("Parsed {} new hands in {:?}", nb_hands, start.elapsed());
+    Ok(nb_hands)
+}
+
+/// Parses a file reported by the watcher, only parsing the hands appended since the last call.
+pub fn parse_modified_file(
+    path: PathBuf,
+    parsers: &mut HashMap<PathBuf, IncrementalParser>,
+    connection: &mut SqliteConnection,
+) -> Result<u32, ApplicationError> {
+    if path.to_str().unwrap().contains("summary") {
+        parse_file(path, connection)
+    } else {
+        let parser = parsers.entry(path.clone()).or_default();
+        parse_new_hands(path, parser, connection)
     }
 }
 
@@ -192,7 +227,8 @@ mod tests {
                 player_name: "Player 1".to_owned(),
                 street: "preflop".to_owned(),
                 action_type: "raise".to_owned(),
-                amount: Some(1.0),
+                amount: Some(100),
+                pot: None,
             },
             Action {
                 id: 1,
@@ -201,7 +237,8 @@ mod tests {
                 player_name: "Player 2".to_owned(),
                 street: "preflop".to_owned(),
                 action_type: "call".to_owned(),
-                amount: Some(1.0),
+                amount: Some(100),
+                pot: None,
             },
             Action {
                 id: 2,
@@ -211,6 +248,7 @@ mod tests {
                 street: "preflop".to_owned(),
                 action_type: "fold".to_owned(),
                 amount: None,
+                pot: None,
             },
             Action {
                 id: 3,
@@ -219,7 +257,8 @@ mod tests {
                 player_name: "Player 4".to_owned(),
                 street: "preflop".to_owned(),
                 action_type: "raise".to_owned(),
-                amount: Some(3.0),
+                amount: Some(300),
+                pot: None,
             },
             Action {
                 id: 4,
@@ -229,6 +268,7 @@ mod tests {
                 street: "preflop".to_owned(),
                 action_type: "fold".to_owned(),
                 amount: None,
+                pot: None,
             },
             Action {
                 id: 5,
@@ -238,6 +278,7 @@ mod tests {
                 street: "preflop".to_owned(),
                 action_type: "fold".to_owned(),
                 amount: None,
+                pot: None,
             },
         ];
         let metrics = compute_hand_metrics(actions);
@@ -6,6 +6,7 @@ use std::path::Path;
 use std::sync::mpsc;
 use std::thread;
 
+use chrono::Local;
 use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use tauri::{App, AppHandle, CustomMenuItem, Manager, Menu, Submenu, WindowBuilder};
@@ -13,12 +14,14 @@ use uuid::Uuid;
 
 use gui::errors::ApplicationError;
 use gui::window_management::{TableWindow, WindowGeometry, WindowManager};
-use gui::{compute_hand_metrics, parse_file, Table};
+use gui::{compute_hand_metrics, parse_modified_file, Table};
 use holdem_suite_db::models::{Action, Hand, Seat, Summary};
 use holdem_suite_db::{
-    establish_connection, get_actions, get_actions_for_hand, get_hands, get_hands_for_player,
-    get_hands_for_tournament, get_latest_hand, get_players, get_players_for_table, get_seats,
-    get_summaries, Player, TablePlayer,
+    establish_connection, get_actions, get_actions_for_hand, get_cash_game_results,
+    get_expresso_results, get_hands, get_hands_for_player, get_hands_for_player_in_pool,
+    get_hands_for_pool, get_hands_for_tournament, get_latest_hand, get_players,
+    get_players_for_table, get_pools, get_seats, get_sessions, get_summaries,
+    get_tournament_results, ExpressoResults, HandResult, Player, Pool, Session, TablePlayer,
 };
 
 #[derive(Clone, Deserialize, Serialize, Default)]
@@ -122,12 +125,62 @@ fn load_summaries(state: tauri::State<Settings>) -> Result<Vec<Summary>, Applica
     Ok(get_summaries(&mut conn)?)
 }
 
+#[tauri::command]
+fn load_expresso_results(
+    state: tauri::State<Settings>,
+) -> Result<Vec<ExpressoResults>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_expresso_results(&mut conn)?)
+}
+
+/// Sessions of the user, dated in their local timezone
+#[tauri::command]
+fn load_sessions(state: tauri::State<Settings>) -> Result<Vec<Session>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_sessions(&mut conn, &Local)?)
+}
+
+/// Results of hero hand by hand, in the cash games played in a currency
+#[tauri::command]
+fn load_cash_game_results(
+    state: tauri::State<Settings>,
+    currency: String,
+) -> Result<Vec<HandResult>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_cash_game_results(&mut conn, &currency)?)
+}
+
+/// Results of hero hand by hand, in a tournament
+#[tauri::command]
+fn load_tournament_results(
+    state: tauri::State<Settings>,
+    tournament_id: i32,
+) -> Result<Vec<HandResult>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_tournament_results(&mut conn, tournament_id)?)
+}
+
 #[tauri::command]
 fn load_hands(state: tauri::State<Settings>) -> Result<Vec<Hand>, ApplicationError> {
     let mut conn = establish_connection(&state.database_url);
     Ok(get_hands(&mut conn)?)
 }
 
+#[tauri::command]
+fn load_pools(state: tauri::State<Settings>) -> Result<Vec<Pool>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_pools(&mut conn)?)
+}
+
+#[tauri::command]
+fn load_hands_for_pool(
+    pool: Pool,
+    state: tauri::State<Settings>,
+) -> Result<Vec<Hand>, ApplicationError> {
+    let mut conn = establish_connection(&state.database_url);
+    Ok(get_hands_for_pool(&mut conn, &pool)?)
+}
+
 #[tauri::command]
 fn load_seats(hand_id: &str, state: tauri::State<Settings>) -> Result<Vec<Seat>, ApplicationError> {
     let mut conn = establish_connection(&state.database_url);
@@ -162,6 +215,7 @@ struct PlayerStats {
 #[tauri::command]
 fn load_player_stats(
     player_name: String,
+    pool: Option<Pool>,
     state: tauri::State<Settings>,
 ) -> Result<PlayerStats, ApplicationError> {
     let mut conn = establish_connection(&state.database_url);
@@ -172,7 +226,10 @@ fn load_player_stats(
         open_limp: 0.0,
         nb_hands: 0,
     };
-    let hands_actions = get_hands_for_player(&mut conn, player_name.as_str())?;
+    let hands_actions = match pool {
+        Some(pool) => get_hands_for_player_in_pool(&mut conn, player_name.as_str(), &pool)?,
+        None => get_hands_for_player(&mut conn, player_name.as_str())?,
+    };
     let nb_hands = hands_actions.len();
     for (_, actions) in hands_actions {
         let metrics = compute_hand_metrics(actions);
@@ -274,12 +331,15 @@ fn watch<P: AsRef<Path>>(
     let mut watcher = RecommendedWatcher::new(tx, Config::default()).unwrap();
     let _ = watcher.watch(path.as_ref(), RecursiveMode::Recursive);
     let mut connection = establish_connection(&database_url);
+    let mut parsers = HashMap::new();
     for res in rx {
         match res {
             Ok(event) => match event.kind {
                 EventKind::Create(_) => {
                     println!("created file: {:?}", event.paths[0]);
-                    let _ = parse_file(event.paths[0].clone(), &mut connection).unwrap();
+                    let _ =
+                        parse_modified_file(event.paths[0].clone(), &mut parsers, &mut connection)
+                            .unwrap();
                     app_handle
                         .emit_all(
                             "watcher",
@@ -293,7 +353,8 @@ fn watch<P: AsRef<Path>>(
                 EventKind::Modify(_) => {
                     let path = event.paths[0].clone();
                     println!("modified file: {:?}", path);
-                    let _ = parse_file(path.clone(), &mut connection).unwrap();
+                    let _ =
+                        parse_modified_file(path.clone(), &mut parsers, &mut connection).unwrap();
                     app_handle
                         .emit_all(
                             "watcher",
@@ -697,7 +758,13 @@ fn main() {
             close_splashscreen,
             detect_tables,
             load_summaries,
+            load_expresso_results,
             load_hands,
+            load_sessions,
+            load_cash_game_results,
+            load_tournament_results,
+            load_pools,
+            load_hands_for_pool,
             get_latest_actions,
             load_players,
             load_players_for_table,
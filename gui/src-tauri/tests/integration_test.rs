@@ -7,9 +7,20 @@ use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use gui::get_table_max_players_and_hero;
 use gui::parse_file;
 use gui::Table;
-use holdem_suite_db::models::{Hand, Summary};
-use holdem_suite_db::schema::hands;
-use holdem_suite_db::{establish_connection, get_hands, get_summaries};
+use holdem_suite_db::models::{Hand, ImportedFile, Summary};
+use holdem_suite_db::schema::{hands, summaries};
+use holdem_suite_db::{
+    establish_connection, export_hands, get_cash_game_results, get_hands, get_imported_files,
+    get_parsed_hand, get_sessions, get_summaries, get_tournament_results, hero_result,
+    import_batch, insert_hands, update_hand_results, Session,
+};
+use holdem_suite_parser::ggpoker::GGPokerParser;
+use holdem_suite_parser::openhh::{self, OpenHhWriter};
+use holdem_suite_parser::parser::{ActionType, Pot, WinamaxParser};
+use holdem_suite_parser::pokerstars::PokerStarsParser;
+use holdem_suite_parser::site::SiteParser;
+use holdem_suite_parser::timezone::zone_for_label;
+use holdem_suite_parser::writer::PokerStarsWriter;
 use std::path::PathBuf;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("../../db/migrations/");
@@ -33,10 +44,38 @@ fn test_insert_hand() {
         hole_card_2: "Ks".to_owned(),
         tournament_id: None,
         cash_game_name: None,
-        datetime: "2021-01-01 00:00:00".to_owned(),
+        datetime: NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
         button: 1,
         max_players: 9,
         hero: "WinterSound".to_owned(),
+        ante: None,
+        small_blind: 1,
+        big_blind: 2,
+        pot: 5,
+        rake: None,
+        flop1: None,
+        flop2: None,
+        flop3: None,
+        turn: None,
+        river: None,
+        poker_type: "holdem-no-limit".to_owned(),
+        hole_card_3: None,
+        hole_card_4: None,
+        hole_card_5: None,
+        pool_name: None,
+        tournament_name: None,
+        tournament_buy_in: None,
+        tournament_rake: None,
+        tournament_level: None,
+        table_number: None,
+        play_money: 0,
+        net_result: None,
+        ev_net_result: None,
+        site: "Winamax".to_owned(),
+        currency: "EUR".to_owned(),
     };
     let inserted = diesel::insert_or_ignore_into(hands::table)
         .values(&hand)
@@ -56,6 +95,35 @@ fn test_parse_file() {
     assert_eq!("6s", hands[0].hole_card_1);
 }
 
+#[test]
+fn test_parse_file_omaha() {
+    let mut conn = establish_test_connection();
+    let nb_parsed_hands =
+        parse_file(PathBuf::from("tests/samples/sample_omaha.txt"), &mut conn).unwrap();
+    assert_eq!(2, nb_parsed_hands);
+
+    let hands = get_hands(&mut conn).unwrap();
+    assert_eq!("omaha5-pot-limit", hands[0].poker_type);
+    assert_eq!(Some(String::from("3s")), hands[0].hole_card_5);
+    assert_eq!("omaha-pot-limit", hands[1].poker_type);
+    assert_eq!(Some(String::from("Qd")), hands[1].hole_card_4);
+    assert_eq!(None, hands[1].hole_card_5);
+
+    let seats = holdem_suite_db::get_seats(&mut conn, &hands[0].id).unwrap();
+    assert_eq!(Some(String::from("2d")), seats[0].card5);
+}
+
+#[test]
+fn test_seat_positions() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn).unwrap();
+    let mut seats =
+        holdem_suite_db::get_seats(&mut conn, "2815488303912976462-17-1684698755").unwrap();
+    seats.sort_by_key(|seat| seat.seat_number);
+    let positions: Vec<&str> = seats.iter().map(|seat| seat.position.as_str()).collect();
+    assert_eq!(vec!["BB", "UTG", "HJ", "CO", "BTN", "SB"], positions);
+}
+
 #[test]
 fn test_get_hands_for_player() {
     let mut conn = establish_test_connection();
@@ -66,6 +134,69 @@ fn test_get_hands_for_player() {
     assert_eq!(2, hands.len());
 }
 
+#[test]
+fn test_get_hands_for_pool() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn);
+    parse_file(PathBuf::from("tests/samples/sample_hold_up.txt"), &mut conn);
+    let pools = holdem_suite_db::get_pools(&mut conn).unwrap();
+    assert_eq!(
+        vec![holdem_suite_db::Pool {
+            name: String::from("Colorado"),
+            poker_type: String::from("holdem-no-limit"),
+            small_blind: 1,
+            big_blind: 2,
+        }],
+        pools
+    );
+    let hands = holdem_suite_db::get_hands_for_pool(&mut conn, &pools[0]).unwrap();
+    assert_eq!(1, hands.len());
+    assert_eq!(Some(String::from("Colorado")), hands[0].pool_name);
+    let hands =
+        holdem_suite_db::get_hands_for_player_in_pool(&mut conn, "WinterSound", &pools[0]).unwrap();
+    assert_eq!(1, hands.len());
+}
+
+#[test]
+fn test_collected_amounts() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn);
+    let actions =
+        holdem_suite_db::get_actions(&mut conn, String::from("2815488303912976462-15-1684698584"))
+            .unwrap();
+    let collect = actions
+        .iter()
+        .find(|action| action.action_type == "collect")
+        .unwrap();
+    assert_eq!("Anonymous 1", collect.player_name);
+    assert_eq!(Some(644000), collect.amount);
+}
+
+#[test]
+fn test_collected_side_pots() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample_hold_up.txt"), &mut conn).unwrap();
+    let data = std::fs::read_to_string("tests/samples/sample_hold_up.txt").unwrap();
+    let hand = &WinamaxParser.parse_hands(&data).unwrap()[0];
+    let stored = get_parsed_hand(&mut conn, &hand.hand_info.hand_id).unwrap();
+    let collects = |hand: &holdem_suite_parser::parser::Hand| -> Vec<_> {
+        hand.streets
+            .iter()
+            .flat_map(|street| street.actions.iter())
+            .filter(|action| matches!(action.action, ActionType::Collect { .. }))
+            .cloned()
+            .collect()
+    };
+    assert!(collects(hand).iter().any(|action| matches!(
+        action.action,
+        ActionType::Collect {
+            pot: Pot::Side(1),
+            ..
+        }
+    )));
+    assert_eq!(collects(hand), collects(&stored));
+}
+
 #[test]
 fn test_get_players() {
     let mut conn = establish_test_connection();
@@ -104,7 +235,7 @@ fn test_insert_and_get_summary() {
     assert_eq!("MYSTERY KO", summary.name);
     assert_eq!(669464094, summary.id);
     assert_eq!(145, summary.finish_place);
-    assert_eq!(1.00, summary.won.unwrap());
+    assert_eq!(100, summary.won.unwrap());
     assert_eq!("knockout", summary.tournament_type);
     assert_eq!(
         NaiveDate::from_ymd_opt(2023, 7, 8)
@@ -114,3 +245,327 @@ fn test_insert_and_get_summary() {
         summary.date
     );
 }
+
+#[test]
+fn test_get_expresso_results() {
+    let mut conn = establish_test_connection();
+    parse_file(
+        PathBuf::from("tests/samples/tournament_summary.txt"),
+        &mut conn,
+    )
+    .expect("Error parsing tournament summary");
+    parse_file(
+        PathBuf::from("tests/samples/expresso_summary.txt"),
+        &mut conn,
+    )
+    .expect("Error parsing expresso summary");
+    let summaries = get_summaries(&mut conn).unwrap();
+    assert_eq!(2, summaries.len());
+
+    let results = holdem_suite_db::get_expresso_results(&mut conn).unwrap();
+    assert_eq!(1, results.len());
+    assert_eq!(5.0, results[0].multiplier);
+    assert_eq!(1, results[0].tournaments);
+    assert_eq!(1, results[0].first_places);
+    assert_eq!(100, results[0].buy_ins);
+    assert_eq!(500, results[0].winnings);
+    assert_eq!(167, results[0].expected_winnings);
+    let json = serde_json::to_value(&results[0]).unwrap();
+    assert_eq!(1.67, json["expected_winnings"]);
+
+    // summaries stored without their cost fall back to the buy-in
+    diesel::update(summaries::table)
+        .set(summaries::total_cost.eq(None::<i64>))
+        .execute(&mut conn)
+        .unwrap();
+    let results = holdem_suite_db::get_expresso_results(&mut conn).unwrap();
+    assert_eq!(92, results[0].buy_ins);
+}
+
+#[test]
+fn test_insert_summary_with_rebuys() {
+    let mut conn = establish_test_connection();
+    parse_file(
+        PathBuf::from("tests/samples/tournament_summary_rebuy.txt"),
+        &mut conn,
+    )
+    .expect("Error parsing tournament summary");
+    let summaries = get_summaries(&mut conn).unwrap();
+    let summary = &summaries[0];
+    assert_eq!(2, summary.nb_entries);
+    assert_eq!(2, summary.rebuys);
+    assert_eq!(1, summary.addons);
+    assert_eq!(Some(2500), summary.total_cost);
+    assert_eq!(Some(1494), summary.won);
+    assert_eq!(Some(250), summary.bounties_won);
+    assert_eq!(Some(String::from("Freeroll 100K")), summary.tickets);
+}
+
+#[test]
+fn test_amounts_sent_in_units() {
+    let pool = holdem_suite_db::Pool {
+        name: String::from("Colorado"),
+        poker_type: String::from("holdem-no-limit"),
+        small_blind: 1,
+        big_blind: 2,
+    };
+    let json = serde_json::to_string(&pool).unwrap();
+    assert_eq!(
+        r#"{"name":"Colorado","poker_type":"holdem-no-limit","small_blind":0.01,"big_blind":0.02}"#,
+        json
+    );
+    assert_eq!(pool, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn test_export_stored_hands() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn).unwrap();
+    let data = std::fs::read_to_string("tests/samples/sample1.txt").unwrap();
+    let hands = WinamaxParser.parse_hands(&data).unwrap();
+    for hand in &hands {
+        let stored = get_parsed_hand(&mut conn, &hand.hand_info.hand_id).unwrap();
+        assert_eq!(hand.hand_info, stored.hand_info);
+        assert_eq!(hand.table_info, stored.table_info);
+        assert_eq!(hand.seats, stored.seats);
+        assert_eq!(hand.dealt_cards, stored.dealt_cards);
+        // shown cards are stored by seat, not in the order they were shown
+        let actions = |hand: &holdem_suite_parser::parser::Hand| -> Vec<_> {
+            hand.streets
+                .iter()
+                .flat_map(|street| street.actions.iter())
+                .filter(|action| !matches!(action.action, ActionType::Shows { .. }))
+                .cloned()
+                .collect()
+        };
+        assert_eq!(actions(hand), actions(&stored));
+        assert_eq!(hand.streets.len(), stored.streets.len());
+        assert_eq!(hand.summary.boards, stored.summary.boards);
+    }
+
+    let hand_ids: Vec<String> = hands
+        .iter()
+        .map(|hand| hand.hand_info.hand_id.to_owned())
+        .collect();
+    let written = export_hands(&mut conn, &hand_ids, &PokerStarsWriter).unwrap();
+    let exported = PokerStarsParser.parse_hands(&written).unwrap();
+    assert_eq!(hands.len(), exported.len());
+    assert_eq!(hands[0].seats, exported[0].seats);
+    assert_eq!(hands[0].hand_info.blinds, exported[0].hand_info.blinds);
+}
+
+#[test]
+fn test_export_site_and_currency() {
+    let mut conn = establish_test_connection();
+    let data = std::fs::read_to_string("tests/samples/ggpoker.txt").unwrap();
+    insert_hands(&mut conn, GGPokerParser.parse_hands(&data).unwrap()).unwrap();
+    let stored = get_parsed_hand(&mut conn, "HD1234567999").unwrap();
+    assert_eq!("GGPoker", stored.hand_info.site);
+    assert_eq!("USD", stored.hand_info.currency_code);
+
+    let hand_ids = vec![String::from("HD1234567999")];
+    let written = export_hands(&mut conn, &hand_ids, &PokerStarsWriter).unwrap();
+    assert!(written.contains("Hold'em No Limit ($0.05/$0.10 USD)"));
+    let written = export_hands(&mut conn, &hand_ids, &OpenHhWriter).unwrap();
+    let exported = openhh::parse_hands(&written).unwrap();
+    assert_eq!(stored.hand_info, exported[0].hand_info);
+}
+
+#[test]
+fn test_import_and_export_openhh() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn).unwrap();
+    let hand_ids: Vec<String> = get_hands(&mut conn)
+        .unwrap()
+        .into_iter()
+        .map(|hand| hand.id)
+        .collect();
+    let written = export_hands(&mut conn, &hand_ids, &OpenHhWriter).unwrap();
+    let exported = openhh::parse_hands(&written).unwrap();
+    assert_eq!(3, exported.len());
+
+    let path = std::env::temp_dir().join("holdem_suite_openhh_test.json");
+    std::fs::write(&path, &written).unwrap();
+    let mut other_conn = establish_test_connection();
+    let nb_parsed_hands = parse_file(path, &mut other_conn).unwrap();
+    assert_eq!(3, nb_parsed_hands);
+    let hands = get_hands(&mut other_conn).unwrap();
+    assert_eq!(
+        get_hands(&mut conn).unwrap()[0].hole_card_1,
+        hands[0].hole_card_1
+    );
+    assert_eq!(get_hands(&mut conn).unwrap()[0].pot, hands[0].pot);
+}
+
+#[test]
+fn test_sessions_in_local_time() {
+    let mut conn = establish_test_connection();
+    let mut hands = vec![];
+    for sample in [
+        "tests/samples/sample1.txt",
+        "tests/samples/sample_omaha.txt",
+    ] {
+        let data = std::fs::read_to_string(sample).unwrap();
+        hands.extend(WinamaxParser.parse_hands(&data).unwrap());
+    }
+    // in Paris, clocks went back from 3:00 to 2:00 on the night of October 29th
+    let datetimes = [
+        Utc.with_ymd_and_hms(2023, 10, 28, 21, 40, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 10, 28, 23, 40, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 10, 29, 0, 10, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 10, 29, 0, 40, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 10, 29, 1, 5, 0).unwrap(),
+    ];
+    for (hand, datetime) in hands.iter_mut().zip(datetimes) {
+        hand.hand_info.datetime = datetime;
+    }
+    insert_hands(&mut conn, hands).unwrap();
+
+    let paris = zone_for_label("CET").unwrap();
+    let sessions = get_sessions(&mut conn, &paris).unwrap();
+    assert_eq!(
+        vec![
+            Session {
+                day: NaiveDate::from_ymd_opt(2023, 10, 28).unwrap(),
+                start: datetimes[0],
+                end: datetimes[0],
+                nb_hands: 1,
+                net: 0,
+                ev_net: 0,
+            },
+            Session {
+                day: NaiveDate::from_ymd_opt(2023, 10, 29).unwrap(),
+                start: datetimes[1],
+                end: datetimes[4],
+                nb_hands: 4,
+                // the two Omaha cash game hands
+                net: 267,
+                ev_net: 267,
+            },
+        ],
+        sessions
+    );
+    // the second session started on the 28th in UTC
+    let sessions = get_sessions(&mut conn, &Utc).unwrap();
+    assert_eq!(
+        NaiveDate::from_ymd_opt(2023, 10, 28).unwrap(),
+        sessions[1].day
+    );
+}
+
+#[test]
+fn test_hand_dates_sent_in_utc() {
+    let mut conn = establish_test_connection();
+    parse_file(PathBuf::from("tests/samples/sample1.txt"), &mut conn).unwrap();
+    let hands = get_hands(&mut conn).unwrap();
+    let json = serde_json::to_value(&hands[0]).unwrap();
+    assert_eq!("2023-05-21T19:52:35Z", json["datetime"]);
+}
+
+#[test]
+fn test_import_batch() {
+    let mut conn = establish_test_connection();
+    let data = std::fs::read_to_string("tests/samples/sample1.txt").unwrap();
+    let hands: Vec<_> = WinamaxParser
+        .parse_hands(&data)
+        .unwrap()
+        .into_iter()
+        .map(|hand| {
+            let result = hero_result(&hand);
+            (hand, result)
+        })
+        .collect();
+    let file = ImportedFile {
+        path: String::from("/history/sample1.txt"),
+        size: data.len() as i64,
+    };
+    let nb_hands = import_batch(&mut conn, hands.clone(), &[file.clone()]).unwrap();
+    assert_eq!(3, nb_hands);
+    let imported = get_imported_files(&mut conn).unwrap();
+    assert_eq!(Some(&file.size), imported.get(&file.path));
+    let stored = get_hands(&mut conn).unwrap();
+    assert!(stored.iter().all(|hand| hand.net_result.is_some()));
+
+    // a file that grew is imported again, without its previous hands
+    let grown = ImportedFile {
+        size: file.size + 100,
+        ..file
+    };
+    let nb_hands = import_batch(&mut conn, hands, &[grown.clone()]).unwrap();
+    assert_eq!(0, nb_hands);
+    let imported = get_imported_files(&mut conn).unwrap();
+    assert_eq!(1, imported.len());
+    assert_eq!(Some(&grown.size), imported.get(&grown.path));
+}
+
+#[test]
+fn test_hand_results() {
+    let mut conn = establish_test_connection();
+    let data = std::fs::read_to_string("tests/samples/ggpoker.txt").unwrap();
+    insert_hands(&mut conn, GGPokerParser.parse_hands(&data).unwrap()).unwrap();
+
+    let results = get_cash_game_results(&mut conn, "USD").unwrap();
+    assert_eq!(
+        vec!["RC1234567890", "HD1234567999"],
+        results.iter().map(|r| &r.hand_id).collect::<Vec<_>>()
+    );
+    // euros and dollars make separate series
+    parse_file(PathBuf::from("tests/samples/sample_omaha.txt"), &mut conn).unwrap();
+    let euros = get_cash_game_results(&mut conn, "EUR").unwrap();
+    assert!(!euros.is_empty());
+    assert!(euros.iter().all(|result| !results.contains(result)));
+    assert_eq!(results, get_cash_game_results(&mut conn, "USD").unwrap());
+
+    // ace-king all-in on the flop against queens, which won the second run
+    assert_eq!(-10, results[0].net);
+    assert_eq!(376, results[0].ev_net);
+    // jacks called all-in by ace-queen, hero paying the risk of their cash-out on top
+    assert_eq!(-1494, results[1].net);
+
+    let tournament = get_tournament_results(&mut conn, 112233445).unwrap();
+    assert_eq!(1, tournament.len());
+    assert_eq!(1112500, tournament[0].net);
+    let json = serde_json::to_value(&tournament[0]).unwrap();
+    assert_eq!(11125.0, json["net"]);
+
+    // hands stored before the results were kept count for nothing until they are updated
+    diesel::update(hands::table)
+        .set((
+            hands::net_result.eq(None::<i64>),
+            hands::ev_net_result.eq(None::<i64>),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    assert!(get_cash_game_results(&mut conn, "USD")
+        .unwrap()
+        .iter()
+        .all(|result| result.net == 0));
+    let hands = get_hands(&mut conn).unwrap();
+    assert!(hands.iter().all(|hand| hand.net_result.is_none()));
+
+    // then they get them from their stored rows
+    let nb_hands = update_hand_results(&mut conn, |hand_id, error| {
+        panic!("hand #{} skipped: {}", hand_id, error)
+    })
+    .unwrap();
+    assert_eq!(5, nb_hands);
+    assert_eq!(results, get_cash_game_results(&mut conn, "USD").unwrap());
+    assert_eq!(
+        tournament,
+        get_tournament_results(&mut conn, 112233445).unwrap()
+    );
+
+    // a hand that cannot be rebuilt is skipped
+    diesel::update(hands::table.find("RC1234567890"))
+        .set((
+            hands::net_result.eq(None::<i64>),
+            hands::poker_type.eq("stud"),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    let mut skipped = vec![];
+    let nb_hands =
+        update_hand_results(&mut conn, |hand_id, _| skipped.push(hand_id.to_owned())).unwrap();
+    assert_eq!(0, nb_hands);
+    assert_eq!(vec!["RC1234567890"], skipped);
+}
@@ -41,6 +41,29 @@ export type Hand = {
     flop3: string,
     turn: string,
     river: string,
+    poker_type: string,
+    hole_card_3: string | null,
+    hole_card_4: string | null,
+    hole_card_5: string | null,
+    pool_name: string | null,
+    net_result: number | null,
+    ev_net_result: number | null,
+    site: string,
+    currency: string,
+}
+
+export type HandResult = {
+    hand_id: string,
+    datetime: string,
+    net: number,
+    ev_net: number,
+}
+
+export type Pool = {
+    name: string,
+    poker_type: string,
+    small_blind: number,
+    big_blind: number,
 }
 
 export type Seat = {
@@ -51,6 +74,10 @@ export type Seat = {
     bounty: number | null,
     card1: string | null,
     card2: string | null,
+    card3: string | null,
+    card4: string | null,
+    card5: string | null,
+    position: string,
 }
 
 export type Action = {
@@ -61,6 +88,7 @@ export type Action = {
     action_type: string,
     amount: number,
     is_all_in: boolean,
+    pot: number | null,
 }
 
 export type Card = {
@@ -0,0 +1,225 @@
+use std::fmt;
+use std::iter::Sum;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use nom::character::complete::{char, digit1};
+use nom::combinator::{map_res, opt, recognize};
+use nom::sequence::pair;
+use nom::IResult;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Exact amount of chips or money, counted in hundredths so that chips stay integers and
+/// euros are kept to the cent
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub const fn from_cents(cents: i64) -> Amount {
+        Amount(cents)
+    }
+
+    /// Amount of whole chips, or of whole euros
+    pub const fn from_units(units: i64) -> Amount {
+        Amount(units * 100)
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// Approximation for display and statistics, amounts should be summed before converting
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// Parses "1250" and "12.5", without any currency symbol
+    pub(crate) fn parse(input: &str) -> IResult<&str, Amount> {
+        map_res(
+            recognize(pair(digit1, opt(pair(char('.'), digit1)))),
+            str::parse,
+        )(input)
+    }
+}
+
+/// Error returned when parsing an amount with more than two decimals or no digits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAmountError;
+
+impl fmt::Display for ParseAmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid amount")
+    }
+}
+
+impl std::error::Error for ParseAmountError {}
+
+impl From<ParseIntError> for ParseAmountError {
+    fn from(_: ParseIntError) -> Self {
+        ParseAmountError
+    }
+}
+
+impl FromStr for Amount {
+    type Err = ParseAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (units, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let valid = |digits: &str| digits.bytes().all(|b| b.is_ascii_digit());
+        if units.is_empty() || !valid(units) || !valid(fraction) || fraction.len() > 2 {
+            return Err(ParseAmountError);
+        }
+        let cents = match fraction.len() {
+            0 => 0,
+            1 => fraction.parse::<i64>()? * 10,
+            _ => fraction.parse::<i64>()?,
+        };
+        let amount = units.parse::<i64>()? * 100 + cents;
+        Ok(Amount(if negative { -amount } else { amount }))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let (units, cents) = (self.0.abs() / 100, self.0.abs() % 100);
+        if cents == 0 {
+            write!(f, "{}{}", sign, units)
+        } else {
+            write!(f, "{}{}.{:02}", sign, units, cents)
+        }
+    }
+}
+
+impl From<i64> for Amount {
+    fn from(cents: i64) -> Self {
+        Amount(cents)
+    }
+}
+
+impl From<Amount> for i64 {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+/// Amounts are serialized as numbers of chips or euros, rounded back to the cent when read
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let units = f64::deserialize(deserializer)?;
+        Ok(Amount((units * 100.0).round() as i64))
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, rhs: Amount) -> Amount {
+        Amount(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Amount) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, rhs: Amount) -> Amount {
+        Amount(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Amount {
+    fn sub_assign(&mut self, rhs: Amount) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Amount {
+    type Output = Amount;
+
+    fn neg(self) -> Amount {
+        Amount(-self.0)
+    }
+}
+
+impl Mul<u32> for Amount {
+    type Output = Amount;
+
+    fn mul(self, rhs: u32) -> Amount {
+        Amount(self.0 * rhs as i64)
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Amount> for Amount {
+    fn sum<I: Iterator<Item = &'a Amount>>(iter: I) -> Amount {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(Amount::parse("1250 "), Ok((" ", Amount::from_units(1250))));
+        assert_eq!(Amount::parse("0.02€"), Ok(("€", Amount::from_cents(2))));
+        assert_eq!("12.5".parse(), Ok(Amount::from_cents(1250)));
+        assert_eq!("-0.05".parse(), Ok(Amount::from_cents(-5)));
+        assert_eq!("0.125".parse::<Amount>(), Err(ParseAmountError));
+        assert_eq!("".parse::<Amount>(), Err(ParseAmountError));
+    }
+
+    #[test]
+    fn test_display_amount() {
+        assert_eq!(Amount::from_units(1000).to_string(), "1000");
+        assert_eq!(Amount::from_cents(150).to_string(), "1.50");
+        assert_eq!(Amount::from_cents(-5).to_string(), "-0.05");
+    }
+
+    #[test]
+    fn test_serde_amount() {
+        assert_eq!(
+            serde_json::to_string(&Amount::from_cents(150)).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            serde_json::from_str::<Amount>("0.07").unwrap(),
+            Amount::from_cents(7)
+        );
+        assert_eq!(
+            serde_json::from_str::<Amount>("1250").unwrap(),
+            Amount::from_units(1250)
+        );
+    }
+
+    #[test]
+    fn test_sum_does_not_drift() {
+        let total: Amount = std::iter::repeat_n(Amount::from_cents(2), 10_000).sum();
+        assert_eq!(total, Amount::from_units(200));
+    }
+}
@@ -0,0 +1,730 @@
+//! Equity of hands at showdown, enumerating the deals left when they are few and sampling them
+//! otherwise
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::evaluator::{best_hand, HandValue, RANKS};
+use crate::parser::{ActionType, Card, Hand, ParseCardError, PokerType, StreetType, Suit};
+use crate::timeline::HandTimeline;
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+/// Attempts at drawing hands from the ranges before giving up on a sample
+const MAX_DRAWS: usize = 10_000;
+
+fn card(rank: usize, suit: usize) -> Card {
+    Card {
+        rank: RANKS[rank].clone(),
+        suit: SUITS[suit].clone(),
+    }
+}
+
+/// Bit of `card` in a set of cards
+fn card_bit(card: &Card) -> u64 {
+    1 << (card.rank.clone() as u32 * 4 + card.suit.clone() as u32)
+}
+
+fn card_set(cards: &[Card]) -> u64 {
+    cards.iter().fold(0, |set, card| set | card_bit(card))
+}
+
+/// Parses cards written one after the other, as in "AhKd" or "Ah Kd 2c"
+pub fn parse_cards(text: &str) -> Result<Vec<Card>, ParseCardError> {
+    let text: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    text.chunks(2)
+        .map(|card| card.iter().collect::<String>().parse())
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+/// Two-card Hold'em hands a player may hold, written as in "QQ+, AKs, A5s-A2s, KJo, AhKd", or
+/// "random" for any hand
+#[derive(Debug, PartialEq, Clone)]
+pub struct Range {
+    pub combos: Vec<[Card; 2]>,
+}
+
+/// Error returned when parsing a range, holding the part that could not be read
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseRangeError(pub String);
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid range: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+impl Range {
+    /// Hands of a class such as "AKs", from its ranks as indices in `RANKS`
+    fn class_combos(high: usize, low: usize, suitedness: Suitedness) -> Vec<[Card; 2]> {
+        let mut combos = vec![];
+        for first in 0..4 {
+            for second in 0..4 {
+                let kept = match suitedness {
+                    _ if high == low => first < second,
+                    Suitedness::Suited => first == second,
+                    Suitedness::Offsuit => first != second,
+                    Suitedness::Any => true,
+                };
+                if kept {
+                    combos.push([card(high, first), card(low, second)]);
+                }
+            }
+        }
+        combos
+    }
+
+    /// Reads a class such as "AKs", "T9o", "QJ" or "77" as its high and low ranks
+    fn parse_class(text: &str) -> Option<(usize, usize, Suitedness)> {
+        let rank = |c: char| {
+            RANKS
+                .iter()
+                .position(|rank| rank.to_string() == c.to_string())
+        };
+        let mut chars = text.chars();
+        let first = rank(chars.next()?)?;
+        let second = rank(chars.next()?)?;
+        let suitedness = match chars.as_str() {
+            "" => Suitedness::Any,
+            "s" if first != second => Suitedness::Suited,
+            "o" if first != second => Suitedness::Offsuit,
+            _ => return None,
+        };
+        Some((first.max(second), first.min(second), suitedness))
+    }
+
+    /// Reads the classes of a part of a range, such as "QQ+", "ATs+" or "A5s-A2s"
+    fn parse_classes(text: &str) -> Option<Vec<(usize, usize, Suitedness)>> {
+        if let Some(base) = text.strip_suffix('+') {
+            let (high, low, suitedness) = Range::parse_class(base)?;
+            return Some(match high == low {
+                true => (low..RANKS.len()).map(|r| (r, r, suitedness)).collect(),
+                false => (low..high).map(|r| (high, r, suitedness)).collect(),
+            });
+        }
+        if let Some((first, last)) = text.split_once('-') {
+            let first = Range::parse_class(first)?;
+            let last = Range::parse_class(last)?;
+            let pairs = first.0 == first.1 && last.0 == last.1;
+            let same_high = first.0 == last.0 && first.0 != first.1 && last.0 != last.1;
+            if first.2 != last.2 || !(pairs || same_high) {
+                return None;
+            }
+            let lows = first.1.min(last.1)..=first.1.max(last.1);
+            return Some(match pairs {
+                true => lows.map(|r| (r, r, first.2)).collect(),
+                false => lows.map(|r| (first.0, r, first.2)).collect(),
+            });
+        }
+        Some(vec![Range::parse_class(text)?])
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut combos: Vec<[Card; 2]> = vec![];
+        for part in s.split(',').map(str::trim) {
+            let part_combos = match part {
+                "random" => (0..52)
+                    .flat_map(|first| (first + 1..52).map(move |second| (first, second)))
+                    .map(|(first, second)| {
+                        [card(first / 4, first % 4), card(second / 4, second % 4)]
+                    })
+                    .collect(),
+                _ => match parse_cards(part)
+                    .ok()
+                    .and_then(|cards| cards.try_into().ok())
+                {
+                    Some(combo) => vec![combo],
+                    None => Range::parse_classes(part)
+                        .ok_or_else(|| ParseRangeError(part.to_owned()))?
+                        .into_iter()
+                        .flat_map(|(high, low, suitedness)| {
+                            Range::class_combos(high, low, suitedness)
+                        })
+                        .collect(),
+                },
+            };
+            for combo in part_combos {
+                let set = card_set(&combo);
+                if !combos.iter().any(|known| card_set(known) == set) {
+                    combos.push(combo);
+                }
+            }
+        }
+        Ok(Range { combos })
+    }
+}
+
+/// Cards of a player, either known or among a range
+#[derive(Debug, PartialEq, Clone)]
+pub enum Holding {
+    Cards(Vec<Card>),
+    Range(Range),
+}
+
+impl FromStr for Holding {
+    type Err = ParseRangeError;
+
+    /// Reads hole cards, as in "AhKd", or else a range
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_cards(s) {
+            Ok(cards) if !s.contains(',') => Ok(Holding::Cards(cards)),
+            _ => Ok(Holding::Range(s.parse()?)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum EquityError {
+    NotEnoughPlayers,
+    /// More than five board cards
+    InvalidBoard(usize),
+    WrongHoleCards {
+        player: usize,
+        expected: usize,
+        found: usize,
+    },
+    DuplicateCard(Card),
+    /// Range given in a game dealing more than two hole cards
+    RangeNotSupported(PokerType),
+    /// Range with no hand left once the known cards are removed
+    EmptyRange {
+        player: usize,
+    },
+    /// Ranges that cannot all be dealt together
+    NoDeal,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::NotEnoughPlayers => write!(f, "equity needs at least two players"),
+            EquityError::InvalidBoard(count) => write!(f, "board of {} cards", count),
+            EquityError::WrongHoleCards {
+                player,
+                expected,
+                found,
+            } => write!(
+                f,
+                "player {} holds {} cards instead of {}",
+                player + 1,
+                found,
+                expected
+            ),
+            EquityError::DuplicateCard(card) => write!(f, "{} is dealt twice", card),
+            EquityError::RangeNotSupported(poker_type) => {
+                write!(f, "ranges are not supported in {}", poker_type)
+            }
+            EquityError::EmptyRange { player } => {
+                write!(f, "no hand is left in the range of player {}", player + 1)
+            }
+            EquityError::NoDeal => write!(f, "no deal gives every player a hand of their range"),
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+/// Share of the pot each player wins on average at showdown
+#[derive(Debug, PartialEq, Clone)]
+pub struct Equity {
+    /// Equity of each player, ties splitting the pot
+    pub equities: Vec<f64>,
+    /// Number of deals evaluated
+    pub deals: u64,
+    /// Whether every deal was enumerated, rather than sampled
+    pub exhaustive: bool,
+}
+
+/// Settings of an equity computation
+#[derive(Debug, PartialEq, Clone)]
+pub struct EquityCalculator {
+    /// Largest number of deals enumerated, more being sampled instead
+    pub max_deals: u64,
+    /// Number of deals sampled
+    pub samples: u64,
+    /// Seed of the sampling, the same seed giving the same equities
+    pub seed: u64,
+}
+
+impl Default for EquityCalculator {
+    fn default() -> Self {
+        EquityCalculator {
+            max_deals: 200_000,
+            samples: 100_000,
+            seed: 0,
+        }
+    }
+}
+
+/// Possible hole cards of a player, with the set of their cards
+type Options = Vec<(u64, Vec<Card>)>;
+
+/// Shares of the pots won over the deals evaluated
+struct Tally<'a> {
+    poker_type: &'a PokerType,
+    shares: Vec<f64>,
+    deals: u64,
+}
+
+impl Tally<'_> {
+    fn showdown(&mut self, hands: &[&[Card]], board: &[Card]) {
+        let values: Vec<HandValue> = hands
+            .iter()
+            .map(|hand| best_hand(self.poker_type, hand, board).expect("complete board"))
+            .collect();
+        let best = values.iter().max().copied();
+        let nb_winners = values.iter().filter(|&&value| Some(value) == best).count();
+        for (share, value) in self.shares.iter_mut().zip(&values) {
+            if Some(*value) == best {
+                *share += 1.0 / nb_winners as f64;
+            }
+        }
+        self.deals += 1;
+    }
+}
+
+/// Number of ways to pick `k` of `n` cards
+fn combinations(n: usize, k: usize) -> f64 {
+    (0..k).map(|i| (n - i) as f64 / (i + 1) as f64).product()
+}
+
+impl EquityCalculator {
+    /// Equity of each holding on `board`, from the flop dealt or not to a complete board
+    pub fn equity(
+        &self,
+        poker_type: &PokerType,
+        holdings: &[Holding],
+        board: &[Card],
+    ) -> Result<Equity, EquityError> {
+        if holdings.len() < 2 {
+            return Err(EquityError::NotEnoughPlayers);
+        }
+        if board.len() > 5 {
+            return Err(EquityError::InvalidBoard(board.len()));
+        }
+        let nb_hole_cards = match poker_type {
+            PokerType::HoldemNoLimit => 2,
+            PokerType::OmahaPotLimit => 4,
+            PokerType::Omaha5PotLimit => 5,
+        };
+        let mut known: Vec<&Card> = board.iter().collect();
+        for (player, holding) in holdings.iter().enumerate() {
+            match holding {
+                Holding::Cards(cards) if cards.len() != nb_hole_cards => {
+                    return Err(EquityError::WrongHoleCards {
+                        player,
+                        expected: nb_hole_cards,
+                        found: cards.len(),
+                    })
+                }
+                Holding::Cards(cards) => known.extend(cards),
+                Holding::Range(_) if nb_hole_cards != 2 => {
+                    return Err(EquityError::RangeNotSupported(poker_type.clone()))
+                }
+                Holding::Range(_) => (),
+            }
+        }
+        let mut dead = 0;
+        for card in known {
+            if dead & card_bit(card) != 0 {
+                return Err(EquityError::DuplicateCard(card.clone()));
+            }
+            dead |= card_bit(card);
+        }
+
+        let mut options: Vec<Options> = vec![];
+        for (player, holding) in holdings.iter().enumerate() {
+            let player_options: Options = match holding {
+                Holding::Cards(cards) => vec![(card_set(cards), cards.clone())],
+                Holding::Range(range) => range
+                    .combos
+                    .iter()
+                    .map(|combo| (card_set(combo), combo.to_vec()))
+                    .filter(|(set, _)| set & dead == 0)
+                    .collect(),
+            };
+            if player_options.is_empty() {
+                return Err(EquityError::EmptyRange { player });
+            }
+            options.push(player_options);
+        }
+        let deck: Vec<Card> = (0..52)
+            .map(|index| card(index / 4, index % 4))
+            .filter(|card| card_bit(card) & dead == 0)
+            .collect();
+
+        let mut tally = Tally {
+            poker_type,
+            shares: vec![0.0; holdings.len()],
+            deals: 0,
+        };
+        let nb_range_cards = 2 * options.iter().filter(|o| o.len() > 1).count();
+        let nb_deals = options.iter().map(|o| o.len() as f64).product::<f64>()
+            * combinations(deck.len() - nb_range_cards, 5 - board.len());
+        let exhaustive = nb_deals <= self.max_deals as f64;
+        let mut hands = vec![];
+        match exhaustive {
+            true => enumerate_hands(&options, &mut hands, 0, board, &deck, &mut tally),
+            false => self.sample(&options, board, &deck, &mut tally)?,
+        }
+        if tally.deals == 0 {
+            return Err(EquityError::NoDeal);
+        }
+        Ok(Equity {
+            equities: tally
+                .shares
+                .iter()
+                .map(|share| share / tally.deals as f64)
+                .collect(),
+            deals: tally.deals,
+            exhaustive,
+        })
+    }
+
+    fn sample(
+        &self,
+        options: &[Options],
+        board: &[Card],
+        deck: &[Card],
+        tally: &mut Tally,
+    ) -> Result<(), EquityError> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut full_board = board.to_vec();
+        for _ in 0..self.samples {
+            // hands are drawn again until no card is dealt twice
+            let mut draws = 0;
+            let (hands, dealt) = loop {
+                draws += 1;
+                if draws > MAX_DRAWS {
+                    return Err(EquityError::NoDeal);
+                }
+                let mut dealt = 0;
+                let mut hands: Vec<&[Card]> = vec![];
+                for player_options in options {
+                    let (set, cards) = &player_options[rng.gen_range(0..player_options.len())];
+                    if dealt & set != 0 {
+                        break;
+                    }
+                    dealt |= set;
+                    hands.push(cards);
+                }
+                if hands.len() == options.len() {
+                    break (hands, dealt);
+                }
+            };
+            let mut left: Vec<&Card> = deck.iter().filter(|c| card_bit(c) & dealt == 0).collect();
+            full_board.truncate(board.len());
+            for i in 0..5 - board.len() {
+                let j = rng.gen_range(i..left.len());
+                left.swap(i, j);
+                full_board.push(left[i].clone());
+            }
+            tally.showdown(&hands, &full_board);
+        }
+        Ok(())
+    }
+}
+
+/// Deals every hand of the players from `hands.len()` on, then every board
+fn enumerate_hands<'a>(
+    options: &'a [Options],
+    hands: &mut Vec<&'a [Card]>,
+    dealt: u64,
+    board: &[Card],
+    deck: &[Card],
+    tally: &mut Tally,
+) {
+    let Some(player_options) = options.get(hands.len()) else {
+        let left: Vec<Card> = deck
+            .iter()
+            .filter(|card| card_bit(card) & dealt == 0)
+            .cloned()
+            .collect();
+        enumerate_boards(hands, &mut board.to_vec(), &left, tally);
+        return;
+    };
+    for (set, cards) in player_options {
+        if dealt & set == 0 {
+            hands.push(cards);
+            enumerate_hands(options, hands, dealt | set, board, deck, tally);
+            hands.pop();
+        }
+    }
+}
+
+/// Completes `board` with every combination of the cards `left`
+fn enumerate_boards(hands: &[&[Card]], board: &mut Vec<Card>, left: &[Card], tally: &mut Tally) {
+    if board.len() == 5 {
+        tally.showdown(hands, board);
+        return;
+    }
+    for (i, card) in left.iter().enumerate() {
+        board.push(card.clone());
+        enumerate_boards(hands, board, &left[i + 1..], tally);
+        board.pop();
+    }
+}
+
+/// Equity of the players left in a hand when the last chips went in
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandEquity {
+    /// Index in `Hand::streets` of the street on which the last chips went in
+    pub street: usize,
+    /// Players left, by their index in `Hand::seats`
+    pub players: Vec<usize>,
+    pub equity: Equity,
+}
+
+impl<S: AsRef<str>> Hand<S> {
+    /// Hole cards of the player at `index` in `seats`, shown in the summary or dealt to hero
+    pub fn known_cards(&self, index: usize) -> Option<&[Card]> {
+        let name = self.seats[index].player_name.as_ref();
+        self.summary
+            .players
+            .iter()
+            .find(|player| player.name.as_ref() == name)
+            .and_then(|player| player.hole_cards.as_ref())
+            .or((self.dealt_cards.player_name.as_ref() == name)
+                .then_some(&self.dealt_cards.hole_cards))
+            .map(|hole_cards| &hole_cards.cards[..])
+            .filter(|cards| !cards.is_empty())
+    }
+
+    /// Players left at the end of `timeline` with their known hole cards, by their index in
+    /// `seats`
+    ///
+    /// Gives `None` when a single player is left or when the cards of one of them are unknown.
+    pub(crate) fn showdown_holdings(
+        &self,
+        timeline: &HandTimeline,
+    ) -> Option<(Vec<usize>, Vec<Holding>)> {
+        let players: Vec<usize> = (0..self.seats.len())
+            .filter(|&index| !timeline.end().folded[index])
+            .collect();
+        let holdings: Option<Vec<Holding>> = players
+            .iter()
+            .map(|&index| Some(Holding::Cards(self.known_cards(index)?.to_vec())))
+            .collect();
+        holdings
+            .filter(|holdings| holdings.len() >= 2)
+            .map(|holdings| (players, holdings))
+    }
+
+    /// Index in `streets` of the street on which the last chips went in
+    pub(crate) fn last_money_street(&self) -> usize {
+        self.streets
+            .iter()
+            .rposition(|street| {
+                street.street_type != StreetType::Showdown
+                    && street.actions.iter().any(|action| {
+                        matches!(
+                            action.action,
+                            ActionType::Post(_)
+                                | ActionType::Bet { .. }
+                                | ActionType::Raise { .. }
+                                | ActionType::Call { .. }
+                        )
+                    })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Equity of the players left at the end of the hand, on the board known when the last chips
+    /// went in
+    ///
+    /// Gives `None` when a single player is left or when the cards of one of them are unknown.
+    pub fn showdown_equity(
+        &self,
+        calculator: &EquityCalculator,
+    ) -> Result<Option<HandEquity>, EquityError> {
+        let Some((players, holdings)) = self.showdown_holdings(&HandTimeline::new(self)) else {
+            return Ok(None);
+        };
+        let street = self.last_money_street();
+        let board: Vec<Card> = self.board_at(street).into_iter().cloned().collect();
+        let equity = calculator.equity(&self.hand_info.poker_type, &holdings, &board)?;
+        Ok(Some(HandEquity {
+            street,
+            players,
+            equity,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_hands;
+
+    fn holdings(holdings: &[&str]) -> Vec<Holding> {
+        holdings.iter().map(|h| h.parse().unwrap()).collect()
+    }
+
+    fn holdem_equity(players: &[&str], board: &str) -> Result<Equity, EquityError> {
+        let board = parse_cards(board).unwrap();
+        EquityCalculator::default().equity(&PokerType::HoldemNoLimit, &holdings(players), &board)
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let size = |range: &str| range.parse::<Range>().unwrap().combos.len();
+        assert_eq!(size("QQ+"), 18);
+        assert_eq!(size("AKs"), 4);
+        assert_eq!(size("AKo"), 12);
+        assert_eq!(size("KA"), 16);
+        assert_eq!(size("ATs+"), 16);
+        assert_eq!(size("A5s-A2s"), 16);
+        assert_eq!(size("22-44, AK, AKs"), 34);
+        assert_eq!(size("AhKd"), 1);
+        assert_eq!(size("random"), 1326);
+        assert_eq!(
+            "QQ+, AXs".parse::<Range>(),
+            Err(ParseRangeError(String::from("AXs")))
+        );
+        assert!("AAs".parse::<Range>().is_err());
+        assert!("AKs-QJs".parse::<Range>().is_err());
+        assert!(matches!("AhKd".parse(), Ok(Holding::Cards(_))));
+        assert!(matches!("AK".parse(), Ok(Holding::Range(_))));
+    }
+
+    #[test]
+    fn test_exhaustive() {
+        // Kings win unless the river is one of the two aces left
+        let equity = holdem_equity(&["AhAd", "KsKc"], "2c 7d 9s Kd").unwrap();
+        assert!(equity.exhaustive);
+        assert_eq!(equity.deals, 44);
+        assert_eq!(equity.equities, vec![2.0 / 44.0, 42.0 / 44.0]);
+
+        let equity = holdem_equity(&["AhKd", "AsKc"], "2c 3d 8h 9s Jd").unwrap();
+        assert_eq!(equity.equities, vec![0.5, 0.5]);
+
+        // the quads beat every pair of aces left
+        let equity = holdem_equity(&["AA", "KsKc"], "2c 7d 9s Kd Kh").unwrap();
+        assert_eq!(equity.deals, 6);
+        assert_eq!(equity.equities, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_sampled() {
+        let calculator = EquityCalculator {
+            samples: 20_000,
+            ..Default::default()
+        };
+        let players = holdings(&["AhAd", "KsKc"]);
+        let equity = calculator
+            .equity(&PokerType::HoldemNoLimit, &players, &[])
+            .unwrap();
+        assert!(!equity.exhaustive);
+        assert_eq!(equity.deals, 20_000);
+        // 81.26% when enumerating every board
+        assert!((equity.equities[0] - 0.8126).abs() < 0.01);
+        assert_eq!(
+            calculator.equity(&PokerType::HoldemNoLimit, &players, &[]),
+            Ok(equity)
+        );
+
+        let equity = calculator
+            .equity(
+                &PokerType::HoldemNoLimit,
+                &holdings(&["AhAd", "random"]),
+                &[],
+            )
+            .unwrap();
+        assert!((equity.equities[0] - 0.85).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_omaha() {
+        let calculator = EquityCalculator::default();
+        let board = parse_cards("Ah 9h 2c 8d").unwrap();
+        let equity = calculator
+            .equity(
+                &PokerType::OmahaPotLimit,
+                &holdings(&["KhQhJcTc", "AcAd7s6s"]),
+                &board,
+            )
+            .unwrap();
+        // seven hearts and nine cards making a straight without pairing the board
+        assert_eq!(equity.deals, 40);
+        assert_eq!(equity.equities[0], 16.0 / 40.0);
+        assert_eq!(
+            calculator.equity(
+                &PokerType::OmahaPotLimit,
+                &holdings(&["KhQhJcTc", "AA"]),
+                &board
+            ),
+            Err(EquityError::RangeNotSupported(PokerType::OmahaPotLimit))
+        );
+    }
+
+    #[test]
+    fn test_equity_errors() {
+        assert_eq!(
+            holdem_equity(&["AhAd"], ""),
+            Err(EquityError::NotEnoughPlayers)
+        );
+        assert_eq!(
+            holdem_equity(&["AhAd", "AhKd"], ""),
+            Err(EquityError::DuplicateCard("Ah".parse().unwrap()))
+        );
+        assert_eq!(
+            holdem_equity(&["AhAdKs", "QsQd"], ""),
+            Err(EquityError::WrongHoleCards {
+                player: 0,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            holdem_equity(&["AhAd", "AA"], "As"),
+            Err(EquityError::EmptyRange { player: 1 })
+        );
+        assert_eq!(
+            holdem_equity(&["KK", "KK", "KK"], ""),
+            Err(EquityError::NoDeal)
+        );
+    }
+
+    #[test]
+    fn test_showdown_equity() {
+        let hand = &parse_hands(include_str!("../samples/ggpoker.txt")).unwrap()[0];
+        let calculator = EquityCalculator {
+            samples: 20_000,
+            ..Default::default()
+        };
+        let equity = hand.showdown_equity(&calculator).unwrap().unwrap();
+        // Hero's queens against ace-jack, all-in before the flop
+        assert_eq!(equity.street, 0);
+        assert_eq!(equity.players, vec![1, 2]);
+        assert!((equity.equity.equities[0] - 0.7147).abs() < 0.01);
+
+        // three players, the last chips going in on the flop
+        let hand = &parse_hands(include_str!("../samples/sample1.txt")).unwrap()[1];
+        let equity = hand.showdown_equity(&calculator).unwrap().unwrap();
+        assert_eq!(equity.street, 1);
+        assert_eq!(equity.players, vec![1, 4, 5]);
+        assert!(equity.equity.exhaustive);
+        assert_eq!(equity.equity.deals, 903);
+        assert!((equity.equity.equities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // a single player is left
+        let hand = &parse_hands(include_str!("../samples/pokerstars.txt")).unwrap()[1];
+        assert_eq!(hand.showdown_equity(&calculator), Ok(None));
+    }
+}
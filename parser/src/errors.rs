@@ -0,0 +1,107 @@
+use std::fmt;
+use std::ops::Range;
+
+use nom::error::ErrorKind;
+
+/// Part of a hand history or tournament summary that failed to parse.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Section {
+    HandInfo,
+    TableInfo,
+    Seats,
+    DealtToHero,
+    Street,
+    Summary,
+    TournamentSummary,
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Section::HandInfo => "hand info",
+                Section::TableInfo => "table info",
+                Section::Seats => "seats",
+                Section::DealtToHero => "dealt cards",
+                Section::Street => "street",
+                Section::Summary => "summary",
+                Section::TournamentSummary => "tournament summary",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    /// Byte offset of the failure in the parsed input
+    pub offset: usize,
+    /// 1-based line number of the failure
+    pub line: usize,
+    pub hand_id: Option<String>,
+    pub section: Section,
+    pub kind: ErrorKind,
+    /// Line of input on which the failure occurred
+    pub context: String,
+}
+
+impl ParseError {
+    /// Locates a nom error raised while parsing `section` of `origin`.
+    pub(crate) fn new(
+        origin: &str,
+        section: Section,
+        hand_id: Option<String>,
+        error: nom::Err<nom::error::Error<&str>>,
+    ) -> ParseError {
+        let (remaining, kind) = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => (e.input, e.code),
+            nom::Err::Incomplete(_) => ("", ErrorKind::Eof),
+        };
+        let offset = origin.len() - remaining.len();
+        let line_start = origin[..offset].rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            offset,
+            line: origin[..offset].matches('\n').count() + 1,
+            hand_id,
+            section,
+            kind,
+            context: origin[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: error parsing {}", self.line, self.section)?;
+        if let Some(hand_id) = &self.hand_id {
+            write!(f, " of hand #{}", hand_id)?;
+        }
+        write!(f, " ({:?}) near {:?}", self.kind, self.context)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Block of input skipped while parsing a hand history file
+#[derive(Debug, PartialEq, Clone)]
+pub struct SkippedHand<E = ParseError> {
+    /// Byte range of the skipped block in the parsed input
+    pub span: Range<usize>,
+    pub error: E,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseReport<E = ParseError> {
+    pub skipped: Vec<SkippedHand<E>>,
+}
+
+impl<E> Default for ParseReport<E> {
+    fn default() -> Self {
+        ParseReport { skipped: vec![] }
+    }
+}
@@ -0,0 +1,313 @@
+//! Ranking of poker hands, from the cards of a player and the board
+
+use crate::parser::{Card, HandCategory, PokerType, Rank};
+
+pub(crate) const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const HIGH_CARD: u32 = 0;
+const PAIR: u32 = 1;
+const TWO_PAIR: u32 = 2;
+const THREE_OF_A_KIND: u32 = 3;
+const STRAIGHT: u32 = 4;
+const FLUSH: u32 = 5;
+const FULL: u32 = 6;
+const FOUR_OF_A_KIND: u32 = 7;
+const STRAIGHT_FLUSH: u32 = 8;
+
+/// Strength of the best five-card hand, a greater value beating a lower one and equal values
+/// splitting the pot
+///
+/// The category is held above the ranks deciding between hands of that category, from the
+/// most significant one.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct HandValue(u32);
+
+impl HandValue {
+    /// Value of a hand of `category`, decided by `ranks` from the most significant
+    fn new(category: u32, ranks: impl IntoIterator<Item = usize>) -> HandValue {
+        let mut ranks = ranks.into_iter();
+        let ranks = (0..5).fold(0, |value, _| {
+            value << 4 | ranks.next().map_or(0, |rank| rank as u32)
+        });
+        HandValue(category << 20 | ranks)
+    }
+
+    /// Category of the hand, named after its deciding ranks as in Winamax summaries
+    pub fn category(&self) -> HandCategory {
+        let rank = |i: u32| RANKS[(self.0 >> (16 - 4 * i) & 0xf) as usize].clone();
+        match self.0 >> 20 {
+            HIGH_CARD => HandCategory::HighCard(rank(0)),
+            PAIR => HandCategory::Pair(rank(0)),
+            TWO_PAIR => HandCategory::TwoPair(rank(0), rank(1)),
+            THREE_OF_A_KIND => HandCategory::ThreeOfAKind(rank(0)),
+            STRAIGHT => HandCategory::Straight(rank(0)),
+            FLUSH => HandCategory::Flush(rank(0)),
+            FULL => HandCategory::Full(rank(0), rank(1)),
+            FOUR_OF_A_KIND => HandCategory::FourOfAKind(rank(0)),
+            _ => HandCategory::StraightFlush(rank(0)),
+        }
+    }
+}
+
+/// Highest card of the best straight among the ranks of `mask`, the ace also ending the wheel
+fn straight_high(mask: u16) -> Option<usize> {
+    let mask = mask << 1 | (mask >> 12 & 1);
+    (4..=13)
+        .rev()
+        .find(|&high| mask >> (high - 4) & 0b11111 == 0b11111)
+        .map(|high| high - 1)
+}
+
+/// Best `n` ranks of `mask`, from the highest
+fn top_ranks(mask: u16, n: usize) -> impl Iterator<Item = usize> {
+    (0..13)
+        .rev()
+        .filter(move |rank| mask >> rank & 1 == 1)
+        .take(n)
+}
+
+/// Highest rank of `mask`, which holds at least one
+fn highest(mask: u16) -> usize {
+    15 - mask.leading_zeros() as usize
+}
+
+/// Value of the best five-card hand among five to seven cards
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    evaluate_cards(cards.iter())
+}
+
+fn evaluate_cards<'a>(cards: impl Iterator<Item = &'a Card>) -> HandValue {
+    let mut counts = [0u8; 13];
+    let mut suits = [0u16; 4];
+    for card in cards {
+        let rank = card.rank.clone() as usize;
+        counts[rank] += 1;
+        suits[card.suit.clone() as usize] |= 1 << rank;
+    }
+    let mask = suits.iter().fold(0, |mask, suit| mask | suit);
+
+    // with seven cards at most, a flush rules out four of a kind and full houses
+    if let Some(&flush) = suits.iter().find(|suit| suit.count_ones() >= 5) {
+        return match straight_high(flush) {
+            Some(high) => HandValue::new(STRAIGHT_FLUSH, [high]),
+            None => HandValue::new(FLUSH, top_ranks(flush, 5)),
+        };
+    }
+
+    // ranks held by four, three and two cards
+    let mut groups = [0u16; 5];
+    for (rank, &count) in counts.iter().enumerate() {
+        groups[count as usize] |= 1 << rank;
+    }
+    let (quads, trips, pairs) = (groups[4], groups[3], groups[2]);
+    let kickers = |used: u16, n: usize| top_ranks(mask & !used, n);
+    if quads != 0 {
+        let rank = highest(quads);
+        return HandValue::new(
+            FOUR_OF_A_KIND,
+            [rank].into_iter().chain(kickers(1 << rank, 1)),
+        );
+    }
+    if trips != 0 {
+        let rank = highest(trips);
+        let pairs = pairs | trips & !(1 << rank);
+        if pairs != 0 {
+            return HandValue::new(FULL, [rank, highest(pairs)]);
+        }
+    }
+    if let Some(high) = straight_high(mask) {
+        return HandValue::new(STRAIGHT, [high]);
+    }
+    if trips != 0 {
+        let rank = highest(trips);
+        return HandValue::new(
+            THREE_OF_A_KIND,
+            [rank].into_iter().chain(kickers(1 << rank, 2)),
+        );
+    }
+    match pairs.count_ones() {
+        0 => HandValue::new(HIGH_CARD, top_ranks(mask, 5)),
+        1 => {
+            let rank = highest(pairs);
+            HandValue::new(PAIR, [rank].into_iter().chain(kickers(1 << rank, 3)))
+        }
+        _ => {
+            let first = highest(pairs);
+            let second = highest(pairs & !(1 << first));
+            let used = 1 << first | 1 << second;
+            HandValue::new(
+                TWO_PAIR,
+                [first, second].into_iter().chain(kickers(used, 1)),
+            )
+        }
+    }
+}
+
+/// Value of the best Omaha hand, made of two of the hole cards and three cards of the board
+///
+/// Gives `None` with less than two hole cards or three board cards.
+pub fn evaluate_omaha(hole_cards: &[Card], board: &[Card]) -> Option<HandValue> {
+    let mut best = None;
+    for (i, first) in hole_cards.iter().enumerate() {
+        for second in &hole_cards[i + 1..] {
+            for (j, a) in board.iter().enumerate() {
+                for (k, b) in board.iter().enumerate().skip(j + 1) {
+                    for c in &board[k + 1..] {
+                        let cards = [first, second, a, b, c];
+                        best = best.max(Some(evaluate_cards(cards.into_iter())));
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Value of the best hand of a player in a game of `poker_type`, or `None` while there are
+/// not enough cards to make five
+pub fn best_hand(poker_type: &PokerType, hole_cards: &[Card], board: &[Card]) -> Option<HandValue> {
+    match poker_type {
+        PokerType::HoldemNoLimit => {
+            (hole_cards.len() + board.len() >= 5).then(|| evaluate(&[hole_cards, board].concat()))
+        }
+        PokerType::OmahaPotLimit | PokerType::Omaha5PotLimit => evaluate_omaha(hole_cards, board),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_hands;
+
+    fn cards(cards: &str) -> Vec<Card> {
+        cards.split(' ').map(|card| card.parse().unwrap()).collect()
+    }
+
+    fn value(hand: &str) -> HandValue {
+        evaluate(&cards(hand))
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(
+            value("Ah Kd 9c 7s 2h").category(),
+            HandCategory::HighCard(Rank::Ace)
+        );
+        assert_eq!(
+            value("9h 9d Kc 7s 2h 3c 4d").category(),
+            HandCategory::Pair(Rank::Nine)
+        );
+        assert_eq!(
+            value("9h 9d Kc Ks 2h 2c 4d").category(),
+            HandCategory::TwoPair(Rank::King, Rank::Nine)
+        );
+        assert_eq!(
+            value("Qh Qd Qc 7s 2h").category(),
+            HandCategory::ThreeOfAKind(Rank::Queen)
+        );
+        assert_eq!(
+            value("Ah 2d 3c 4s 5h Kd").category(),
+            HandCategory::Straight(Rank::Five)
+        );
+        assert_eq!(
+            value("Ts Jd Qc Ks Ah 9d").category(),
+            HandCategory::Straight(Rank::Ace)
+        );
+        assert_eq!(
+            value("Ah 8h 3h 4h 5h 6d 7c").category(),
+            HandCategory::Flush(Rank::Ace)
+        );
+        assert_eq!(
+            value("8h 8d 8c 3s 3h 3c 2d").category(),
+            HandCategory::Full(Rank::Eight, Rank::Three)
+        );
+        assert_eq!(
+            value("5h 5d 5c 5s 2h Ac Ad").category(),
+            HandCategory::FourOfAKind(Rank::Five)
+        );
+        assert_eq!(
+            value("Ac 2c 3c 4c 5c 6h 7d").category(),
+            HandCategory::StraightFlush(Rank::Five)
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        // kickers decide between hands of a category
+        assert!(value("Ah Ad Kc 7s 2h") > value("As Ac Qc Js Th"));
+        assert!(value("Ah Ad Kc Ks 3h") > value("As Ac Kd Kh 2h"));
+        assert_eq!(value("Ah Ad Kc 7s 3h 2c"), value("As Ac Kd 7h 3c"));
+        // categories rank above any of their cards
+        assert!(value("2h 3d 4c 5s 6h") > value("Ah Ad Ac Ks Qh"));
+        assert!(value("Ah 2d 3c 4s 5h") < value("2h 3d 4c 5s 6h"));
+        assert!(value("2h 2d 2c 3s 3h") > value("Ah Kh Qh Jh 9h"));
+        assert!(value("2h 3h 4h 5h 6h") > value("Ah Ad Ac As Kh"));
+        // the best five of seven cards
+        assert_eq!(value("Ah Kd 9c 7s 2h 3c 4d"), value("Ah Kd 9c 7s 4d"));
+    }
+
+    #[test]
+    fn test_omaha() {
+        // a single heart in hand makes no flush, and the board pair needs two hole cards
+        let hole_cards = cards("Ah Kd Kc Qs");
+        let board = cards("2h 5h 9h Jh 9d");
+        let value = evaluate_omaha(&hole_cards, &board).unwrap();
+        assert_eq!(
+            value.category(),
+            HandCategory::TwoPair(Rank::King, Rank::Nine)
+        );
+        assert_eq!(evaluate_omaha(&hole_cards, &board[..2]), None);
+        assert_eq!(
+            best_hand(&PokerType::HoldemNoLimit, &hole_cards[..2], &board)
+                .unwrap()
+                .category(),
+            HandCategory::Flush(Rank::Ace)
+        );
+    }
+
+    #[test]
+    fn test_sample_categories() {
+        // the categories Winamax gives to the hands shown
+        for data in [
+            include_str!("../samples/sample1.txt"),
+            include_str!("../samples/sample_omaha.txt"),
+        ] {
+            for hand in parse_hands(data).unwrap() {
+                let board: Vec<Card> = hand
+                    .board_at(hand.streets.len() - 1)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+                for player in &hand.summary.players {
+                    let (Some(hole_cards), Some(category)) =
+                        (&player.hole_cards, &player.hand_category)
+                    else {
+                        continue;
+                    };
+                    let value =
+                        best_hand(&hand.hand_info.poker_type, &hole_cards.cards, &board).unwrap();
+                    assert_eq!(
+                        &value.category(),
+                        category,
+                        "hand {}",
+                        hand.hand_info.hand_id
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until, take_while1};
+use nom::character::complete::{anychar, char, line_ending, not_line_ending};
+use nom::combinator::{map, opt};
+use nom::multi::many_till;
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
+use nom::IResult;
+
+use crate::amount::Amount;
+use crate::errors::ParseError;
+use crate::parser::{Blinds, GameInfo, Hand, PokerType, TournamentInfo};
+use crate::pokerstars::{parse_amount, parse_hand, parse_poker_type, Header};
+use crate::site::{Site, SiteParser};
+use crate::timezone::parse_datetime;
+
+/// Parser for GGPoker No Limit Hold'em and Pot Limit Omaha hand histories
+///
+/// GGPoker uses the PokerStars format with its own header. Only the hero's hole cards are
+/// known, and the other players are named by a hash which is kept as their name.
+pub struct GGPokerParser;
+
+impl SiteParser for GGPokerParser {
+    fn hand_header(&self) -> &'static str {
+        "Poker Hand #"
+    }
+
+    fn parse_hand<'a>(&self, input: &'a str, origin: &str) -> Result<(&'a str, Hand), ParseError> {
+        parse_hand(input, origin, parse_header)
+    }
+}
+
+/// Parses "($0.02/$0.05)" and "(500/1,000(125))", where the ante is optional
+fn parse_blinds(input: &str) -> IResult<&str, Blinds> {
+    let (input, (small_blind, big_blind, ante)) = delimited(
+        char('('),
+        tuple((
+            parse_amount,
+            preceded(char('/'), parse_amount),
+            opt(delimited(char('('), parse_amount, char(')'))),
+        )),
+        char(')'),
+    )(input)?;
+    Ok((
+        input,
+        Blinds {
+            ante,
+            small_blind,
+            big_blind,
+        },
+    ))
+}
+
+/// Parses "Tournament #112233445, Daily Hyper $5 Hold'em No Limit - Level12(500/1,000(125))"
+///
+/// The buy-in is read from the end of the tournament name; its rake is not given.
+fn parse_tournament(input: &str) -> IResult<&str, (GameInfo, PokerType, Blinds, Option<u32>)> {
+    let (input, (id, (name, poker_type), level, blinds)) = tuple((
+        delimited(
+            tag("Tournament #"),
+            take_while1(|c: char| c.is_ascii_digit()),
+            tag(", "),
+        ),
+        many_till(anychar, preceded(char(' '), parse_poker_type)),
+        preceded(
+            pair(tag(" - Level"), opt(char(' '))),
+            nom::character::complete::u32,
+        ),
+        parse_blinds,
+    ))(input)?;
+    let name: String = name.into_iter().collect();
+    let buy_in = name
+        .rsplit(' ')
+        .next()
+        .and_then(|buy_in| parse_amount(buy_in).ok())
+        .map_or(Amount::ZERO, |(_, buy_in)| buy_in);
+    Ok((
+        input,
+        (
+            GameInfo::Tournament(TournamentInfo {
+                name,
+                buy_in,
+                rake: Amount::ZERO,
+                level,
+            }),
+            poker_type,
+            blinds,
+            id.parse().ok(),
+        ),
+    ))
+}
+
+fn parse_cash_game(input: &str) -> IResult<&str, (GameInfo, PokerType, Blinds, Option<u32>)> {
+    map(
+        separated_pair(parse_poker_type, char(' '), parse_blinds),
+        |(poker_type, blinds)| (GameInfo::CashGame, poker_type, blinds, None),
+    )(input)
+}
+
+fn parse_header(input: &str) -> IResult<&str, Header<'_>> {
+    let (input, (hand_id, (game_info, poker_type, blinds, tournament_id), datetime, _)) =
+        tuple((
+            delimited(tag("Poker Hand #"), take_until(": "), tag(": ")),
+            alt((parse_tournament, parse_cash_game)),
+            preceded(tag(" - "), parse_datetime),
+            pair(not_line_ending, line_ending),
+        ))(input)?;
+    // Rush & Cash hands are identified by their prefix
+    let game_info = match game_info {
+        GameInfo::CashGame if hand_id.starts_with("RC") => {
+            GameInfo::HoldUp(String::from("Rush & Cash"))
+        }
+        game_info => game_info,
+    };
+    Ok((
+        input,
+        Header {
+            hand_id,
+            game_info,
+            poker_type,
+            blinds,
+            tournament_id,
+            datetime,
+            site: Site::GGPoker,
+            currency_code: Site::GGPoker.currency_code(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ActionType, PostType, Pot, StreetType, SummaryResult, TableName};
+    use crate::timeline::HandTimeline;
+    use chrono::prelude::*;
+
+    #[test]
+    fn test_parse_header_tournament() {
+        let input = "Poker Hand #TM2345678901: Tournament #112233445, Daily Hyper $5 Hold'em No \
+        Limit - Level12(500/1,000(125)) - 2023/05/21 22:10:05\n";
+        let (_, header) = parse_header(input).unwrap();
+        assert_eq!(header.hand_id, "TM2345678901");
+        assert_eq!(header.tournament_id, Some(112233445));
+        assert_eq!(
+            header.game_info,
+            GameInfo::Tournament(TournamentInfo {
+                name: String::from("Daily Hyper $5"),
+                buy_in: Amount::from_units(5),
+                rake: Amount::from_units(0),
+                level: 12,
+            })
+        );
+        assert_eq!(
+            header.blinds,
+            Blinds {
+                ante: Some(Amount::from_units(125)),
+                small_blind: Amount::from_units(500),
+                big_blind: Amount::from_units(1000),
+            }
+        );
+        assert_eq!(
+            header.datetime,
+            Utc.with_ymd_and_hms(2023, 5, 21, 22, 10, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_header_rush_and_cash() {
+        let input =
+            "Poker Hand #RC1234567890: Hold'em No Limit ($0.02/$0.05) - 2023/05/21 22:15:40\n";
+        let (_, header) = parse_header(input).unwrap();
+        assert_eq!(
+            header.game_info,
+            GameInfo::HoldUp(String::from("Rush & Cash"))
+        );
+        assert_eq!(header.blinds.ante, None);
+        assert_eq!(header.blinds.big_blind, Amount::from_cents(5));
+    }
+
+    #[test]
+    fn test_parse_hands() {
+        let data = include_str!("../samples/ggpoker.txt");
+        let hands = GGPokerParser.parse_hands(data).unwrap();
+        assert_eq!(hands.len(), 3);
+
+        let tournament = &hands[0];
+        assert_eq!(
+            tournament.table_info.table_name,
+            TableName::Tournament(String::from("Daily Hyper $5"), 112233445, 12)
+        );
+        assert_eq!(tournament.seats[0].player_name, "4a5b6c7d");
+        assert_eq!(tournament.seats[0].stack, Amount::from_units(18250));
+        assert_eq!(tournament.dealt_cards.player_name, "Hero");
+        assert_eq!(tournament.streets[0].actions.len(), 13);
+        assert_eq!(
+            tournament.streets[0].actions[5].action,
+            ActionType::Post(PostType::BigBlind(Amount::from_units(1000)))
+        );
+        assert_eq!(tournament.summary.pot, Amount::from_units(21000));
+
+        let run_twice = &hands[1];
+        let turn = &run_twice.streets[2];
+        assert_eq!(turn.street_type, StreetType::Turn);
+        assert_eq!(turn.cards.len(), 1);
+        assert_eq!(turn.cards[0].to_string(), "2c");
+        let board: Vec<String> = run_twice
+            .board_at(2)
+            .iter()
+            .map(|card| card.to_string())
+            .collect();
+        assert_eq!(board, ["8c", "4d", "Ks", "2c"]);
+        assert_eq!(run_twice.summary.boards.len(), 2);
+        assert_eq!(
+            run_twice.summary.boards[1].cards[3]
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            "Qd"
+        );
+        assert_eq!(
+            run_twice.hand_info.game_info,
+            GameInfo::HoldUp(String::from("Rush & Cash"))
+        );
+        let showdown = run_twice.streets.last().unwrap();
+        assert_eq!(showdown.street_type, StreetType::Showdown);
+        assert_eq!(showdown.actions.len(), 2);
+        assert_eq!(
+            showdown.actions[0].action,
+            ActionType::Collect {
+                amount: Amount::from_cents(470),
+                pot: Pot::Main,
+            }
+        );
+        assert_eq!(
+            showdown.actions[1].action,
+            ActionType::Collect {
+                amount: Amount::from_cents(469),
+                pot: Pot::Main,
+            }
+        );
+        assert_eq!(run_twice.summary.rake, Some(Amount::from_cents(21)));
+        assert_eq!(
+            run_twice.summary.players[1].result,
+            SummaryResult::Won(Amount::from_cents(470))
+        );
+        assert_eq!(
+            run_twice.summary.players[2].result,
+            SummaryResult::Won(Amount::from_cents(469))
+        );
+
+        let cash_out = &hands[2];
+        assert_eq!(cash_out.hand_info.game_info, GameInfo::CashGame);
+        assert_eq!(cash_out.streets[0].actions.len(), 9);
+        assert_eq!(
+            cash_out.streets[0].actions[8].action,
+            ActionType::CashoutRisk {
+                amount: Amount::from_cents(644)
+            }
+        );
+        assert_eq!(cash_out.summary.players[0].result, SummaryResult::Lost);
+        // the risk paid adds up with the call lost
+        let settlement = HandTimeline::new(cash_out).settlement(cash_out);
+        assert_eq!(settlement.net(0), Amount::from_cents(-1494));
+    }
+}
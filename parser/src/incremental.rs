@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::io;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::errors::ParseReport;
+use crate::parser::Hand;
+use crate::site::Site;
+
+/// Parses a hand history file that is still being written to.
+///
+/// The parser remembers the byte offset following the last complete hand, so that each
+/// call only parses the hands appended since. A hand is considered complete once it is
+/// followed by a blank line or by the header of the next hand; a half-written final hand
+/// is left for a later call.
+#[derive(Debug, Default, Clone)]
+pub struct IncrementalParser {
+    offset: u64,
+    line: usize,
+    site: Option<Site>,
+}
+
+impl IncrementalParser {
+    pub fn new() -> IncrementalParser {
+        IncrementalParser::default()
+    }
+
+    /// Byte offset following the last complete hand
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Reads and parses the complete hands written to `path` since the last call.
+    ///
+    /// Starts over from the beginning of the file if it was truncated.
+    pub fn read(&mut self, path: &Path) -> io::Result<(Vec<Hand>, ParseReport)> {
+        let mut file = File::open(path)?;
+        if file.metadata()?.len() < self.offset {
+            *self = IncrementalParser::default();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+        let data = match std::str::from_utf8(&data) {
+            Ok(data) => data,
+            // the last character may not be completely written yet
+            Err(e) => std::str::from_utf8(&data[..e.valid_up_to()]).unwrap(),
+        };
+        Ok(self.feed(data))
+    }
+
+    /// Parses the complete hands at the beginning of `data`, which must start at `offset()`.
+    ///
+    /// Errors and skipped spans in the report are located relative to the whole file.
+    pub fn feed(&mut self, data: &str) -> (Vec<Hand>, ParseReport) {
+        if self.site.is_none() {
+            self.site = Site::detect(data);
+        }
+        let parser = self.site.unwrap_or(Site::Winamax).parser();
+        let complete = &data[..complete_len(data, parser.hand_header())];
+        let (hands, mut report) = parser.parse_hands_recovering(complete);
+        let base = self.offset as usize;
+        for skipped in report.skipped.iter_mut() {
+            skipped.span = skipped.span.start + base..skipped.span.end + base;
+            skipped.error.offset += base;
+            skipped.error.line += self.line;
+        }
+        self.offset += complete.len() as u64;
+        self.line += complete.matches('\n').count();
+        (hands, report)
+    }
+}
+
+/// Length of the part of `data` made of complete hands
+fn complete_len(data: &str, hand_header: &str) -> usize {
+    if data.ends_with("\n\n") || data.ends_with("\r\n\r\n") {
+        return data.len();
+    }
+    data.rfind(&format!("\n{}", hand_header))
+        .map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_half_written_hand() {
+        let data = include_str!("../samples/sample1.txt");
+        let cut = data.find("Anonymous 2 raises 500 to 1000").unwrap();
+        let mut parser = IncrementalParser::new();
+
+        let (hands, report) = parser.feed(&data[..cut]);
+        assert_eq!(hands.len(), 1);
+        assert!(report.skipped.is_empty());
+        let offset = parser.offset() as usize;
+        assert!(data[offset..].starts_with("Winamax Poker - "));
+
+        let (hands, report) = parser.feed(&data[offset..]);
+        assert_eq!(hands.len(), 1);
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            hands[0].hand_info.hand_id,
+            "2815488303912976462-16-1684698652"
+        );
+
+        let offset = parser.offset() as usize;
+        let (hands, _) = parser.feed(&format!("{}\n\n", &data[offset..]));
+        assert_eq!(hands.len(), 1);
+        assert_eq!(
+            hands[0].hand_info.hand_id,
+            "2815488303912976462-17-1684698755"
+        );
+    }
+
+    #[test]
+    fn test_feed_locates_errors_in_file() {
+        let data = include_str!("../samples/sample1.txt")
+            .replace("Anonymous 2 raises 500 to 1000", "Anonymous 2 sings");
+        let mut parser = IncrementalParser::new();
+        let (cut, _) = data.match_indices("Winamax Poker - ").nth(1).unwrap();
+        parser.feed(&data[..cut]);
+        let (hands, report) = parser.feed(&data[cut..]);
+        assert_eq!(hands.len(), 0);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].span.start, cut);
+        assert_eq!(report.skipped[0].error.line, 67);
+    }
+}
@@ -1,2 +1,17 @@
+pub mod amount;
+pub mod equity;
+pub mod errors;
+pub mod evaluator;
+pub mod ggpoker;
+pub mod incremental;
+pub mod openhh;
 pub mod parser;
+pub mod pokerstars;
+pub mod position;
+pub mod results;
+pub mod site;
 pub mod summary_parser;
+pub mod timeline;
+pub mod timezone;
+pub mod validation;
+pub mod writer;
@@ -0,0 +1,1053 @@
+//! Open Hand History, the JSON hand history standard shared by poker trackers
+//!
+//! See <https://hh-specs.handhistory.org>. Each hand is an object with a single `ohh` field;
+//! files hold one hand after another.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::errors::{ParseReport, SkippedHand};
+use crate::parser::{
+    self, parse_table_name_tournament, ActionType, GameInfo, MoneyType, PokerType, PostType, Pot,
+    StreetType, SummaryResult, TableName,
+};
+use crate::site::Site;
+use crate::writer::SiteWriter;
+
+pub const SPEC_VERSION: &str = "1.4.6";
+/// Currency code of play money tables
+pub const PLAY_MONEY: &str = "PLAY";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OpenHandHistory {
+    pub ohh: OhhHand,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhHand {
+    pub spec_version: String,
+    pub site_name: String,
+    pub network_name: String,
+    #[serde(default)]
+    pub internal_version: String,
+    pub tournament: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tournament_info: Option<OhhTournamentInfo>,
+    pub game_number: String,
+    pub start_date_utc: DateTime<Utc>,
+    pub table_name: String,
+    pub game_type: OhhGameType,
+    pub bet_limit: OhhBetLimit,
+    pub table_size: u32,
+    pub currency: String,
+    pub dealer_seat: u32,
+    pub small_blind_amount: Amount,
+    pub big_blind_amount: Amount,
+    #[serde(default)]
+    pub ante_amount: Amount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_player_id: Option<u32>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    pub players: Vec<OhhPlayer>,
+    pub rounds: Vec<OhhRound>,
+    #[serde(default)]
+    pub pots: Vec<OhhPot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhTournamentInfo {
+    pub tournament_number: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date_utc: Option<DateTime<Utc>>,
+    pub currency: String,
+    pub buyin_amount: Amount,
+    pub fee_amount: Amount,
+    #[serde(default)]
+    pub bounty_fee_amount: Amount,
+    #[serde(rename = "type")]
+    pub tournament_type: String,
+    #[serde(default)]
+    pub flags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum OhhGameType {
+    Holdem,
+    Omaha,
+    OmahaHiLo,
+    Stud,
+    StudHiLo,
+    Draw,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhBetLimit {
+    pub bet_type: OhhBetType,
+    #[serde(default)]
+    pub bet_cap: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum OhhBetType {
+    #[serde(rename = "NL")]
+    NoLimit,
+    #[serde(rename = "PL")]
+    PotLimit,
+    #[serde(rename = "FL")]
+    FixedLimit,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhPlayer {
+    pub id: u32,
+    pub seat: u32,
+    pub name: String,
+    pub starting_stack: Amount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_bounty: Option<Amount>,
+    #[serde(default)]
+    pub is_sitting_out: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhRound {
+    pub id: u32,
+    pub street: OhhStreet,
+    #[serde(default)]
+    pub cards: Vec<String>,
+    pub actions: Vec<OhhAction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum OhhStreet {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhAction {
+    pub action_number: u32,
+    pub player_id: u32,
+    pub action: OhhActionType,
+    #[serde(default)]
+    pub amount: Amount,
+    #[serde(default)]
+    pub is_allin: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum OhhActionType {
+    #[serde(rename = "Dealt Cards")]
+    DealtCards,
+    #[serde(rename = "Mucks Cards")]
+    MucksCards,
+    #[serde(rename = "Shows Cards")]
+    ShowsCards,
+    #[serde(rename = "Post Ante")]
+    PostAnte,
+    #[serde(rename = "Post SB")]
+    PostSmallBlind,
+    #[serde(rename = "Post BB")]
+    PostBigBlind,
+    Straddle,
+    #[serde(rename = "Post Dead")]
+    PostDead,
+    #[serde(rename = "Post Extra Blind")]
+    PostExtraBlind,
+    Fold,
+    Check,
+    Bet,
+    /// The amount of a raise is the total bet it makes
+    Raise,
+    Call,
+    #[serde(rename = "Added Chips")]
+    AddedChips,
+    #[serde(rename = "Sits Down")]
+    SitsDown,
+    #[serde(rename = "Stands Up")]
+    StandsUp,
+    #[serde(rename = "Added To Pot")]
+    AddedToPot,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhPot {
+    /// 0 for the main pot, then the number of the side pot
+    pub number: u32,
+    pub amount: Amount,
+    #[serde(default)]
+    pub rake: Amount,
+    #[serde(default)]
+    pub player_wins: Vec<OhhPlayerWin>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct OhhPlayerWin {
+    pub player_id: u32,
+    pub win_amount: Amount,
+}
+
+/// Error returned when reading an OpenHH file or converting one of its hands
+#[derive(Debug)]
+pub enum OpenHhError {
+    Json(serde_json::Error),
+    UnsupportedGame(String),
+    UnknownPlayer(u32),
+    InvalidCard(String),
+    MissingHeroCards,
+}
+
+impl fmt::Display for OpenHhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenHhError::Json(e) => write!(f, "invalid OpenHH hand: {}", e),
+            OpenHhError::UnsupportedGame(game) => write!(f, "unsupported game: {}", game),
+            OpenHhError::UnknownPlayer(id) => write!(f, "unknown player id: {}", id),
+            OpenHhError::InvalidCard(card) => write!(f, "invalid card: {}", card),
+            OpenHhError::MissingHeroCards => write!(f, "no cards dealt to the hero"),
+        }
+    }
+}
+
+impl std::error::Error for OpenHhError {}
+
+impl From<serde_json::Error> for OpenHhError {
+    fn from(e: serde_json::Error) -> Self {
+        OpenHhError::Json(e)
+    }
+}
+
+fn card_names(cards: &[parser::Card]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn parse_cards(names: &[String]) -> Result<Vec<parser::Card>, OpenHhError> {
+    names
+        .iter()
+        .map(|name| {
+            name.parse()
+                .map_err(|_| OpenHhError::InvalidCard(name.to_owned()))
+        })
+        .collect()
+}
+
+fn action(player_id: u32, action: OhhActionType, amount: Amount) -> OhhAction {
+    OhhAction {
+        action_number: 0,
+        player_id,
+        action,
+        amount,
+        is_allin: false,
+        cards: vec![],
+    }
+}
+
+/// Converts an action of a street, collected pots and uncalled bets being left to the pots
+fn convert_action(action: &parser::Action, player_id: u32) -> Option<OhhAction> {
+    let (action_type, amount) = match &action.action {
+        ActionType::Bet { amount } => (OhhActionType::Bet, *amount),
+        ActionType::Call { amount } => (OhhActionType::Call, *amount),
+        ActionType::Raise { amount, .. } => (OhhActionType::Raise, *amount),
+        ActionType::Check => (OhhActionType::Check, Amount::ZERO),
+        ActionType::Fold => (OhhActionType::Fold, Amount::ZERO),
+        ActionType::Post(PostType::Ante(amount)) => (OhhActionType::PostAnte, *amount),
+        ActionType::Post(PostType::SmallBlind(amount)) => (OhhActionType::PostSmallBlind, *amount),
+        ActionType::Post(PostType::BigBlind(amount)) => (OhhActionType::PostBigBlind, *amount),
+        ActionType::Post(PostType::Dead(amount)) => (OhhActionType::PostDead, *amount),
+        ActionType::Shows { cards } => {
+            return Some(OhhAction {
+                cards: card_names(&cards.cards),
+                ..self::action(player_id, OhhActionType::ShowsCards, Amount::ZERO)
+            })
+        }
+        ActionType::Collect { .. }
+        | ActionType::UncalledBet { .. }
+        | ActionType::CashoutRisk { .. }
+        | ActionType::Cashout { .. } => return None,
+    };
+    Some(OhhAction {
+        is_allin: action.is_all_in,
+        ..self::action(player_id, action_type, amount)
+    })
+}
+
+/// Writes a hand as OpenHH
+///
+/// Players are identified by their seat number. The blinds and antes are posted at the start
+/// of the pre-flop round, rebuilt from the button position when the hand has no posts, and
+/// the collected amounts are listed in the pots. Uncalled bets and hand descriptions have no
+/// place in the standard and are left out, and so has the tournament level, which a hand
+/// read back has at 0.
+impl From<&parser::Hand> for OpenHandHistory {
+    fn from(hand: &parser::Hand) -> Self {
+        let player_id = |name: &str| {
+            hand.seats
+                .iter()
+                .find(|seat| seat.player_name == name)
+                .map_or(0, |seat| seat.seat_number)
+        };
+        let blinds = &hand.hand_info.blinds;
+
+        let mut posts: Vec<OhhAction> = hand
+            .streets
+            .iter()
+            .flat_map(|street| street.actions.iter())
+            .filter(|action| matches!(action.action, ActionType::Post(_)))
+            .filter_map(|action| convert_action(action, player_id(&action.player_name)))
+            .collect();
+        if posts.is_empty() {
+            if let Some(ante) = blinds.ante {
+                for seat in &hand.seats {
+                    posts.push(action(seat.seat_number, OhhActionType::PostAnte, ante));
+                }
+            }
+            let (small_blind, big_blind) = hand.blind_seats();
+            for (seat, action_type, amount) in [
+                (
+                    small_blind,
+                    OhhActionType::PostSmallBlind,
+                    blinds.small_blind,
+                ),
+                (big_blind, OhhActionType::PostBigBlind, blinds.big_blind),
+            ] {
+                if let Some(seat) = seat {
+                    posts.push(action(seat.seat_number, action_type, amount));
+                }
+            }
+        }
+        let hero_id = player_id(&hand.dealt_cards.player_name);
+        posts.push(OhhAction {
+            cards: card_names(&hand.dealt_cards.hole_cards.cards),
+            ..action(hero_id, OhhActionType::DealtCards, Amount::ZERO)
+        });
+
+        let mut rounds: Vec<OhhRound> = hand
+            .streets
+            .iter()
+            .map(|street| OhhRound {
+                id: 0,
+                street: match street.street_type {
+                    StreetType::Preflop => OhhStreet::Preflop,
+                    StreetType::Flop => OhhStreet::Flop,
+                    StreetType::Turn => OhhStreet::Turn,
+                    StreetType::River => OhhStreet::River,
+                    StreetType::Showdown => OhhStreet::Showdown,
+                },
+                cards: card_names(&street.cards),
+                actions: street
+                    .actions
+                    .iter()
+                    .filter(|action| !matches!(action.action, ActionType::Post(_)))
+                    .filter_map(|action| convert_action(action, player_id(&action.player_name)))
+                    .collect(),
+            })
+            .collect();
+        match rounds.first_mut() {
+            Some(round) if round.street == OhhStreet::Preflop => {
+                round.actions.splice(0..0, posts);
+            }
+            _ => rounds.insert(
+                0,
+                OhhRound {
+                    id: 0,
+                    street: OhhStreet::Preflop,
+                    cards: vec![],
+                    actions: posts,
+                },
+            ),
+        }
+        for (id, round) in rounds.iter_mut().enumerate() {
+            round.id = id as u32;
+        }
+        for (number, action) in rounds
+            .iter_mut()
+            .flat_map(|round| round.actions.iter_mut())
+            .enumerate()
+        {
+            action.action_number = number as u32 + 1;
+        }
+
+        // pots are listed in the order they are collected
+        let mut pots: Vec<OhhPot> = vec![];
+        for action in hand.streets.iter().flat_map(|street| street.actions.iter()) {
+            if let ActionType::Collect { amount, pot } = &action.action {
+                let number = match pot {
+                    Pot::Main => 0,
+                    Pot::Side(n) => *n,
+                };
+                let index = match pots.iter().position(|pot| pot.number == number) {
+                    Some(index) => index,
+                    None => {
+                        pots.push(OhhPot {
+                            number,
+                            amount: Amount::ZERO,
+                            rake: Amount::ZERO,
+                            player_wins: vec![],
+                        });
+                        pots.len() - 1
+                    }
+                };
+                pots[index].amount += *amount;
+                pots[index].player_wins.push(OhhPlayerWin {
+                    player_id: player_id(&action.player_name),
+                    win_amount: *amount,
+                });
+            }
+        }
+        if !pots.iter().any(|pot| pot.number == 0) {
+            pots.insert(
+                0,
+                OhhPot {
+                    number: 0,
+                    amount: Amount::ZERO,
+                    rake: Amount::ZERO,
+                    player_wins: vec![],
+                },
+            );
+        }
+        // the main pot holds what the side pots do not, so that they add up to the total pot
+        let side_pots: Amount = pots
+            .iter()
+            .filter(|pot| pot.number != 0)
+            .map(|pot| pot.amount)
+            .sum();
+        if let Some(main_pot) = pots.iter_mut().find(|pot| pot.number == 0) {
+            main_pot.amount = hand.summary.pot - side_pots;
+            main_pot.rake = hand.summary.rake.unwrap_or_default();
+        }
+
+        let info = &hand.hand_info;
+        let currency = match hand.table_info.currency {
+            MoneyType::RealMoney => info.currency_code.as_str(),
+            MoneyType::PlayMoney => PLAY_MONEY,
+        };
+        let tournament_info = match &info.game_info {
+            GameInfo::Tournament(tournament) => Some(OhhTournamentInfo {
+                tournament_number: match &hand.table_info.table_name {
+                    TableName::Tournament(_, tournament_id, _) => tournament_id.to_string(),
+                    TableName::CashGame(_) => String::new(),
+                },
+                name: tournament.name.to_owned(),
+                start_date_utc: None,
+                currency: currency.to_owned(),
+                buyin_amount: tournament.buy_in,
+                fee_amount: tournament.rake,
+                bounty_fee_amount: Amount::ZERO,
+                tournament_type: String::from("MTT"),
+                flags: vec![],
+            }),
+            _ => None,
+        };
+        let (game_type, bet_type) = match info.poker_type {
+            PokerType::HoldemNoLimit => (OhhGameType::Holdem, OhhBetType::NoLimit),
+            PokerType::OmahaPotLimit | PokerType::Omaha5PotLimit => {
+                (OhhGameType::Omaha, OhhBetType::PotLimit)
+            }
+        };
+        OpenHandHistory {
+            ohh: OhhHand {
+                spec_version: String::from(SPEC_VERSION),
+                site_name: info.site.to_owned(),
+                network_name: Site::from_name(&info.site)
+                    .map_or(info.site.as_str(), |site| site.network())
+                    .to_owned(),
+                internal_version: String::new(),
+                tournament: tournament_info.is_some(),
+                tournament_info,
+                game_number: info.hand_id.to_owned(),
+                start_date_utc: info.datetime,
+                table_name: match &hand.table_info.table_name {
+                    TableName::Tournament(name, tournament_id, table_id) => {
+                        format!("{}({})#{:03}", name, tournament_id, table_id)
+                    }
+                    TableName::CashGame(name) => name.to_owned(),
+                },
+                game_type,
+                bet_limit: OhhBetLimit {
+                    bet_type,
+                    bet_cap: 0.0,
+                },
+                table_size: hand.table_info.max_players,
+                currency: currency.to_owned(),
+                dealer_seat: hand.table_info.button,
+                small_blind_amount: blinds.small_blind,
+                big_blind_amount: blinds.big_blind,
+                ante_amount: blinds.ante.unwrap_or_default(),
+                hero_player_id: Some(hero_id),
+                flags: match info.game_info {
+                    GameInfo::HoldUp(_) => vec![String::from("Fast")],
+                    _ => vec![],
+                },
+                players: hand
+                    .seats
+                    .iter()
+                    .map(|seat| OhhPlayer {
+                        id: seat.seat_number,
+                        seat: seat.seat_number,
+                        name: seat.player_name.to_owned(),
+                        starting_stack: seat.stack,
+                        player_bounty: seat.bounty,
+                        is_sitting_out: false,
+                    })
+                    .collect(),
+                rounds,
+                pots,
+            },
+        }
+    }
+}
+
+/// Reads an OpenHH hand
+///
+/// Antes, blinds and dead blinds are kept as posts, straddles, extra blinds, mucks and
+/// seating changes are not. Pots are collected at the end of the last round, and the summary
+/// lists the players who showed their cards or won a pot. The tournament level is not part of
+/// the standard and is left at 0, as is the table number of tournament tables not named
+/// "Name(tournament)#table".
+impl TryFrom<OpenHandHistory> for parser::Hand {
+    type Error = OpenHhError;
+
+    fn try_from(history: OpenHandHistory) -> Result<Self, Self::Error> {
+        let ohh = history.ohh;
+        let player_name = |id: u32| {
+            ohh.players
+                .iter()
+                .find(|player| player.id == id)
+                .map(|player| player.name.to_owned())
+                .ok_or(OpenHhError::UnknownPlayer(id))
+        };
+        let actions = || ohh.rounds.iter().flat_map(|round| round.actions.iter());
+
+        let hero_cards = actions()
+            .find(|action| {
+                action.action == OhhActionType::DealtCards
+                    && ohh.hero_player_id.is_none_or(|id| id == action.player_id)
+                    && !action.cards.is_empty()
+            })
+            .ok_or(OpenHhError::MissingHeroCards)?;
+        let dealt_cards = parser::DealtToHero {
+            player_name: player_name(hero_cards.player_id)?,
+            hole_cards: parser::HoleCards {
+                cards: parse_cards(&hero_cards.cards)?,
+            },
+        };
+        let poker_type = match (&ohh.game_type, &ohh.bet_limit.bet_type) {
+            (OhhGameType::Holdem, OhhBetType::NoLimit) => PokerType::HoldemNoLimit,
+            (OhhGameType::Omaha, OhhBetType::PotLimit) => {
+                match dealt_cards.hole_cards.cards.len() {
+                    5 => PokerType::Omaha5PotLimit,
+                    _ => PokerType::OmahaPotLimit,
+                }
+            }
+            (game_type, bet_type) => {
+                return Err(OpenHhError::UnsupportedGame(format!(
+                    "{:?} {:?}",
+                    game_type, bet_type
+                )))
+            }
+        };
+        let (game_info, table_name) = match &ohh.tournament_info {
+            Some(tournament) => {
+                let table_name = match parse_table_name_tournament(&ohh.table_name) {
+                    Ok(("", table_name)) => table_name.into_owned(),
+                    _ => TableName::Tournament(
+                        tournament.name.to_owned(),
+                        tournament.tournament_number.parse().unwrap_or_default(),
+                        0,
+                    ),
+                };
+                let info = parser::TournamentInfo {
+                    name: tournament.name.to_owned(),
+                    buy_in: tournament.buyin_amount,
+                    rake: tournament.fee_amount,
+                    level: 0,
+                };
+                (GameInfo::Tournament(info), table_name)
+            }
+            None => (
+                match ohh.flags.iter().any(|flag| flag == "Fast") {
+                    true => GameInfo::HoldUp(ohh.table_name.to_owned()),
+                    false => GameInfo::CashGame,
+                },
+                TableName::CashGame(ohh.table_name.to_owned()),
+            ),
+        };
+
+        let mut streets = vec![];
+        for round in &ohh.rounds {
+            let street_type = match round.street {
+                OhhStreet::Preflop => StreetType::Preflop,
+                OhhStreet::Flop => StreetType::Flop,
+                OhhStreet::Turn => StreetType::Turn,
+                OhhStreet::River => StreetType::River,
+                OhhStreet::Showdown => StreetType::Showdown,
+            };
+            let mut highest_bet = match street_type {
+                StreetType::Preflop => ohh.big_blind_amount,
+                _ => Amount::ZERO,
+            };
+            let mut actions = vec![];
+            for action in &round.actions {
+                let amount = action.amount;
+                let action_type = match action.action {
+                    OhhActionType::PostAnte => ActionType::Post(PostType::Ante(amount)),
+                    OhhActionType::PostSmallBlind => ActionType::Post(PostType::SmallBlind(amount)),
+                    OhhActionType::PostBigBlind => ActionType::Post(PostType::BigBlind(amount)),
+                    OhhActionType::PostDead => ActionType::Post(PostType::Dead(amount)),
+                    OhhActionType::Fold => ActionType::Fold,
+                    OhhActionType::Check => ActionType::Check,
+                    OhhActionType::Call => ActionType::Call { amount },
+                    OhhActionType::Bet => {
+                        highest_bet = amount;
+                        ActionType::Bet { amount }
+                    }
+                    OhhActionType::Raise => {
+                        let to_call = amount - highest_bet;
+                        highest_bet = amount;
+                        ActionType::Raise { to_call, amount }
+                    }
+                    OhhActionType::ShowsCards => ActionType::Shows {
+                        cards: parser::HoleCards {
+                            cards: parse_cards(&action.cards)?,
+                        },
+                    },
+                    _ => continue,
+                };
+                actions.push(parser::Action {
+                    player_name: player_name(action.player_id)?,
+                    action: action_type,
+                    is_all_in: action.is_allin,
+                });
+            }
+            streets.push(parser::Street {
+                street_type,
+                cards: parse_cards(&round.cards)?,
+                actions,
+            });
+        }
+        let mut collects = vec![];
+        for pot in &ohh.pots {
+            for win in &pot.player_wins {
+                collects.push(parser::Action {
+                    player_name: player_name(win.player_id)?,
+                    action: ActionType::Collect {
+                        amount: win.win_amount,
+                        pot: match pot.number {
+                            0 => Pot::Main,
+                            n => Pot::Side(n),
+                        },
+                    },
+                    is_all_in: false,
+                });
+            }
+        }
+        if let Some(street) = streets.last_mut() {
+            street.actions.extend(collects);
+        }
+
+        let board: Vec<parser::Card> = streets
+            .iter()
+            .flat_map(|street| street.cards.iter().cloned())
+            .collect();
+        let mut players = vec![];
+        for player in &ohh.players {
+            let won: Amount = ohh
+                .pots
+                .iter()
+                .flat_map(|pot| pot.player_wins.iter())
+                .filter(|win| win.player_id == player.id)
+                .map(|win| win.win_amount)
+                .sum();
+            let shown = actions().find(|action| {
+                action.action == OhhActionType::ShowsCards && action.player_id == player.id
+            });
+            let hole_cards = match shown {
+                Some(action) => Some(parser::HoleCards {
+                    cards: parse_cards(&action.cards)?,
+                }),
+                None => None,
+            };
+            if won == Amount::ZERO && hole_cards.is_none() {
+                continue;
+            }
+            players.push(parser::SummaryPlayer {
+                name: player.name.to_owned(),
+                seat: player.seat,
+                hole_cards,
+                result: match won > Amount::ZERO {
+                    true => SummaryResult::Won(won),
+                    false => SummaryResult::Lost,
+                },
+                hand_category: None,
+            });
+        }
+        let rake: Amount = ohh.pots.iter().map(|pot| pot.rake).sum();
+
+        Ok(parser::Hand {
+            hand_info: parser::HandInfo {
+                game_info,
+                hand_id: ohh.game_number.to_owned(),
+                poker_type,
+                blinds: parser::Blinds {
+                    ante: Some(ohh.ante_amount).filter(|ante| *ante > Amount::ZERO),
+                    small_blind: ohh.small_blind_amount,
+                    big_blind: ohh.big_blind_amount,
+                },
+                datetime: ohh.start_date_utc,
+                site: ohh.site_name.to_owned(),
+                // play money hands keep the currency of the real money games of their site
+                currency_code: match ohh.currency.as_str() {
+                    PLAY_MONEY => Site::from_name(&ohh.site_name)
+                        .unwrap_or(Site::Winamax)
+                        .currency_code()
+                        .to_owned(),
+                    currency => currency.to_owned(),
+                },
+            },
+            table_info: parser::TableInfo {
+                table_name,
+                max_players: ohh.table_size,
+                currency: match ohh.currency.as_str() {
+                    PLAY_MONEY => MoneyType::PlayMoney,
+                    _ => MoneyType::RealMoney,
+                },
+                button: ohh.dealer_seat,
+            },
+            seats: ohh
+                .players
+                .iter()
+                .map(|player| parser::Seat {
+                    seat_number: player.seat,
+                    player_name: player.name.to_owned(),
+                    stack: player.starting_stack,
+                    bounty: player.player_bounty,
+                })
+                .collect(),
+            dealt_cards,
+            streets,
+            summary: parser::Summary {
+                pot: ohh.pots.iter().map(|pot| pot.amount).sum(),
+                rake: Some(rake).filter(|rake| *rake > Amount::ZERO),
+                players,
+                boards: match board.is_empty() {
+                    true => vec![],
+                    false => {
+                        let mut cards: Vec<Option<parser::Card>> =
+                            board.into_iter().map(Some).collect();
+                        cards.resize_with(5, || None);
+                        vec![parser::Board { cards }]
+                    }
+                },
+            },
+        })
+    }
+}
+
+/// Tells OpenHH files from text hand histories, which never start with a JSON object
+pub fn is_openhh(input: &str) -> bool {
+    input.trim_start().starts_with('{')
+}
+
+/// Reads every hand of an OpenHH file, whatever separates them
+pub fn parse_hands(input: &str) -> Result<Vec<parser::Hand>, OpenHhError> {
+    serde_json::Deserializer::from_str(input)
+        .into_iter::<OpenHandHistory>()
+        .map(|history| parser::Hand::try_from(history?))
+        .collect()
+}
+
+/// Reads every hand of an OpenHH file, skipping the ones that cannot be converted.
+///
+/// A JSON syntax error leaves no way to find the next hand, so the rest of the file is
+/// reported as a single skipped block.
+pub fn parse_hands_recovering(input: &str) -> (Vec<parser::Hand>, ParseReport<OpenHhError>) {
+    let mut hands = vec![];
+    let mut report = ParseReport::default();
+    let mut stream = serde_json::Deserializer::from_str(input).into_iter::<serde_json::Value>();
+    loop {
+        let offset = stream.byte_offset();
+        let start = input.len() - input[offset..].trim_start().len();
+        let value = match stream.next() {
+            Some(Ok(value)) => value,
+            Some(Err(error)) => {
+                report.skipped.push(SkippedHand {
+                    span: start..input.len(),
+                    error: error.into(),
+                });
+                break;
+            }
+            None => break,
+        };
+        let hand = serde_json::from_value::<OpenHandHistory>(value)
+            .map_err(OpenHhError::from)
+            .and_then(parser::Hand::try_from);
+        match hand {
+            Ok(hand) => hands.push(hand),
+            Err(error) => report.skipped.push(SkippedHand {
+                span: start..stream.byte_offset(),
+                error,
+            }),
+        }
+    }
+    (hands, report)
+}
+
+/// Writer for OpenHH files, with one indented hand after another
+pub struct OpenHhWriter;
+
+impl SiteWriter for OpenHhWriter {
+    fn write_hand(&self, hand: &parser::Hand) -> String {
+        // the model holds nothing that JSON cannot represent
+        serde_json::to_string_pretty(&OpenHandHistory::from(hand)).unwrap() + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ggpoker::GGPokerParser;
+    use crate::parser::{Board, Hand, HoleCards, SummaryPlayer, WinamaxParser};
+    use crate::site::SiteParser;
+
+    /// What is left of a hand once written as OpenHH and read back
+    fn openhh_view(hand: &Hand) -> Hand {
+        let mut hand = hand.clone();
+        let info = &mut hand.hand_info;
+        match &mut info.game_info {
+            GameInfo::Tournament(tournament) => tournament.level = 0,
+            GameInfo::HoldUp(pool) => {
+                if let TableName::CashGame(table_name) = &hand.table_info.table_name {
+                    *pool = table_name.to_owned();
+                }
+            }
+            GameInfo::CashGame => {}
+        }
+        // collects are moved to the end of the hand, grouped by pot
+        let mut collects: Vec<parser::Action> = vec![];
+        for street in &mut hand.streets {
+            for action in &street.actions {
+                if let ActionType::Collect { pot, .. } = &action.action {
+                    let same_pot = |other: &parser::Action| matches!(&other.action, ActionType::Collect { pot: other, .. } if other == pot);
+                    let index = match collects.iter().rposition(same_pot) {
+                        Some(index) => index + 1,
+                        None => collects.len(),
+                    };
+                    collects.insert(index, action.clone());
+                }
+            }
+            street.actions.retain(|action| {
+                !matches!(
+                    action.action,
+                    ActionType::Collect { .. }
+                        | ActionType::UncalledBet { .. }
+                        | ActionType::CashoutRisk { .. }
+                        | ActionType::Cashout { .. }
+                )
+            });
+        }
+        if let Some(street) = hand.streets.last_mut() {
+            street.actions.extend(collects.iter().cloned());
+        }
+
+        let actions: Vec<&parser::Action> = hand
+            .streets
+            .iter()
+            .flat_map(|street| street.actions.iter())
+            .collect();
+        hand.summary.players = hand
+            .seats
+            .iter()
+            .filter_map(|seat| {
+                let won: Amount = collects
+                    .iter()
+                    .filter(|action| action.player_name == seat.player_name)
+                    .map(|action| match action.action {
+                        ActionType::Collect { amount, .. } => amount,
+                        _ => Amount::ZERO,
+                    })
+                    .sum();
+                let hole_cards = actions.iter().find_map(|action| match &action.action {
+                    ActionType::Shows { cards } if action.player_name == seat.player_name => {
+                        Some(cards.clone())
+                    }
+                    _ => None,
+                });
+                if won == Amount::ZERO && hole_cards.is_none() {
+                    return None;
+                }
+                Some(SummaryPlayer {
+                    name: seat.player_name.to_owned(),
+                    seat: seat.seat_number,
+                    hole_cards,
+                    result: match won > Amount::ZERO {
+                        true => SummaryResult::Won(won),
+                        false => SummaryResult::Lost,
+                    },
+                    hand_category: None,
+                })
+            })
+            .collect();
+        hand.summary.rake = hand.summary.rake.filter(|rake| *rake > Amount::ZERO);
+        let board: Vec<Option<parser::Card>> = hand
+            .streets
+            .iter()
+            .flat_map(|street| street.cards.iter().cloned().map(Some))
+            .collect();
+        hand.summary.boards = match board.is_empty() {
+            true => vec![],
+            false => {
+                let mut cards = board;
+                cards.resize_with(5, || None);
+                vec![Board { cards }]
+            }
+        };
+        hand
+    }
+
+    #[test]
+    fn test_openhh_round_trip() {
+        for data in [
+            include_str!("../samples/sample1.txt"),
+            include_str!("../samples/sample2.txt"),
+            include_str!("../samples/sample_omaha.txt"),
+            include_str!("../samples/sample_cash_play_money.txt"),
+        ] {
+            let hands = WinamaxParser.parse_hands(data).unwrap();
+            let written = OpenHhWriter.write_hands(&hands);
+            let expected: Vec<Hand> = hands.iter().map(openhh_view).collect();
+            assert_eq!(parse_hands(&written).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_write_openhh_hand() {
+        let data = include_str!("../samples/sample1.txt");
+        let hands = WinamaxParser.parse_hands(data).unwrap();
+        let ohh = OpenHandHistory::from(&hands[0]).ohh;
+        assert_eq!(ohh.table_name, "WESTERN(655531954)#077");
+        assert_eq!(ohh.site_name, "Winamax");
+        assert_eq!(ohh.currency, "EUR");
+        assert!(ohh.tournament);
+
+        let preflop = &ohh.rounds[0].actions;
+        assert_eq!(preflop[0].action_number, 1);
+        assert!(preflop
+            .iter()
+            .any(|action| action.action == OhhActionType::PostBigBlind));
+        let dealt = preflop
+            .iter()
+            .find(|action| action.action == OhhActionType::DealtCards)
+            .unwrap();
+        assert_eq!(Some(dealt.player_id), ohh.hero_player_id);
+        let pot: Amount = ohh.pots.iter().map(|pot| pot.amount).sum();
+        assert_eq!(pot, hands[0].summary.pot);
+    }
+
+    #[test]
+    fn test_write_openhh_site() {
+        let data = include_str!("../samples/ggpoker.txt");
+        let hand = &GGPokerParser.parse_hands(data).unwrap()[2];
+        let ohh = OpenHandHistory::from(hand).ohh;
+        assert_eq!(ohh.site_name, "GGPoker");
+        assert_eq!(ohh.network_name, "GGNetwork");
+        assert_eq!(ohh.currency, "USD");
+        let parsed = Hand::try_from(OpenHandHistory { ohh }).unwrap();
+        assert_eq!(parsed.hand_info.site, "GGPoker");
+        assert_eq!(parsed.hand_info.currency_code, "USD");
+    }
+
+    #[test]
+    fn test_parse_openhh_hand() {
+        let data = r#"{"ohh": {
+            "spec_version": "1.4.6",
+            "site_name": "Other",
+            "network_name": "Other",
+            "tournament": false,
+            "game_number": "42",
+            "start_date_utc": "2023-05-21T19:49:44Z",
+            "table_name": "Tokyo",
+            "game_type": "Holdem",
+            "bet_limit": {"bet_type": "NL", "bet_cap": 0},
+            "table_size": 6,
+            "currency": "EUR",
+            "dealer_seat": 1,
+            "small_blind_amount": 0.01,
+            "big_blind_amount": 0.02,
+            "hero_player_id": 7,
+            "players": [
+                {"id": 7, "seat": 1, "name": "Hero", "starting_stack": 2},
+                {"id": 8, "seat": 2, "name": "Villain", "starting_stack": 1.5}
+            ],
+            "rounds": [{"id": 0, "street": "Preflop", "actions": [
+                {"action_number": 1, "player_id": 7, "action": "Post SB", "amount": 0.01},
+                {"action_number": 2, "player_id": 8, "action": "Post BB", "amount": 0.02},
+                {"action_number": 3, "player_id": 7, "action": "Dealt Cards", "cards": ["Ah", "Kd"]},
+                {"action_number": 4, "player_id": 7, "action": "Raise", "amount": 0.06},
+                {"action_number": 5, "player_id": 8, "action": "Fold"}
+            ]}],
+            "pots": [{"number": 0, "amount": 0.08, "rake": 0,
+                "player_wins": [{"player_id": 7, "win_amount": 0.08}]}]
+        }}"#;
+        assert!(is_openhh(data));
+        let hands = parse_hands(&format!("{}\n{}", data, data)).unwrap();
+        assert_eq!(hands.len(), 2);
+        let hand = &hands[0];
+        assert_eq!(hand.seats[1].stack, Amount::from_cents(150));
+        assert_eq!(hand.dealt_cards.player_name, "Hero");
+        assert_eq!(
+            hand.dealt_cards.hole_cards,
+            HoleCards {
+                cards: vec!["Ah".parse().unwrap(), "Kd".parse().unwrap()]
+            }
+        );
+        assert_eq!(
+            hand.streets[0].actions[0].action,
+            ActionType::Post(PostType::SmallBlind(Amount::from_cents(1)))
+        );
+        assert_eq!(hand.streets[0].actions[1].player_name, "Villain");
+        assert_eq!(
+            hand.streets[0].actions[2].action,
+            ActionType::Raise {
+                to_call: Amount::from_cents(4),
+                amount: Amount::from_cents(6)
+            }
+        );
+        assert_eq!(hand.summary.pot, Amount::from_cents(8));
+        assert_eq!(hand.summary.rake, None);
+        assert_eq!(
+            hand.summary.players[0].result,
+            SummaryResult::Won(Amount::from_cents(8))
+        );
+
+        let unknown = data.replace(
+            r#""player_id": 8, "action": "Fold""#,
+            r#""player_id": 9, "action": "Fold""#,
+        );
+        assert!(matches!(
+            parse_hands(&unknown),
+            Err(OpenHhError::UnknownPlayer(9))
+        ));
+
+        let input = format!("{}\n{}\n{}", data, unknown, data);
+        let (hands, report) = parse_hands_recovering(&input);
+        assert_eq!(hands.len(), 2);
+        assert_eq!(report.skipped.len(), 1);
+        let skipped = &report.skipped[0];
+        assert_eq!(&input[skipped.span.clone()], unknown);
+        assert!(matches!(skipped.error, OpenHhError::UnknownPlayer(9)));
+
+        let (hands, report) = parse_hands_recovering(&format!("{}\n{{\"ohh\": ", data));
+        assert_eq!(hands.len(), 1);
+        assert!(matches!(report.skipped[0].error, OpenHhError::Json(_)));
+    }
+}
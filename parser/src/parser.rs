@@ -1,29 +1,35 @@
 use std::fmt;
+use std::str::FromStr;
 
 use chrono::prelude::*;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_till, take_until, take_while};
-use nom::character::complete::{alpha1, anychar, char, line_ending, none_of, not_line_ending};
-use nom::combinator::{eof, map, map_res, opt};
+use nom::character::complete::{anychar, char, line_ending, none_of, one_of};
+use nom::combinator::{eof, map, opt};
 use nom::multi::{many0, many1, many_till, separated_list0, separated_list1};
-use nom::number::complete::double;
 use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple, Tuple};
 use nom::{IResult, Parser};
-
-#[derive(Debug, PartialEq)]
-pub struct TournamentInfo {
-    name: String,
-    buy_in: f64,
-    rake: f64,
-    level: u32,
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::errors::{ParseError, ParseReport, Section};
+use crate::site::{Site, SiteParser};
+use crate::timezone::parse_datetime;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TournamentInfo<S = String> {
+    pub name: S,
+    pub buy_in: Amount,
+    pub rake: Amount,
+    pub level: u32,
 }
 
-impl TournamentInfo {
-    fn parse(input: &str) -> IResult<&str, TournamentInfo> {
+impl<'a> TournamentInfo<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let name_parser = delimited(tag("\""), take_while(|c: char| c != '"'), tag("\""));
 
-        let buyin_parser = terminated(double, opt(tag("€")));
-        let rake_parser = terminated(double, opt(tag("€")));
+        let buyin_parser = terminated(Amount::parse, opt(tag("€")));
+        let rake_parser = terminated(Amount::parse, opt(tag("€")));
 
         let buyin_rake_parser = preceded(
             tag("buyIn: "),
@@ -44,24 +50,34 @@ impl TournamentInfo {
         Ok((
             input,
             TournamentInfo {
-                name: name.to_owned(),
+                name,
                 buy_in,
                 rake,
                 level,
             },
         ))
     }
+
+    pub fn into_owned(self) -> TournamentInfo {
+        TournamentInfo {
+            name: self.name.to_owned(),
+            buy_in: self.buy_in,
+            rake: self.rake,
+            level: self.level,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
-pub enum GameInfo {
-    Tournament(TournamentInfo),
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum GameInfo<S = String> {
+    Tournament(TournamentInfo<S>),
     CashGame,
-    HoldUp,
+    /// Fast-fold cash game, with the name of its player pool
+    HoldUp(S),
 }
 
-impl GameInfo {
-    fn parse(input: &str) -> IResult<&str, GameInfo> {
+impl<'a> GameInfo<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let winamax = tag("Winamax Poker - ");
         let tournament = preceded(
             tag("Tournament "),
@@ -69,24 +85,35 @@ impl GameInfo {
         );
         let cashgame = map(tag("CashGame"), |_| GameInfo::CashGame);
         let hold_up = map(
-            preceded(tag("HOLD-UP"), delimited(tag(" \""), alpha1, char('"'))),
-            |_| GameInfo::HoldUp,
+            preceded(
+                tag("HOLD-UP"),
+                delimited(tag(" \""), take_until("\""), char('"')),
+            ),
+            GameInfo::HoldUp,
         );
         let (input, game_info) =
             preceded(winamax, alt((tournament, cashgame, hold_up))).parse(input)?;
         Ok((input, game_info))
     }
+
+    pub fn into_owned(self) -> GameInfo {
+        match self {
+            GameInfo::Tournament(info) => GameInfo::Tournament(info.into_owned()),
+            GameInfo::CashGame => GameInfo::CashGame,
+            GameInfo::HoldUp(pool) => GameInfo::HoldUp(pool.to_owned()),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Blinds {
-    pub ante: Option<f64>,
-    pub small_blind: f64,
-    pub big_blind: f64,
+    pub ante: Option<Amount>,
+    pub small_blind: Amount,
+    pub big_blind: Amount,
 }
 
-fn parse_blind(input: &str) -> IResult<&str, f64> {
-    let (input, blind) = terminated(double, opt(tag("€"))).parse(input)?;
+fn parse_blind(input: &str) -> IResult<&str, Amount> {
+    let (input, blind) = terminated(Amount::parse, opt(tag("€"))).parse(input)?;
     Ok((input, blind))
 }
 
@@ -115,31 +142,64 @@ impl Blinds {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum PokerType {
     HoldemNoLimit,
+    OmahaPotLimit,
+    Omaha5PotLimit,
 }
 
 impl PokerType {
     fn parse(input: &str) -> IResult<&str, PokerType> {
-        let (input, _) = tag("Holdem no limit").parse(input)?;
-        Ok((input, PokerType::HoldemNoLimit))
+        let (input, poker_type) = alt((
+            map(tag("Holdem no limit"), |_| PokerType::HoldemNoLimit),
+            map(tag("Omaha5 pot limit"), |_| PokerType::Omaha5PotLimit),
+            map(tag("Omaha pot limit"), |_| PokerType::OmahaPotLimit),
+        ))
+        .parse(input)?;
+        Ok((input, poker_type))
+    }
+
+    /// Number of hole cards dealt to each player
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            PokerType::HoldemNoLimit => 2,
+            PokerType::OmahaPotLimit => 4,
+            PokerType::Omaha5PotLimit => 5,
+        }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct HandInfo {
-    pub game_info: GameInfo,
-    pub hand_id: String,
+impl fmt::Display for PokerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PokerType::HoldemNoLimit => "holdem-no-limit",
+                PokerType::OmahaPotLimit => "omaha-pot-limit",
+                PokerType::Omaha5PotLimit => "omaha5-pot-limit",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct HandInfo<S = String> {
+    pub game_info: GameInfo<S>,
+    pub hand_id: S,
     pub poker_type: PokerType,
     pub blinds: Blinds,
     pub datetime: DateTime<Utc>,
+    /// Name of the site the hand was played on, as in "Winamax"
+    pub site: S,
+    /// ISO 4217 code of the real money amounts, as in "EUR"
+    pub currency_code: S,
 }
 
-impl HandInfo {
-    fn parse(input: &str) -> IResult<&str, HandInfo> {
+impl<'a> HandInfo<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let hand_id = preceded(tag("HandId: #"), take_while(|c: char| c != ' '));
-        let datetime = terminated(not_line_ending, line_ending);
         let (input, (game_info, _, hand_id, _, poker_type, _, blinds, _, datetime)) = (
             GameInfo::parse,
             tag(" - "),
@@ -149,48 +209,55 @@ impl HandInfo {
             char(' '),
             delimited(char('('), Blinds::parse, char(')')),
             tag(" - "),
-            map_res(datetime, |s: &str| {
-                Utc.datetime_from_str(s, "%Y/%m/%d %H:%M:%S %Z")
-            }),
+            terminated(parse_datetime, line_ending),
         )
             .parse(input)?;
         Ok((
             input,
             HandInfo {
                 game_info,
-                hand_id: hand_id.to_owned(),
+                hand_id,
                 poker_type,
                 blinds,
                 datetime,
+                site: Site::Winamax.name(),
+                currency_code: Site::Winamax.currency_code(),
             },
         ))
     }
+
+    pub fn into_owned(self) -> HandInfo {
+        HandInfo {
+            game_info: self.game_info.into_owned(),
+            hand_id: self.hand_id.to_owned(),
+            poker_type: self.poker_type,
+            blinds: self.blinds,
+            datetime: self.datetime,
+            site: self.site.to_owned(),
+            currency_code: self.currency_code.to_owned(),
+        }
+    }
 }
 
-fn parse_table_name_tournament(input: &str) -> IResult<&str, TableName> {
+pub(crate) fn parse_table_name_tournament(input: &str) -> IResult<&str, TableName<&str>> {
     let (input, (name, tournament_id, table_id)) = tuple((
         terminated(take_while(|c| c != '('), tag("(")),
         terminated(nom::character::complete::u32, tag(")#")),
         nom::character::complete::u32,
     ))
     .parse(input)?;
-    Ok((
-        input,
-        TableName::Tournament(name.to_owned(), tournament_id, table_id),
-    ))
+    Ok((input, TableName::Tournament(name, tournament_id, table_id)))
 }
 
-#[derive(Debug, PartialEq)]
-pub enum TableName {
-    Tournament(String, u32, u32),
-    CashGame(String),
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum TableName<S = String> {
+    Tournament(S, u32, u32),
+    CashGame(S),
 }
 
-impl TableName {
-    fn parse(input: &str) -> IResult<&str, TableName> {
-        let parse_cashgame = map(take_while(|c| c != '\''), |name: &str| {
-            TableName::CashGame(name.to_owned())
-        });
+impl<'a> TableName<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        let parse_cashgame = map(take_while(|c| c != '\''), TableName::CashGame);
         let (input, table_name) = delimited(
             tag("'"),
             alt((parse_table_name_tournament, parse_cashgame)),
@@ -199,9 +266,18 @@ impl TableName {
         .parse(input)?;
         Ok((input, table_name))
     }
+
+    pub fn into_owned(self) -> TableName {
+        match self {
+            TableName::Tournament(name, tournament_id, table_id) => {
+                TableName::Tournament(name.to_owned(), tournament_id, table_id)
+            }
+            TableName::CashGame(name) => TableName::CashGame(name.to_owned()),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum MoneyType {
     RealMoney,
     PlayMoney,
@@ -221,16 +297,16 @@ impl MoneyType {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct TableInfo {
-    pub table_name: TableName,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TableInfo<S = String> {
+    pub table_name: TableName<S>,
     pub max_players: u32,
     pub currency: MoneyType,
     pub button: u32,
 }
 
-impl TableInfo {
-    fn parse(input: &str) -> IResult<&str, TableInfo> {
+impl<'a> TableInfo<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let (input, (table_name, _, max_players, currency, _, button, _, _)) = tuple((
             preceded(tag("Table: "), TableName::parse),
             tag(" "),
@@ -252,23 +328,32 @@ impl TableInfo {
             },
         ))
     }
+
+    pub fn into_owned(self) -> TableInfo {
+        TableInfo {
+            table_name: self.table_name.into_owned(),
+            max_players: self.max_players,
+            currency: self.currency,
+            button: self.button,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Seat {
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Seat<S = String> {
     pub seat_number: u32,
-    pub player_name: String,
-    pub stack: f64,
-    pub bounty: Option<f64>,
+    pub player_name: S,
+    pub stack: Amount,
+    pub bounty: Option<Amount>,
 }
 
-impl Seat {
-    fn parse(input: &str) -> IResult<&str, Seat> {
+impl<'a> Seat<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let stack_bounty = tuple((
-            terminated(double, opt(tag("€"))),
+            terminated(Amount::parse, opt(tag("€"))),
             opt(preceded(
                 tag(", "),
-                terminated(double, terminated(opt(tag("€")), tag(" bounty"))),
+                terminated(Amount::parse, terminated(opt(tag("€")), tag(" bounty"))),
             )),
         ));
         let (input, (seat_number, _, player_name, _, (stack, bounty))) = tuple((
@@ -283,29 +368,40 @@ impl Seat {
             input,
             Seat {
                 seat_number,
-                player_name: player_name.to_owned(),
+                player_name,
                 stack,
                 bounty,
             },
         ))
     }
+
+    pub fn into_owned(self) -> Seat {
+        Seat {
+            seat_number: self.seat_number,
+            player_name: self.player_name.to_owned(),
+            stack: self.stack,
+            bounty: self.bounty,
+        }
+    }
 }
 
-fn parse_seats(input: &str) -> IResult<&str, Vec<Seat>> {
+fn parse_seats(input: &str) -> IResult<&str, Vec<Seat<&str>>> {
     let (input, seats) = many1(terminated(Seat::parse, line_ending)).parse(input)?;
     Ok((input, seats))
 }
 
-fn parse_amount(input: &str) -> IResult<&str, f64> {
-    let (input, amount) = terminated(double, opt(tag("€"))).parse(input)?;
+fn parse_amount(input: &str) -> IResult<&str, Amount> {
+    let (input, amount) = terminated(Amount::parse, opt(tag("€"))).parse(input)?;
     Ok((input, amount))
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum PostType {
-    BigBlind(f64),
-    SmallBlind(f64),
-    Ante(f64),
+    BigBlind(Amount),
+    SmallBlind(Amount),
+    Ante(Amount),
+    /// Blind posted out of turn, going to the pot without counting as a bet
+    Dead(Amount),
 }
 
 impl PostType {
@@ -324,16 +420,64 @@ impl PostType {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Pot a player collects from, side pots being numbered from 1
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Pot {
+    Main,
+    Side(u32),
+}
+
+impl Pot {
+    /// Parses "pot", "main pot", "side pot 1" and "side pot-1"
+    pub(crate) fn parse(input: &str) -> IResult<&str, Pot> {
+        alt((
+            map(tag("main pot"), |_| Pot::Main),
+            map(
+                preceded(
+                    tag("side pot"),
+                    opt(preceded(one_of(" -"), nom::character::complete::u32)),
+                ),
+                |n| Pot::Side(n.unwrap_or(1)),
+            ),
+            map(tag("pot"), |_| Pot::Main),
+        ))(input)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ActionType {
-    Bet { amount: f64 },
-    Call { amount: f64 },
+    Bet {
+        amount: Amount,
+    },
+    Call {
+        amount: Amount,
+    },
     Check,
     Fold,
     Post(PostType),
-    Raise { to_call: f64, amount: f64 },
-    Collect,
-    Shows,
+    Raise {
+        to_call: Amount,
+        amount: Amount,
+    },
+    Collect {
+        amount: Amount,
+        pot: Pot,
+    },
+    Shows {
+        cards: HoleCards,
+    },
+    /// Part of a bet that no one called, given back to the player
+    UncalledBet {
+        amount: Amount,
+    },
+    /// Amount paid by a player who cashed out an all-in on GGPoker, outside of the pots
+    CashoutRisk {
+        amount: Amount,
+    },
+    /// Amount received by a player who cashed out an all-in on GGPoker, outside of the pots
+    Cashout {
+        amount: Amount,
+    },
 }
 
 impl ActionType {
@@ -356,14 +500,22 @@ impl ActionType {
                 ),
                 |(to_call, _, amount)| ActionType::Raise { to_call, amount },
             ),
-            // "collected" and "shows" are not actual actions, we don't care about the rest
-            // of the line
-            map(preceded(tag("collected"), take_until("\n")), |_| {
-                ActionType::Collect
-            }),
-            map(preceded(tag("shows"), take_until("\n")), |_| {
-                ActionType::Shows
-            }),
+            map(
+                preceded(
+                    tag("collected "),
+                    separated_pair(parse_amount, tag(" from "), Pot::parse),
+                ),
+                |(amount, pot)| ActionType::Collect { amount, pot },
+            ),
+            // the description of the shown hand is not kept
+            map(
+                delimited(
+                    tag("shows ["),
+                    HoleCards::parse,
+                    pair(char(']'), take_until("\n")),
+                ),
+                |cards| ActionType::Shows { cards },
+            ),
         ))(input)?;
         Ok((input, action_type))
     }
@@ -379,39 +531,53 @@ impl fmt::Display for ActionType {
                 ActionType::Call { .. } => "call",
                 ActionType::Check => "check",
                 ActionType::Fold => "fold",
-                ActionType::Post(_) => "post",
+                ActionType::Post(PostType::Ante(_)) => "post-ante",
+                ActionType::Post(PostType::SmallBlind(_)) => "post-small-blind",
+                ActionType::Post(PostType::BigBlind(_)) => "post-big-blind",
+                ActionType::Post(PostType::Dead(_)) => "post-dead",
                 ActionType::Raise { .. } => "raise",
-                ActionType::Collect => "collect",
-                ActionType::Shows => "show",
+                ActionType::Collect { .. } => "collect",
+                ActionType::Shows { .. } => "show",
+                ActionType::UncalledBet { .. } => "uncalled-bet",
+                ActionType::CashoutRisk { .. } => "cashout-risk",
+                ActionType::Cashout { .. } => "cashout",
             }
         )
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Action {
-    pub player_name: String,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Action<S = String> {
+    pub player_name: S,
     pub action: ActionType,
     pub is_all_in: bool,
 }
 
-impl Action {
-    fn parse(input: &str) -> IResult<&str, Action> {
-        let (input, (player_name_vec, (action_type, all_in))) =
+impl<'a> Action<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        let (input, (player_name, (action_type, all_in))) =
             // anychar would work too, but we want to fail on newlines for robustness
-            many_till(none_of("\n"), delimited(tag(" "), pair(ActionType::parse, opt(tag(" and is all-in"))), tag("\n")))(input)?;
+            take_till_match(none_of("\n"), delimited(tag(" "), pair(ActionType::parse, opt(tag(" and is all-in"))), tag("\n")))(input)?;
         Ok((
             input,
             Action {
-                player_name: player_name_vec.into_iter().collect(),
+                player_name,
                 action: action_type,
                 is_all_in: all_in.is_some(),
             },
         ))
     }
+
+    pub fn into_owned(self) -> Action {
+        Action {
+            player_name: self.player_name.to_owned(),
+            action: self.action,
+            is_all_in: self.is_all_in,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Rank {
     Two,
     Three,
@@ -497,7 +663,7 @@ impl fmt::Display for Rank {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Suit {
     Spades,
     Hearts,
@@ -532,14 +698,14 @@ impl fmt::Display for Suit {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
 }
 
 impl Card {
-    fn parse(input: &str) -> IResult<&str, Card> {
+    pub(crate) fn parse(input: &str) -> IResult<&str, Card> {
         let (input, (rank, suit)) = tuple((Rank::parse, Suit::parse))(input)?;
         Ok((input, Card { rank, suit }))
     }
@@ -551,44 +717,74 @@ impl fmt::Display for Card {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Error returned when parsing a card that is not a rank followed by a suit, as in "Th"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCardError;
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid card")
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Card::parse(s) {
+            Ok(("", card)) => Ok(card),
+            _ => Err(ParseCardError),
+        }
+    }
+}
+
+/// Two cards in Hold'em, four or five in Omaha
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct HoleCards {
-    pub card1: Card,
-    pub card2: Card,
+    pub cards: Vec<Card>,
 }
 
 impl HoleCards {
-    fn parse(input: &str) -> IResult<&str, HoleCards> {
-        let (input, (card1, card2)) = separated_pair(Card::parse, tag(" "), Card::parse)(input)?;
-        Ok((input, HoleCards { card1, card2 }))
+    pub(crate) fn parse(input: &str) -> IResult<&str, HoleCards> {
+        let (input, cards) = separated_list1(tag(" "), Card::parse)(input)?;
+        Ok((input, HoleCards { cards }))
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct DealtToHero {
-    pub player_name: String,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DealtToHero<S = String> {
+    pub player_name: S,
     pub hole_cards: HoleCards,
 }
 
-impl DealtToHero {
-    fn parse(input: &str) -> IResult<&str, DealtToHero> {
+impl<'a> DealtToHero<&'a str> {
+    pub(crate) fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let hole_cards = delimited(tag(" ["), HoleCards::parse, tag("]"));
-        let (input, (player_name_vec, hole_cards)) = delimited(
+        let (input, (player_name, hole_cards)) = delimited(
             tag("Dealt to "),
-            many_till(anychar, hole_cards),
+            take_till_match(anychar, hole_cards),
             line_ending,
         )(input)?;
         Ok((
             input,
             DealtToHero {
-                player_name: player_name_vec.into_iter().collect(),
+                player_name,
                 hole_cards,
             },
         ))
     }
+
+    pub fn into_owned(self) -> DealtToHero {
+        DealtToHero {
+            player_name: self.player_name.to_owned(),
+            hole_cards: self.hole_cards,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum StreetType {
     Preflop,
     Flop,
@@ -613,14 +809,30 @@ impl fmt::Display for StreetType {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Street {
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Street<S = String> {
     pub street_type: StreetType,
-    pub actions: Vec<Action>,
+    /// Cards dealt on this street, on the first run of the board when it was run twice
+    pub cards: Vec<Card>,
+    pub actions: Vec<Action<S>>,
+}
+
+/// Parses the cards dealt on a street from the end of its header line, as in " [Ad 7c 2h][9s]"
+/// or " [7h 3c 9s] [2d]", where the last group holds the new cards
+pub(crate) fn parse_street_cards(input: &str) -> IResult<&str, Vec<Card>> {
+    let (input, groups) = many0(preceded(
+        opt(char(' ')),
+        delimited(
+            char('['),
+            separated_list0(char(' '), Card::parse),
+            char(']'),
+        ),
+    ))(input)?;
+    Ok((input, groups.into_iter().last().unwrap_or_default()))
 }
 
-impl Street {
-    fn parse(input: &str) -> IResult<&str, Street> {
+impl<'a> Street<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let street_type = alt((
             map(tag("*** PRE-FLOP ***"), |_| StreetType::Preflop),
             map(tag("*** FLOP ***"), |_| StreetType::Flop),
@@ -629,22 +841,32 @@ impl Street {
             map(tag("*** SHOW DOWN ***"), |_| StreetType::Showdown),
         ));
 
-        let (input, (street_type, _, actions)) = tuple((
+        let (input, (street_type, cards, _, actions)) = tuple((
             street_type,
-            many_till(anychar, line_ending), // ignore partial boards
+            parse_street_cards,
+            take_till_match(anychar, line_ending),
             many0(Action::parse),
         ))(input)?;
         Ok((
             input,
             Street {
                 street_type,
+                cards,
                 actions,
             },
         ))
     }
+
+    pub fn into_owned(self) -> Street {
+        Street {
+            street_type: self.street_type,
+            cards: self.cards,
+            actions: self.actions.into_iter().map(Action::into_owned).collect(),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Board {
     pub cards: Vec<Option<Card>>,
 }
@@ -662,13 +884,13 @@ impl Board {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SummaryResult {
-    Won(f64),
+    Won(Amount),
     Lost,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum HandCategory {
     HighCard(Rank),
     Pair(Rank),
@@ -743,17 +965,17 @@ impl HandCategory {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct SummaryPlayer {
-    pub name: String,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SummaryPlayer<S = String> {
+    pub name: S,
     pub seat: u32,
     pub hole_cards: Option<HoleCards>,
     pub result: SummaryResult,
     pub hand_category: Option<HandCategory>,
 }
 
-impl SummaryPlayer {
-    fn parse(input: &str) -> IResult<&str, SummaryPlayer> {
+impl<'a> SummaryPlayer<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let position = delimited(tag(" ("), take_until(")"), tag(")"));
         let showed = delimited(tag(" showed ["), HoleCards::parse, tag("] and"));
         let result = alt((
@@ -771,13 +993,13 @@ impl SummaryPlayer {
         ));
 
         let winner_seat = preceded(tag("Seat "), nom::character::complete::u32);
-        let winner_name_vec = preceded(tag(": "), many_till(anychar, position_show_result));
-        let (input, (winner_seat, (winner_name_vec, (_, showed, result, hand_category, _)))) =
-            tuple((winner_seat, winner_name_vec))(input)?;
+        let winner_name = preceded(tag(": "), take_till_match(anychar, position_show_result));
+        let (input, (winner_seat, (winner_name, (_, showed, result, hand_category, _)))) =
+            tuple((winner_seat, winner_name))(input)?;
         Ok((
             input,
             SummaryPlayer {
-                name: winner_name_vec.into_iter().collect(),
+                name: winner_name,
                 seat: winner_seat,
                 hole_cards: showed,
                 result,
@@ -785,18 +1007,29 @@ impl SummaryPlayer {
             },
         ))
     }
+
+    pub fn into_owned(self) -> SummaryPlayer {
+        SummaryPlayer {
+            name: self.name.to_owned(),
+            seat: self.seat,
+            hole_cards: self.hole_cards,
+            result: self.result,
+            hand_category: self.hand_category,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Summary {
-    pub pot: f64,
-    pub rake: Option<f64>,
-    pub players: Vec<SummaryPlayer>,
-    pub board: Option<Board>,
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Summary<S = String> {
+    pub pot: Amount,
+    pub rake: Option<Amount>,
+    pub players: Vec<SummaryPlayer<S>>,
+    /// Final boards, one for each run of the board, empty if no flop was dealt
+    pub boards: Vec<Board>,
 }
 
-impl Summary {
-    fn parse(input: &str) -> IResult<&str, Summary> {
+impl<'a> Summary<&'a str> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
         let pot_amount = delimited(tag("Total pot "), parse_amount, tag(" | "));
         let rake = alt((
             map(preceded(tag("Rake "), parse_amount), Some),
@@ -815,38 +1048,168 @@ impl Summary {
                 pot: pot_amount,
                 rake,
                 players,
-                board,
+                boards: board.into_iter().collect(),
             },
         ))
     }
+
+    pub fn into_owned(self) -> Summary {
+        Summary {
+            pot: self.pot,
+            rake: self.rake,
+            players: self
+                .players
+                .into_iter()
+                .map(SummaryPlayer::into_owned)
+                .collect(),
+            boards: self.boards,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Hand<S = String> {
+    pub hand_info: HandInfo<S>,
+    pub table_info: TableInfo<S>,
+    pub seats: Vec<Seat<S>>,
+    pub dealt_cards: DealtToHero<S>,
+    pub streets: Vec<Street<S>>,
+    pub summary: Summary<S>,
+}
+
+/// Hand borrowing its names from the parsed input, which saves copying them when the hand
+/// is only read once
+pub type HandRef<'a> = Hand<&'a str>;
+
+/// Like `many_till(item, end)`, returning the text matched by `item` as a slice of the input
+/// instead of collecting it
+fn take_till_match<'a, O, I, E>(
+    mut item: I,
+    mut end: E,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)>
+where
+    I: Parser<&'a str, char, nom::error::Error<&'a str>>,
+    E: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    move |input: &'a str| {
+        let mut remaining = input;
+        loop {
+            match end.parse(remaining) {
+                Ok((rest, output)) => {
+                    let text = &input[..input.len() - remaining.len()];
+                    return Ok((rest, (text, output)));
+                }
+                Err(nom::Err::Error(_)) => remaining = item.parse(remaining)?.0,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Error raised while parsing a single hand, before it is located in the whole input
+struct HandError<'a> {
+    section: Section,
+    hand_id: Option<&'a str>,
+    error: nom::Err<nom::error::Error<&'a str>>,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Hand {
-    pub hand_info: HandInfo,
-    pub table_info: TableInfo,
-    pub seats: Vec<Seat>,
-    pub dealt_cards: DealtToHero,
-    pub streets: Vec<Street>,
-    pub summary: Summary,
+impl<'a> HandError<'a> {
+    fn at(
+        section: Section,
+        hand_id: Option<&'a str>,
+    ) -> impl FnOnce(nom::Err<nom::error::Error<&'a str>>) -> HandError<'a> {
+        move |error| HandError {
+            section,
+            hand_id,
+            error,
+        }
+    }
+
+    fn located(self, origin: &str) -> ParseError {
+        ParseError::new(
+            origin,
+            self.section,
+            self.hand_id.map(str::to_owned),
+            self.error,
+        )
+    }
 }
 
 impl Hand {
     pub fn parse(input: &str) -> IResult<&str, Hand> {
-        let (input, (_, hand_info, table_info, seats, _, dealt_cards, (streets, _), summary)) =
-            tuple((
-                take_till(|c: char| c.is_alphabetic()),
-                HandInfo::parse,
-                TableInfo::parse,
-                parse_seats,
-                take_until("Dealt to"),
-                DealtToHero::parse,
-                many_till(
-                    Street::parse,
-                    terminated(tag("*** SUMMARY ***"), line_ending),
-                ),
-                Summary::parse,
-            ))(input)?;
+        let (input, hand) = HandRef::parse_borrowed(input)?;
+        Ok((input, hand.into_owned()))
+    }
+}
+
+impl<S> Hand<S> {
+    /// Board cards known during the actions of the street at `index` in `streets`
+    pub fn board_at(&self, index: usize) -> Vec<&Card> {
+        self.streets
+            .iter()
+            .take(index + 1)
+            .flat_map(|street| street.cards.iter())
+            .collect()
+    }
+}
+
+impl<'a> HandRef<'a> {
+    /// Parses a Winamax hand without copying the text it holds
+    pub fn parse_borrowed(input: &'a str) -> IResult<&'a str, HandRef<'a>> {
+        HandRef::parse_sections(input).map_err(|e| e.error)
+    }
+
+    /// Copies the borrowed text into an owned `Hand`
+    pub fn into_owned(self) -> Hand {
+        Hand {
+            hand_info: self.hand_info.into_owned(),
+            table_info: self.table_info.into_owned(),
+            seats: self.seats.into_iter().map(Seat::into_owned).collect(),
+            dealt_cards: self.dealt_cards.into_owned(),
+            streets: self.streets.into_iter().map(Street::into_owned).collect(),
+            summary: self.summary.into_owned(),
+        }
+    }
+
+    fn parse_sections(input: &'a str) -> Result<(&'a str, HandRef<'a>), HandError<'a>> {
+        let (input, _) = take_till(|c: char| c.is_alphabetic())(input)
+            .map_err(HandError::at(Section::HandInfo, None))?;
+        let (input, hand_info) =
+            HandInfo::parse(input).map_err(HandError::at(Section::HandInfo, None))?;
+        let hand_id = Some(hand_info.hand_id);
+        let (input, table_info) =
+            TableInfo::parse(input).map_err(HandError::at(Section::TableInfo, hand_id))?;
+        let (input, seats) = parse_seats(input).map_err(HandError::at(Section::Seats, hand_id))?;
+        let (input, posts) = opt(preceded(
+            terminated(tag("*** ANTE/BLINDS ***"), line_ending),
+            many0(Action::parse),
+        ))(input)
+        .map_err(HandError::at(Section::Seats, hand_id))?;
+        let (input, dealt_cards) = preceded(take_until("Dealt to"), DealtToHero::parse)(input)
+            .map_err(HandError::at(Section::DealtToHero, hand_id))?;
+        let (input, (mut streets, _)) = many_till(
+            Street::parse,
+            terminated(tag("*** SUMMARY ***"), line_ending),
+        )(input)
+        .map_err(HandError::at(Section::Street, hand_id))?;
+        let (input, summary) =
+            Summary::parse(input).map_err(HandError::at(Section::Summary, hand_id))?;
+        // the blinds and antes are the first actions of the pre-flop street
+        let posts = posts.unwrap_or_default();
+        match streets.first_mut() {
+            Some(street) if street.street_type == StreetType::Preflop => {
+                street.actions.splice(0..0, posts);
+            }
+            _ if !posts.is_empty() => streets.insert(
+                0,
+                Street {
+                    street_type: StreetType::Preflop,
+                    cards: vec![],
+                    actions: posts,
+                },
+            ),
+            _ => {}
+        }
         Ok((
             input,
             Hand {
@@ -861,8 +1224,50 @@ impl Hand {
     }
 }
 
-pub fn parse_hands(input: &str) -> IResult<&str, Vec<Hand>> {
-    separated_list1(line_ending, Hand::parse)(input)
+/// Parser for Winamax hand histories
+pub struct WinamaxParser;
+
+impl SiteParser for WinamaxParser {
+    fn hand_header(&self) -> &'static str {
+        "Winamax Poker - "
+    }
+
+    fn parse_hand<'a>(&self, input: &'a str, origin: &str) -> Result<(&'a str, Hand), ParseError> {
+        let (input, hand) = HandRef::parse_sections(input).map_err(|e| e.located(origin))?;
+        Ok((input, hand.into_owned()))
+    }
+}
+
+fn site_parser(input: &str) -> &'static dyn SiteParser {
+    Site::detect(input).unwrap_or(Site::Winamax).parser()
+}
+
+/// Parses every hand of a hand history file, failing on the first hand that cannot be parsed.
+///
+/// The site is detected from the header of the first hand.
+pub fn parse_hands(input: &str) -> Result<Vec<Hand>, ParseError> {
+    site_parser(input).parse_hands(input)
+}
+
+/// Parses every hand of a hand history file, skipping the hands that cannot be parsed.
+///
+/// The site is detected from the header of the first hand. After a failure, parsing resumes
+/// at the next hand header; the skipped blocks are listed in the returned report.
+pub fn parse_hands_recovering(input: &str) -> (Vec<Hand>, ParseReport) {
+    site_parser(input).parse_hands_recovering(input)
+}
+
+/// Parses every hand of a Winamax hand history file into hands borrowing from `input`,
+/// failing on the first hand that cannot be parsed.
+pub fn parse_hands_borrowed(input: &str) -> Result<Vec<HandRef<'_>>, ParseError> {
+    let mut hands = vec![];
+    let mut remaining = input;
+    while !remaining.trim().is_empty() {
+        let (rest, hand) = HandRef::parse_sections(remaining).map_err(|e| e.located(input))?;
+        hands.push(hand);
+        remaining = rest;
+    }
+    Ok(hands)
 }
 
 #[cfg(test)]
@@ -873,9 +1278,9 @@ mod tests {
     fn test_parse_tournament_info() {
         let input = "\"WESTERN\" buyIn: 0.90€ + 0.10€ level: 6";
         let expected = TournamentInfo {
-            name: String::from("WESTERN"),
-            buy_in: 0.90,
-            rake: 0.10,
+            name: "WESTERN",
+            buy_in: Amount::from_cents(90),
+            rake: Amount::from_cents(10),
             level: 6,
         };
         let (_, actual) = TournamentInfo::parse(input).unwrap();
@@ -886,9 +1291,9 @@ mod tests {
     fn test_parse_game_info_tournament() {
         let input = "Winamax Poker - Tournament \"WESTERN\" buyIn: 0.90€ + 0.10€ level: 6";
         let expected = GameInfo::Tournament(TournamentInfo {
-            name: String::from("WESTERN"),
-            buy_in: 0.90,
-            rake: 0.10,
+            name: "WESTERN",
+            buy_in: Amount::from_cents(90),
+            rake: Amount::from_cents(10),
             level: 6,
         });
         let (_, actual) = GameInfo::parse(input).unwrap();
@@ -905,14 +1310,14 @@ mod tests {
     #[test]
     fn test_parse_blind_chips() {
         let input = "60";
-        let expected = 60.0;
+        let expected = Amount::from_units(60);
         assert_eq!(expected, parse_blind(input).unwrap().1);
     }
 
     #[test]
     fn test_parse_blind_money() {
         let input = "60€";
-        let expected = 60.0;
+        let expected = Amount::from_units(60);
         assert_eq!(expected, parse_blind(input).unwrap().1);
     }
 
@@ -920,9 +1325,9 @@ mod tests {
     fn test_parse_blinds_chips() {
         let input = "60/250/500";
         let expected = Blinds {
-            ante: Some(60.0),
-            small_blind: 250.0,
-            big_blind: 500.0,
+            ante: Some(Amount::from_units(60)),
+            small_blind: Amount::from_units(250),
+            big_blind: Amount::from_units(500),
         };
         let (_, actual) = Blinds::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -933,8 +1338,8 @@ mod tests {
         let input = "250/500";
         let expected = Blinds {
             ante: None,
-            small_blind: 250.0,
-            big_blind: 500.0,
+            small_blind: Amount::from_units(250),
+            big_blind: Amount::from_units(500),
         };
         let (_, actual) = Blinds::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -947,20 +1352,22 @@ mod tests {
 
         let expected = HandInfo {
             game_info: GameInfo::Tournament(TournamentInfo {
-                name: String::from("WESTERN"),
-                buy_in: 0.90,
-                rake: 0.10,
+                name: "WESTERN",
+                buy_in: Amount::from_cents(90),
+                rake: Amount::from_cents(10),
                 level: 6,
             }),
-            hand_id: String::from("2815488303912976462-15-1684698584"),
+            hand_id: "2815488303912976462-15-1684698584",
             poker_type: PokerType::HoldemNoLimit,
             blinds: Blinds {
-                ante: Some(60.0),
-                small_blind: 250.0,
-                big_blind: 500.0,
+                ante: Some(Amount::from_units(60)),
+                small_blind: Amount::from_units(250),
+                big_blind: Amount::from_units(500),
             },
-            // datetime: String::from("2023/05/21 19:49:44 UTC"),
+            // datetime: "2023/05/21 19:49:44 UTC",
             datetime: Utc.with_ymd_and_hms(2023, 5, 21, 19, 49, 44).unwrap(),
+            site: "Winamax",
+            currency_code: "EUR",
         };
         let (_, actual) = HandInfo::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -971,15 +1378,17 @@ mod tests {
         let input = "Winamax Poker - HOLD-UP \"Colorado\" - HandId: #18559747-238220-1687014585 - Holdem no limit (0.01€/0.02€) - 2023/06/17 15:09:45 UTC\n";
 
         let expected = HandInfo {
-            game_info: GameInfo::HoldUp,
-            hand_id: String::from("18559747-238220-1687014585"),
+            game_info: GameInfo::HoldUp("Colorado"),
+            hand_id: "18559747-238220-1687014585",
             poker_type: PokerType::HoldemNoLimit,
             blinds: Blinds {
                 ante: None,
-                small_blind: 0.01,
-                big_blind: 0.02,
+                small_blind: Amount::from_cents(1),
+                big_blind: Amount::from_cents(2),
             },
             datetime: Utc.with_ymd_and_hms(2023, 6, 17, 15, 9, 45).unwrap(),
+            site: "Winamax",
+            currency_code: "EUR",
         };
         let (_, actual) = HandInfo::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -990,24 +1399,35 @@ mod tests {
         let input = "Winamax Poker - CashGame - HandId: #18567763-280-1687022958 - Holdem no limit (0.01€/0.02€) - 2023/06/17 17:29:18 UTC\n";
         let expected = HandInfo {
             game_info: GameInfo::CashGame,
-            hand_id: String::from("18567763-280-1687022958"),
+            hand_id: "18567763-280-1687022958",
             poker_type: PokerType::HoldemNoLimit,
             blinds: Blinds {
                 ante: None,
-                small_blind: 0.01,
-                big_blind: 0.02,
+                small_blind: Amount::from_cents(1),
+                big_blind: Amount::from_cents(2),
             },
             datetime: Utc.with_ymd_and_hms(2023, 6, 17, 17, 29, 18).unwrap(),
+            site: "Winamax",
+            currency_code: "EUR",
         };
 
         let (_, actual) = HandInfo::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_hand_info_omaha() {
+        let input = "Winamax Poker - CashGame - HandId: #18671300-513-1690709050 - Omaha5 pot limit (0.05€/0.10€) - 2023/07/30 09:24:10 UTC\n";
+        let (_, actual) = HandInfo::parse(input).unwrap();
+        assert_eq!(actual.poker_type, PokerType::Omaha5PotLimit);
+        assert_eq!(actual.poker_type.hole_cards(), 5);
+        assert_eq!(actual.poker_type.to_string(), "omaha5-pot-limit");
+    }
+
     #[test]
     fn test_parse_table_name_tournament() {
         let input = "'Kill The Fish(651864208)#003'";
-        let expected = TableName::Tournament(String::from("Kill The Fish"), 651864208, 3);
+        let expected = TableName::Tournament("Kill The Fish", 651864208, 3);
         let (_, actual) = TableName::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1015,7 +1435,7 @@ mod tests {
     #[test]
     fn test_parse_table_name_cashgame() {
         let input = "'Nice 17'";
-        let expected = TableName::CashGame(String::from("Nice 17"));
+        let expected = TableName::CashGame("Nice 17");
         let (_, actual) = TableName::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1023,7 +1443,7 @@ mod tests {
     #[test]
     fn test_parse_table_name_holdup() {
         let input = "'Colorado'";
-        let expected = TableName::CashGame(String::from("Colorado"));
+        let expected = TableName::CashGame("Colorado");
         let (_, actual) = TableName::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1032,7 +1452,7 @@ mod tests {
     fn test_parse_table_info_tournament() {
         let input = "Table: 'WESTERN(655531954)#077' 6-max (real money) Seat #3 is the button\n";
         let expected = TableInfo {
-            table_name: TableName::Tournament(String::from("WESTERN"), 655531954, 77),
+            table_name: TableName::Tournament("WESTERN", 655531954, 77),
             max_players: 6,
             currency: MoneyType::RealMoney,
             button: 3,
@@ -1045,7 +1465,7 @@ mod tests {
     fn test_parse_table_info_cash_game_play_money() {
         let input = "Table: 'Wichita 05' 5-max (play money) Seat #1 is the button\n";
         let expected = TableInfo {
-            table_name: TableName::CashGame(String::from("Wichita 05")),
+            table_name: TableName::CashGame("Wichita 05"),
             max_players: 5,
             currency: MoneyType::PlayMoney,
             button: 1,
@@ -1059,9 +1479,9 @@ mod tests {
         let input = "Seat 5: WinterSound (20000, 0.45€ bounty)\n";
         let expected = Seat {
             seat_number: 5,
-            player_name: String::from("WinterSound"),
-            stack: 20000.0,
-            bounty: Some(0.45),
+            player_name: "WinterSound",
+            stack: Amount::from_units(20000),
+            bounty: Some(Amount::from_cents(45)),
         };
         let (_, actual) = Seat::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1072,9 +1492,9 @@ mod tests {
         let input = "Seat 5: WinterSound (20000, 13.50 bounty)\n";
         let expected = Seat {
             seat_number: 5,
-            player_name: String::from("WinterSound"),
-            stack: 20000.0,
-            bounty: Some(13.50),
+            player_name: "WinterSound",
+            stack: Amount::from_units(20000),
+            bounty: Some(Amount::from_cents(1350)),
         };
         let (_, actual) = Seat::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1085,8 +1505,8 @@ mod tests {
         let input = "Seat 3: WinterSound (18744)\n";
         let expected = Seat {
             seat_number: 3,
-            player_name: String::from("WinterSound"),
-            stack: 18744.0,
+            player_name: "WinterSound",
+            stack: Amount::from_units(18744),
             bounty: None,
         };
         let (_, actual) = Seat::parse(input).unwrap();
@@ -1098,8 +1518,8 @@ mod tests {
         let input = "Seat 3: Winter Sound (0.50€)\n";
         let expected = Seat {
             seat_number: 3,
-            player_name: String::from("Winter Sound"),
-            stack: 0.50,
+            player_name: "Winter Sound",
+            stack: Amount::from_cents(50),
             bounty: None,
         };
         let (_, actual) = Seat::parse(input).unwrap();
@@ -1112,14 +1532,14 @@ mod tests {
         let expected = vec![
             Seat {
                 seat_number: 1,
-                player_name: String::from("WinterSound"),
-                stack: 20000.0,
-                bounty: Some(0.45),
+                player_name: "WinterSound",
+                stack: Amount::from_units(20000),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 2,
-                player_name: String::from("Player Two"),
-                stack: 18744.0,
+                player_name: "Player Two",
+                stack: Amount::from_units(18744),
                 bounty: None,
             },
         ];
@@ -1141,39 +1561,39 @@ mod tests {
         let expected = vec![
             Seat {
                 seat_number: 1,
-                player_name: String::from("Anonymous1"),
-                stack: 23940.0,
-                bounty: Some(0.45),
+                player_name: "Anonymous1",
+                stack: Amount::from_units(23940),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 2,
-                player_name: String::from("Anonymous 2"),
-                stack: 14388.0,
-                bounty: Some(0.45),
+                player_name: "Anonymous 2",
+                stack: Amount::from_units(14388),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 3,
-                player_name: String::from("Anonymous 3"),
-                stack: 20410.0,
-                bounty: Some(0.45),
+                player_name: "Anonymous 3",
+                stack: Amount::from_units(20410),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 4,
-                player_name: String::from("Anonymous4"),
-                stack: 15425.0,
-                bounty: Some(0.45),
+                player_name: "Anonymous4",
+                stack: Amount::from_units(15425),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 5,
-                player_name: String::from("WinterSound"),
-                stack: 14285.0,
-                bounty: Some(0.45),
+                player_name: "WinterSound",
+                stack: Amount::from_units(14285),
+                bounty: Some(Amount::from_cents(45)),
             },
             Seat {
                 seat_number: 6,
-                player_name: String::from("Anonymous5"),
-                stack: 109973.0,
-                bounty: Some(1.0),
+                player_name: "Anonymous5",
+                stack: Amount::from_units(109973),
+                bounty: Some(Amount::from_units(1)),
             },
         ];
         let (_, actual) = parse_seats(input).unwrap();
@@ -1183,7 +1603,7 @@ mod tests {
     #[test]
     fn test_parse_post_type_sb() {
         let input = "small blind 250\n";
-        let expected = PostType::SmallBlind(250.0);
+        let expected = PostType::SmallBlind(Amount::from_units(250));
         let (_, actual) = PostType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1191,7 +1611,7 @@ mod tests {
     #[test]
     fn test_parse_post_type_bb_cash() {
         let input = "big blind 0.02€\n";
-        let expected = PostType::BigBlind(0.02);
+        let expected = PostType::BigBlind(Amount::from_cents(2));
         let (_, actual) = PostType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1199,7 +1619,7 @@ mod tests {
     #[test]
     fn test_parse_post_type_ante_chips() {
         let input = "big blind 60\n";
-        let expected = PostType::BigBlind(60.0);
+        let expected = PostType::BigBlind(Amount::from_units(60));
         let (_, actual) = PostType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1207,7 +1627,7 @@ mod tests {
     #[test]
     fn test_parse_action_type_post_bb() {
         let input = "posts big blind 500\n";
-        let expected = ActionType::Post(PostType::BigBlind(500.0));
+        let expected = ActionType::Post(PostType::BigBlind(Amount::from_units(500)));
         let (_, actual) = ActionType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1223,7 +1643,9 @@ mod tests {
     #[test]
     fn test_parse_action_type_call() {
         let input = "calls 500\n";
-        let expected = ActionType::Call { amount: 500.0 };
+        let expected = ActionType::Call {
+            amount: Amount::from_units(500),
+        };
         let (_, actual) = ActionType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1231,7 +1653,9 @@ mod tests {
     #[test]
     fn test_parse_action_type_bet() {
         let input = "bets 500\n";
-        let expected = ActionType::Bet { amount: 500.0 };
+        let expected = ActionType::Bet {
+            amount: Amount::from_units(500),
+        };
         let (_, actual) = ActionType::parse(input).unwrap();
         assert_eq!(expected, actual);
     }
@@ -1240,8 +1664,40 @@ mod tests {
     fn test_parse_action_type_raise() {
         let input = "raises 500 to 1000\n";
         let expected = ActionType::Raise {
-            to_call: 500.0,
-            amount: 1000.0,
+            to_call: Amount::from_units(500),
+            amount: Amount::from_units(1000),
+        };
+        let (_, actual) = ActionType::parse(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_parse_action_type_collect_side_pot() {
+        let input = "collected 0.34€ from side pot 1\n";
+        let expected = ActionType::Collect {
+            amount: Amount::from_cents(34),
+            pot: Pot::Side(1),
+        };
+        let (_, actual) = ActionType::parse(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_parse_action_type_shows() {
+        let input = "shows [Kc Ah] (High card : Ace)\n";
+        let expected = ActionType::Shows {
+            cards: HoleCards {
+                cards: vec![
+                    Card {
+                        rank: Rank::King,
+                        suit: Suit::Clubs,
+                    },
+                    Card {
+                        rank: Rank::Ace,
+                        suit: Suit::Hearts,
+                    },
+                ],
+            },
         };
         let (_, actual) = ActionType::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1251,7 +1707,7 @@ mod tests {
     fn test_parse_action_fold() {
         let input = "As 2 carrot folds\n";
         let expected = Action {
-            player_name: String::from("As 2 carrot"),
+            player_name: "As 2 carrot",
             action: ActionType::Fold,
             is_all_in: false,
         };
@@ -1263,7 +1719,7 @@ mod tests {
     fn test_parse_action_check() {
         let input = "Player One checks\n";
         let expected = Action {
-            player_name: String::from("Player One"),
+            player_name: "Player One",
             action: ActionType::Check,
             is_all_in: false,
         };
@@ -1275,10 +1731,10 @@ mod tests {
     fn test_parse_action_raises() {
         let input = "Player One raises 500 to 1000\n";
         let expected = Action {
-            player_name: String::from("Player One"),
+            player_name: "Player One",
             action: ActionType::Raise {
-                to_call: 500.0,
-                amount: 1000.0,
+                to_call: Amount::from_units(500),
+                amount: Amount::from_units(1000),
             },
             is_all_in: false,
         };
@@ -1290,16 +1746,18 @@ mod tests {
     fn test_parse_dealt_to() {
         let input = "Dealt to Player One [Ks 9s]\n";
         let expected = DealtToHero {
-            player_name: String::from("Player One"),
+            player_name: "Player One",
             hole_cards: HoleCards {
-                card1: Card {
-                    rank: Rank::King,
-                    suit: Suit::Spades,
-                },
-                card2: Card {
-                    rank: Rank::Nine,
-                    suit: Suit::Spades,
-                },
+                cards: vec![
+                    Card {
+                        rank: Rank::King,
+                        suit: Suit::Spades,
+                    },
+                    Card {
+                        rank: Rank::Nine,
+                        suit: Suit::Spades,
+                    },
+                ],
             },
         };
         let (_, actual) = DealtToHero::parse(input).unwrap();
@@ -1312,18 +1770,34 @@ mod tests {
             "*** FLOP *** [8s 7h 4h]\nPlayer One raises 500 to 1000\nPlayer Two calls 1000\n";
         let expected = Street {
             street_type: StreetType::Flop,
+            cards: vec![
+                Card {
+                    rank: Rank::Eight,
+                    suit: Suit::Spades,
+                },
+                Card {
+                    rank: Rank::Seven,
+                    suit: Suit::Hearts,
+                },
+                Card {
+                    rank: Rank::Four,
+                    suit: Suit::Hearts,
+                },
+            ],
             actions: vec![
                 Action {
-                    player_name: String::from("Player One"),
+                    player_name: "Player One",
                     action: ActionType::Raise {
-                        to_call: 500.0,
-                        amount: 1000.0,
+                        to_call: Amount::from_units(500),
+                        amount: Amount::from_units(1000),
                     },
                     is_all_in: false,
                 },
                 Action {
-                    player_name: String::from("Player Two"),
-                    action: ActionType::Call { amount: 1000.0 },
+                    player_name: "Player Two",
+                    action: ActionType::Call {
+                        amount: Amount::from_units(1000),
+                    },
                     is_all_in: false,
                 },
             ],
@@ -1332,6 +1806,39 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_parse_street_cards() {
+        let (_, cards) = parse_street_cards(" [Ad 7c 2h][9s]").unwrap();
+        assert_eq!(
+            cards,
+            vec![Card {
+                rank: Rank::Nine,
+                suit: Suit::Spades,
+            }]
+        );
+        let (_, cards) = parse_street_cards(" [7h 3c 9s] [2d]").unwrap();
+        assert_eq!(
+            cards,
+            vec![Card {
+                rank: Rank::Two,
+                suit: Suit::Diamonds,
+            }]
+        );
+        assert_eq!(parse_street_cards(""), Ok(("", vec![])));
+    }
+
+    #[test]
+    fn test_card_from_str() {
+        let card = Card {
+            rank: Rank::Ten,
+            suit: Suit::Hearts,
+        };
+        assert_eq!("Th".parse(), Ok(card.clone()));
+        assert_eq!(card.to_string().parse(), Ok(card));
+        assert_eq!("Th ".parse::<Card>(), Err(ParseCardError));
+        assert_eq!("1h".parse::<Card>(), Err(ParseCardError));
+    }
+
     #[test]
     fn test_parse_board() {
         let input = "Board: [8s 7h 4h 3s 2h]\n";
@@ -1416,8 +1923,8 @@ mod tests {
         let input = "Seat 6: Alexarango (button) won 0.31€";
         let expected = SummaryPlayer {
             seat: 6,
-            name: String::from("Alexarango"),
-            result: SummaryResult::Won(0.31),
+            name: "Alexarango",
+            result: SummaryResult::Won(Amount::from_cents(31)),
             hole_cards: None,
             hand_category: None,
         };
@@ -1431,17 +1938,19 @@ mod tests {
             "Seat 6: Alexarango (button) showed [8d Td] and won 0.36€ with Straight Ten high\n";
         let expected = SummaryPlayer {
             seat: 6,
-            name: String::from("Alexarango"),
-            result: SummaryResult::Won(0.36),
+            name: "Alexarango",
+            result: SummaryResult::Won(Amount::from_cents(36)),
             hole_cards: Some(HoleCards {
-                card1: Card {
-                    rank: Rank::Eight,
-                    suit: Suit::Diamonds,
-                },
-                card2: Card {
-                    rank: Rank::Ten,
-                    suit: Suit::Diamonds,
-                },
+                cards: vec![
+                    Card {
+                        rank: Rank::Eight,
+                        suit: Suit::Diamonds,
+                    },
+                    Card {
+                        rank: Rank::Ten,
+                        suit: Suit::Diamonds,
+                    },
+                ],
             }),
             hand_category: Some(HandCategory::Straight(Rank::Ten)),
         };
@@ -1455,17 +1964,19 @@ mod tests {
             "Seat 3: Player Two showed [Qd As] and won 0.36€ with Two pairs : Queens and 2\n";
         let expected = SummaryPlayer {
             seat: 3,
-            name: String::from("Player Two"),
-            result: SummaryResult::Won(0.36),
+            name: "Player Two",
+            result: SummaryResult::Won(Amount::from_cents(36)),
             hole_cards: Some(HoleCards {
-                card1: Card {
-                    rank: Rank::Queen,
-                    suit: Suit::Diamonds,
-                },
-                card2: Card {
-                    rank: Rank::Ace,
-                    suit: Suit::Spades,
-                },
+                cards: vec![
+                    Card {
+                        rank: Rank::Queen,
+                        suit: Suit::Diamonds,
+                    },
+                    Card {
+                        rank: Rank::Ace,
+                        suit: Suit::Spades,
+                    },
+                ],
             }),
             hand_category: Some(HandCategory::TwoPair(Rank::Queen, Rank::Two)),
         };
@@ -1477,16 +1988,16 @@ mod tests {
     fn test_parse_summary_no_flop_no_rake() {
         let input = "Total pot 2670 | No rake\nSeat 3: Player One won 2670\n\n";
         let expected = Summary {
-            pot: 2670.0,
+            pot: Amount::from_units(2670),
             rake: None,
             players: vec![SummaryPlayer {
                 seat: 3,
-                name: String::from("Player One"),
-                result: SummaryResult::Won(2670.0),
+                name: "Player One",
+                result: SummaryResult::Won(Amount::from_units(2670)),
                 hole_cards: None,
                 hand_category: None,
             }],
-            board: None,
+            boards: vec![],
         };
         let (_, actual) = Summary::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1497,16 +2008,16 @@ mod tests {
         let input =
             "Total pot 0.79€ | Rake 0.01€\nBoard: [8c 5h Ts Kd Td]\nSeat 3: Player One won 0.79€";
         let expected = Summary {
-            pot: 0.79,
-            rake: Some(0.01),
+            pot: Amount::from_cents(79),
+            rake: Some(Amount::from_cents(1)),
             players: vec![SummaryPlayer {
                 seat: 3,
-                name: String::from("Player One"),
-                result: SummaryResult::Won(0.79),
+                name: "Player One",
+                result: SummaryResult::Won(Amount::from_cents(79)),
                 hole_cards: None,
                 hand_category: None,
             }],
-            board: Some(Board {
+            boards: vec![Board {
                 cards: vec![
                     Some(Card {
                         rank: Rank::Eight,
@@ -1529,7 +2040,7 @@ mod tests {
                         suit: Suit::Diamonds,
                     }),
                 ],
-            }),
+            }],
         };
         let (_, actual) = Summary::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1540,16 +2051,16 @@ mod tests {
         let input =
             "Total pot 2670 | No rake\nBoard: [8s 7h 4h 3s 2h]\nSeat 3: Player One won 2670\n\n";
         let expected = Summary {
-            pot: 2670.0,
+            pot: Amount::from_units(2670),
             rake: None,
             players: vec![SummaryPlayer {
                 seat: 3,
-                name: String::from("Player One"),
-                result: SummaryResult::Won(2670.0),
+                name: "Player One",
+                result: SummaryResult::Won(Amount::from_units(2670)),
                 hole_cards: None,
                 hand_category: None,
             }],
-            board: Some(Board {
+            boards: vec![Board {
                 cards: vec![
                     Some(Card {
                         rank: Rank::Eight,
@@ -1572,7 +2083,7 @@ mod tests {
                         suit: Suit::Hearts,
                     }),
                 ],
-            }),
+            }],
         };
         let (_, actual) = Summary::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1589,43 +2100,47 @@ mod tests {
         );
 
         let expected = Summary {
-            pot: 0.30,
-            rake: Some(0.03),
+            pot: Amount::from_cents(30),
+            rake: Some(Amount::from_cents(3)),
             players: vec![
                 SummaryPlayer {
                     seat: 2,
-                    name: String::from("Player One"),
-                    result: SummaryResult::Won(0.30),
+                    name: "Player One",
+                    result: SummaryResult::Won(Amount::from_cents(30)),
                     hole_cards: Some(HoleCards {
-                        card1: Card {
-                            rank: Rank::Nine,
-                            suit: Suit::Clubs,
-                        },
-                        card2: Card {
-                            rank: Rank::King,
-                            suit: Suit::Diamonds,
-                        },
+                        cards: vec![
+                            Card {
+                                rank: Rank::Nine,
+                                suit: Suit::Clubs,
+                            },
+                            Card {
+                                rank: Rank::King,
+                                suit: Suit::Diamonds,
+                            },
+                        ],
                     }),
                     hand_category: Some(HandCategory::Pair(Rank::King)),
                 },
                 SummaryPlayer {
                     seat: 3,
-                    name: String::from("Player Two"),
+                    name: "Player Two",
                     result: SummaryResult::Lost,
                     hole_cards: Some(HoleCards {
-                        card1: Card {
-                            rank: Rank::Queen,
-                            suit: Suit::Diamonds,
-                        },
-                        card2: Card {
-                            rank: Rank::Ace,
-                            suit: Suit::Spades,
-                        },
+                        cards: vec![
+                            Card {
+                                rank: Rank::Queen,
+                                suit: Suit::Diamonds,
+                            },
+                            Card {
+                                rank: Rank::Ace,
+                                suit: Suit::Spades,
+                            },
+                        ],
                     }),
                     hand_category: Some(HandCategory::TwoPair(Rank::Queen, Rank::Two)),
                 },
             ],
-            board: Some(Board {
+            boards: vec![Board {
                 cards: vec![
                     Some(Card {
                         rank: Rank::Three,
@@ -1648,7 +2163,7 @@ mod tests {
                         suit: Suit::Clubs,
                     }),
                 ],
-            }),
+            }],
         };
         let (_, actual) = Summary::parse(input).unwrap();
         assert_eq!(expected, actual);
@@ -1694,18 +2209,20 @@ mod tests {
             hand_info: HandInfo {
                 game_info: GameInfo::Tournament(TournamentInfo {
                     name: String::from("WESTERN"),
-                    buy_in: 0.90,
-                    rake: 0.10,
+                    buy_in: Amount::from_cents(90),
+                    rake: Amount::from_cents(10),
                     level: 7,
                 }),
                 hand_id: String::from("2815488303912976462-17-1684698755"),
                 poker_type: PokerType::HoldemNoLimit,
                 blinds: Blinds {
-                    ante: Some(70.0),
-                    small_blind: 300.0,
-                    big_blind: 600.0,
+                    ante: Some(Amount::from_units(70)),
+                    small_blind: Amount::from_units(300),
+                    big_blind: Amount::from_units(600),
                 },
                 datetime: Utc.with_ymd_and_hms(2023, 5, 21, 19, 52, 35).unwrap(),
+                site: String::from("Winamax"),
+                currency_code: String::from("EUR"),
             },
             table_info: TableInfo {
                 table_name: TableName::Tournament(String::from("WESTERN"), 1684698755, 4),
@@ -1717,56 +2234,99 @@ mod tests {
                 Seat {
                     seat_number: 1,
                     player_name: String::from("Anonymous1"),
-                    stack: 23940.0,
-                    bounty: Some(0.45),
+                    stack: Amount::from_units(23940),
+                    bounty: Some(Amount::from_cents(45)),
                 },
                 Seat {
                     seat_number: 2,
                     player_name: String::from("Anonymous 2"),
-                    stack: 14388.0,
-                    bounty: Some(0.45),
+                    stack: Amount::from_units(14388),
+                    bounty: Some(Amount::from_cents(45)),
                 },
                 Seat {
                     seat_number: 3,
                     player_name: String::from("Anonymous 3"),
-                    stack: 20410.0,
-                    bounty: Some(0.45),
+                    stack: Amount::from_units(20410),
+                    bounty: Some(Amount::from_cents(45)),
                 },
                 Seat {
                     seat_number: 4,
                     player_name: String::from("Anonymous4"),
-                    stack: 15425.0,
-                    bounty: Some(0.45),
+                    stack: Amount::from_units(15425),
+                    bounty: Some(Amount::from_cents(45)),
                 },
                 Seat {
                     seat_number: 5,
                     player_name: String::from("WinterSound"),
-                    stack: 14285.0,
-                    bounty: Some(0.45),
+                    stack: Amount::from_units(14285),
+                    bounty: Some(Amount::from_cents(45)),
                 },
                 Seat {
                     seat_number: 6,
                     player_name: String::from("Anonymous5"),
-                    stack: 109973.0,
-                    bounty: Some(1.0),
+                    stack: Amount::from_units(109973),
+                    bounty: Some(Amount::from_units(1)),
                 },
             ],
             dealt_cards: DealtToHero {
                 player_name: String::from("WinterSound"),
                 hole_cards: HoleCards {
-                    card1: Card {
-                        rank: Rank::Six,
-                        suit: Suit::Spades,
-                    },
-                    card2: Card {
-                        rank: Rank::Queen,
-                        suit: Suit::Hearts,
-                    },
+                    cards: vec![
+                        Card {
+                            rank: Rank::Six,
+                            suit: Suit::Spades,
+                        },
+                        Card {
+                            rank: Rank::Queen,
+                            suit: Suit::Hearts,
+                        },
+                    ],
                 },
             },
             streets: vec![Street {
                 street_type: StreetType::Preflop,
+                cards: vec![],
                 actions: vec![
+                    Action {
+                        player_name: String::from("Anonymous5"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous1"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous 2"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous 3"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous4"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("WinterSound"),
+                        action: ActionType::Post(PostType::Ante(Amount::from_units(70))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous5"),
+                        action: ActionType::Post(PostType::SmallBlind(Amount::from_units(300))),
+                        is_all_in: false,
+                    },
+                    Action {
+                        player_name: String::from("Anonymous1"),
+                        action: ActionType::Post(PostType::BigBlind(Amount::from_units(60))),
+                        is_all_in: false,
+                    },
                     Action {
                         player_name: String::from("Anonymous 2"),
                         action: ActionType::Fold,
@@ -1775,8 +2335,8 @@ mod tests {
                     Action {
                         player_name: String::from("Anonymous 3"),
                         action: ActionType::Raise {
-                            to_call: 750.0,
-                            amount: 1350.0,
+                            to_call: Amount::from_units(750),
+                            amount: Amount::from_units(1350),
                         },
                         is_all_in: false,
                     },
@@ -1802,22 +2362,25 @@ mod tests {
                     },
                     Action {
                         player_name: String::from("Anonymous 3"),
-                        action: ActionType::Collect,
+                        action: ActionType::Collect {
+                            amount: Amount::from_units(2670),
+                            pot: Pot::Main,
+                        },
                         is_all_in: false,
                     },
                 ],
             }],
             summary: Summary {
-                pot: 2670.0,
+                pot: Amount::from_units(2670),
                 rake: None,
                 players: vec![SummaryPlayer {
                     name: String::from("Anonymous 3"),
                     seat: 3,
                     hole_cards: None,
-                    result: SummaryResult::Won(2670.0),
+                    result: SummaryResult::Won(Amount::from_units(2670)),
                     hand_category: None,
                 }],
-                board: None,
+                boards: vec![],
             },
         };
         let (_, actual) = Hand::parse(input).unwrap();
@@ -1827,14 +2390,14 @@ mod tests {
     #[test]
     fn test_parse_hands() {
         let data = include_str!("../samples/sample1.txt");
-        let (_, hands) = parse_hands(data).unwrap();
+        let hands = parse_hands(data).unwrap();
         assert_eq!(hands.len(), 3);
     }
 
     #[test]
     fn test_parse_empty_streets() {
         let data = include_str!("../samples/sample2.txt");
-        let (_, hands) = parse_hands(data).unwrap();
+        let hands = parse_hands(data).unwrap();
         println!("{:?}", hands);
         assert_eq!(hands.len(), 1);
     }
@@ -1842,14 +2405,138 @@ mod tests {
     #[test]
     fn test_parse_hands_play_money() {
         let data = include_str!("../samples/sample_expresso_play_money.txt");
-        let (_, hands) = parse_hands(data).unwrap();
+        let hands = parse_hands(data).unwrap();
         assert_eq!(hands.len(), 1);
     }
 
     #[test]
     fn test_parse_hands_cash_play_money() {
         let data = include_str!("../samples/sample_cash_play_money.txt");
-        let (_, hands) = parse_hands(data).unwrap();
+        let hands = parse_hands(data).unwrap();
         assert_eq!(hands.len(), 1);
     }
+
+    #[test]
+    fn test_parse_hands_omaha() {
+        let data = include_str!("../samples/sample_omaha.txt");
+        let hands = parse_hands(data).unwrap();
+        assert_eq!(hands.len(), 2);
+        assert_eq!(hands[0].hand_info.poker_type, PokerType::OmahaPotLimit);
+        assert_eq!(hands[0].dealt_cards.hole_cards.cards.len(), 4);
+        assert_eq!(hands[1].hand_info.poker_type, PokerType::Omaha5PotLimit);
+        assert_eq!(hands[1].dealt_cards.hole_cards.cards.len(), 5);
+        let showdown = hands[1].summary.players[0].hole_cards.as_ref().unwrap();
+        assert_eq!(showdown.cards.len(), 5);
+        assert_eq!(showdown.cards[0].to_string(), "Ac");
+    }
+
+    #[test]
+    fn test_parse_hands_error_location() {
+        let data = include_str!("../samples/sample1.txt")
+            .replace("Anonymous 2 raises 500 to 1000", "Anonymous 2 sings");
+        let error = parse_hands(&data).unwrap_err();
+        assert_eq!(error.section, Section::Street);
+        assert_eq!(
+            error.hand_id,
+            Some(String::from("2815488303912976462-16-1684698652"))
+        );
+        assert_eq!(error.line, 67);
+        assert_eq!(error.context, "Anonymous 2 sings");
+        assert_eq!(&data[error.offset..error.offset + 17], "Anonymous 2 sings");
+    }
+
+    #[test]
+    fn test_parse_hands_error_hand_info() {
+        let data = include_str!("../samples/sample1.txt").replace("(70/300/600)", "[70/300/600]");
+        let error = parse_hands(&data).unwrap_err();
+        assert_eq!(error.section, Section::HandInfo);
+        assert_eq!(error.hand_id, None);
+        assert_eq!(error.line, 98);
+    }
+
+    #[test]
+    fn test_parse_hands_recovering() {
+        let data = include_str!("../samples/sample1.txt")
+            .replace("Anonymous 2 raises 500 to 1000", "Anonymous 2 sings");
+        let (hands, report) = parse_hands_recovering(&data);
+        assert_eq!(hands.len(), 2);
+        assert_eq!(
+            hands[1].hand_info.hand_id,
+            "2815488303912976462-17-1684698755"
+        );
+        assert_eq!(report.skipped.len(), 1);
+        let skipped = &report.skipped[0];
+        assert_eq!(skipped.error.section, Section::Street);
+        let text = &data[skipped.span.clone()];
+        assert!(text.starts_with("Winamax Poker - Tournament"));
+        assert!(text.contains("HandId: #2815488303912976462-16-1684698652"));
+        assert!(!text.contains("2815488303912976462-17-1684698755"));
+    }
+
+    #[test]
+    fn test_parse_hands_recovering_last_hand() {
+        let data = include_str!("../samples/sample1.txt").replace("Seat 3: Anonymous 3 won", "");
+        let (hands, report) = parse_hands_recovering(&data);
+        assert_eq!(hands.len(), 2);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].span.end, data.len());
+        assert_eq!(report.skipped[0].error.section, Section::Summary);
+    }
+
+    #[test]
+    fn test_hand_json_round_trip() {
+        for data in [
+            include_str!("../samples/sample1.txt"),
+            include_str!("../samples/sample_omaha.txt"),
+            include_str!("../samples/pokerstars.txt"),
+        ] {
+            let (hands, _) = parse_hands_recovering(data);
+            let json = serde_json::to_string(&hands).unwrap();
+            assert_eq!(serde_json::from_str::<Vec<Hand>>(&json).unwrap(), hands);
+        }
+    }
+
+    #[test]
+    fn test_hand_json_fields() {
+        let data = include_str!("../samples/sample1.txt");
+        let (hands, _) = parse_hands_recovering(data);
+        let json = serde_json::to_value(&hands[0]).unwrap();
+        assert_eq!(
+            json["hand_info"]["hand_id"],
+            "2815488303912976462-15-1684698584"
+        );
+        assert_eq!(json["hand_info"]["blinds"]["big_blind"], 500.0);
+        assert_eq!(json["dealt_cards"]["hole_cards"]["cards"][0]["rank"], "Ten");
+        let street = &json["streets"][0];
+        assert_eq!(street["street_type"], "Preflop");
+        assert_eq!(street["actions"][0]["player_name"], "Anonymous 4");
+        assert_eq!(street["actions"][0]["action"]["Post"]["Ante"], 60.0);
+        assert_eq!(street["actions"][8]["player_name"], "Anonymous 5");
+        assert_eq!(street["actions"][8]["action"], "Fold");
+    }
+
+    #[test]
+    fn test_parse_hands_borrowed() {
+        let data = include_str!("../samples/sample1.txt");
+        let hands = parse_hands_borrowed(data).unwrap();
+        let owned: Vec<Hand> = hands.iter().cloned().map(HandRef::into_owned).collect();
+        assert_eq!(owned, parse_hands(data).unwrap());
+
+        // names point into the input
+        let range = data.as_bytes().as_ptr_range();
+        let seat = &hands[0].seats[0];
+        assert!(range.contains(&seat.player_name.as_ptr()));
+        let action = &hands[0].streets[0].actions[8];
+        assert_eq!(action.player_name, "Anonymous 5");
+        assert!(range.contains(&action.player_name.as_ptr()));
+    }
+
+    #[test]
+    fn test_parse_borrowed_error() {
+        let data = include_str!("../samples/sample1.txt");
+        let truncated = &data[..data.find("*** SUMMARY ***").unwrap()];
+        let error = parse_hands_borrowed(truncated).unwrap_err();
+        assert_eq!(error.section, Section::Street);
+        assert!(HandRef::parse_borrowed(truncated).is_err());
+    }
 }
@@ -0,0 +1,977 @@
+use chrono::prelude::*;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_till, take_until, take_while1};
+use nom::character::complete::{
+    alpha1, anychar, char, digit1, line_ending, none_of, not_line_ending, one_of,
+};
+use nom::combinator::{eof, map, map_res, opt, recognize, verify};
+use nom::multi::{many0, many1, many_till, separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+
+use crate::amount::Amount;
+use crate::errors::{ParseError, Section};
+use crate::parser::{
+    parse_street_cards, Action, ActionType, Blinds, Board, Card, DealtToHero, GameInfo, Hand,
+    HandCategory, HandInfo, HoleCards, MoneyType, PokerType, PostType, Pot, Rank, Seat, Street,
+    StreetType, Summary, SummaryPlayer, SummaryResult, TableInfo, TableName, TournamentInfo,
+};
+use crate::site::{Site, SiteParser};
+use crate::timezone::parse_datetime;
+
+/// Parser for PokerStars No Limit Hold'em and Pot Limit Omaha hand histories
+pub struct PokerStarsParser;
+
+impl SiteParser for PokerStarsParser {
+    fn hand_header(&self) -> &'static str {
+        "PokerStars "
+    }
+
+    fn parse_hand<'a>(&self, input: &'a str, origin: &str) -> Result<(&'a str, Hand), ParseError> {
+        parse_hand(input, origin, parse_header)
+    }
+}
+
+/// First line of a hand in the PokerStars format
+pub(crate) struct Header<'a> {
+    pub hand_id: &'a str,
+    pub game_info: GameInfo,
+    pub poker_type: PokerType,
+    pub blinds: Blinds,
+    pub tournament_id: Option<u32>,
+    pub datetime: DateTime<Utc>,
+    pub site: Site,
+    pub currency_code: &'static str,
+}
+
+/// Parses a hand in the PokerStars format, which other sites reuse with their own header.
+pub(crate) fn parse_hand<'a>(
+    input: &'a str,
+    origin: &str,
+    parse_header: fn(&str) -> IResult<&str, Header<'_>>,
+) -> Result<(&'a str, Hand), ParseError> {
+    let locate = |section, hand_id: &Option<String>| {
+        let hand_id = hand_id.clone();
+        move |e| ParseError::new(origin, section, hand_id, e)
+    };
+    let (input, _) =
+        take_till(|c: char| c.is_alphabetic())(input).map_err(locate(Section::HandInfo, &None))?;
+    let (input, header) = parse_header(input).map_err(locate(Section::HandInfo, &None))?;
+    let id = Some(header.hand_id.to_owned());
+    let (input, table_info) = parse_table_info(input, &header.game_info, header.tournament_id)
+        .map_err(locate(Section::TableInfo, &id))?;
+    let (input, seats) =
+        many1(terminated(parse_seat, line_ending))(input).map_err(locate(Section::Seats, &id))?;
+    let (input, (posts, _)) = many_till(
+        alt((
+            parse_posts(header.blinds.big_blind),
+            map(terminated(not_line_ending, line_ending), |_| vec![]),
+        )),
+        pair(tag("*** HOLE CARDS ***"), line_ending),
+    )(input)
+    .map_err(locate(Section::Seats, &id))?;
+    let posts: Vec<Action> = posts.into_iter().flatten().collect();
+    let (input, dealt_cards) =
+        parse_dealt_cards(input).map_err(locate(Section::DealtToHero, &id))?;
+    let (input, preflop) = parse_actions(input).map_err(locate(Section::Street, &id))?;
+    let (input, (mut streets, _)) =
+        many_till(parse_street, pair(tag("*** SUMMARY ***"), line_ending))(input)
+            .map_err(locate(Section::Street, &id))?;
+    let (input, summary) = parse_summary(input).map_err(locate(Section::Summary, &id))?;
+
+    streets.insert(
+        0,
+        Street {
+            street_type: StreetType::Preflop,
+            cards: vec![],
+            actions: posts.into_iter().chain(preflop).collect(),
+        },
+    );
+    let ante = header.blinds.ante.or_else(|| {
+        streets[0]
+            .actions
+            .iter()
+            .find_map(|action| match action.action {
+                ActionType::Post(PostType::Ante(amount)) => Some(amount),
+                _ => None,
+            })
+    });
+    Ok((
+        input,
+        Hand {
+            hand_info: HandInfo {
+                game_info: header.game_info,
+                hand_id: header.hand_id.to_owned(),
+                poker_type: header.poker_type,
+                blinds: Blinds {
+                    ante,
+                    ..header.blinds
+                },
+                datetime: header.datetime,
+                site: header.site.name().to_owned(),
+                currency_code: header.currency_code.to_owned(),
+            },
+            table_info,
+            seats,
+            dealt_cards,
+            streets,
+            summary,
+        },
+    ))
+}
+
+/// Parses "$1,250.50", ignoring the currency symbol and the thousands separators
+pub(crate) fn parse_amount(input: &str) -> IResult<&str, Amount> {
+    let number = recognize(tuple((
+        digit1,
+        many0(pair(char(','), digit1)),
+        opt(pair(char('.'), digit1)),
+    )));
+    preceded(
+        opt(one_of("$€£")),
+        map_res(number, |s: &str| s.replace(',', "").parse()),
+    )(input)
+}
+
+/// Parses " USD", a currency code that cannot be taken for the poker type that may follow
+fn parse_currency(input: &str) -> IResult<&str, &str> {
+    preceded(
+        char(' '),
+        verify(alpha1, |code: &str| {
+            code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+        }),
+    )(input)
+}
+
+fn parse_roman_numeral(input: &str) -> IResult<&str, u32> {
+    map(
+        take_while1(|c: char| "IVXLC".contains(c)),
+        |numeral: &str| {
+            let values: Vec<u32> = numeral
+                .chars()
+                .map(|c| match c {
+                    'I' => 1,
+                    'V' => 5,
+                    'X' => 10,
+                    'L' => 50,
+                    _ => 100,
+                })
+                .collect();
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| match values.get(i + 1) {
+                    Some(&next) if next > value => -(value as i64),
+                    _ => value as i64,
+                })
+                .sum::<i64>() as u32
+        },
+    )(input)
+}
+
+fn parse_blinds(input: &str) -> IResult<&str, Blinds> {
+    let (input, (small_blind, big_blind)) = delimited(
+        char('('),
+        separated_pair(parse_amount, char('/'), parse_amount),
+        pair(opt(parse_currency), char(')')),
+    )(input)?;
+    Ok((
+        input,
+        Blinds {
+            ante: None,
+            small_blind,
+            big_blind,
+        },
+    ))
+}
+
+/// Parses "Hold'em No Limit", "Omaha Pot Limit" and "5 Card Omaha Pot Limit"
+pub(crate) fn parse_poker_type(input: &str) -> IResult<&str, PokerType> {
+    alt((
+        map(tag("Hold'em No Limit"), |_| PokerType::HoldemNoLimit),
+        map(tag("Omaha Pot Limit"), |_| PokerType::OmahaPotLimit),
+        map(tag("5 Card Omaha Pot Limit"), |_| PokerType::Omaha5PotLimit),
+    ))(input)
+}
+
+/// Parses "Tournament #3456789012, $0.98+$0.12 USD Hold'em No Limit - Level II (15/30)"
+fn parse_tournament(input: &str) -> IResult<&str, (GameInfo, PokerType, Blinds)> {
+    let buy_in = alt((
+        map(tag("Freeroll"), |_| vec![Amount::ZERO, Amount::ZERO]),
+        terminated(
+            separated_list1(char('+'), parse_amount),
+            opt(parse_currency),
+        ),
+    ));
+    let (input, (id, _, amounts, _, poker_type, level, _, blinds)) = tuple((
+        preceded(tag("Tournament #"), nom::character::complete::u64),
+        tag(", "),
+        buy_in,
+        take_while1(|c| c == ' '),
+        parse_poker_type,
+        preceded(tag(" - Level "), parse_roman_numeral),
+        char(' '),
+        parse_blinds,
+    ))(input)?;
+    let (rake, buy_in) = amounts.split_last().unwrap_or((&Amount::ZERO, &[]));
+    Ok((
+        input,
+        (
+            GameInfo::Tournament(TournamentInfo {
+                name: id.to_string(),
+                buy_in: buy_in.iter().sum(),
+                rake: *rake,
+                level,
+            }),
+            poker_type,
+            blinds,
+        ),
+    ))
+}
+
+fn parse_cash_game(input: &str) -> IResult<&str, (GameInfo, PokerType, Blinds)> {
+    let (input, (poker_type, blinds)) = separated_pair(
+        preceded(char(' '), parse_poker_type),
+        char(' '),
+        parse_blinds,
+    )(input)?;
+    Ok((input, (GameInfo::CashGame, poker_type, blinds)))
+}
+
+/// Currency code of a header line, from the code or sign of its amounts, dollars by default
+fn header_currency_code(line: &str) -> &'static str {
+    [("EUR", "€"), ("GBP", "£"), ("USD", "$")]
+        .into_iter()
+        .find(|(code, sign)| line.contains(sign) || line.contains(&format!(" {}", code)))
+        .map_or(Site::PokerStars.currency_code(), |(code, _)| code)
+}
+
+fn parse_header(input: &str) -> IResult<&str, Header<'_>> {
+    let currency_code = header_currency_code(input.lines().next().unwrap_or_default());
+    let (input, (zoom, hand_id, (game_info, poker_type, blinds), datetime, _)) = tuple((
+        delimited(tag("PokerStars "), opt(tag("Zoom ")), tag("Hand #")),
+        terminated(take_while1(|c: char| c.is_ascii_digit()), tag(": ")),
+        alt((parse_tournament, parse_cash_game)),
+        // the date is repeated in other timezones
+        preceded(tag(" - "), parse_datetime),
+        pair(not_line_ending, line_ending),
+    ))(input)?;
+    let game_info = match (zoom, game_info) {
+        (Some(_), GameInfo::CashGame) => GameInfo::HoldUp(String::from("Zoom")),
+        (_, game_info) => game_info,
+    };
+    let tournament_id = match &game_info {
+        GameInfo::Tournament(info) => info.name.parse().ok(),
+        _ => None,
+    };
+    Ok((
+        input,
+        Header {
+            hand_id,
+            game_info,
+            poker_type,
+            blinds,
+            tournament_id,
+            datetime,
+            site: Site::PokerStars,
+            currency_code,
+        },
+    ))
+}
+
+/// Parses "Table '3456789012 1' 9-max Seat #3 is the button"
+fn parse_table_info<'a>(
+    input: &'a str,
+    game_info: &GameInfo,
+    tournament_id: Option<u32>,
+) -> IResult<&'a str, TableInfo> {
+    let (input, (name, max_players, play_money, button, _)) = tuple((
+        delimited(tag("Table '"), take_until("' "), tag("' ")),
+        terminated(nom::character::complete::u32, tag("-max ")),
+        opt(tag("(Play Money) ")),
+        delimited(
+            tag("Seat #"),
+            nom::character::complete::u32,
+            tag(" is the button"),
+        ),
+        line_ending,
+    ))(input)?;
+    let table_name = match game_info {
+        GameInfo::Tournament(info) => {
+            // the table number ends the name of tournament tables
+            let table_id = name
+                .rsplit(' ')
+                .next()
+                .and_then(|table| table.parse().ok())
+                .unwrap_or_default();
+            TableName::Tournament(
+                info.name.to_owned(),
+                tournament_id.unwrap_or_default(),
+                table_id,
+            )
+        }
+        _ => TableName::CashGame(name.to_owned()),
+    };
+    Ok((
+        input,
+        TableInfo {
+            table_name,
+            max_players,
+            currency: match play_money {
+                Some(_) => MoneyType::PlayMoney,
+                None => MoneyType::RealMoney,
+            },
+            button,
+        },
+    ))
+}
+
+/// Parses "Seat 1: Player (1500 in chips, $0.50 bounty) is sitting out"
+fn parse_seat(input: &str) -> IResult<&str, Seat> {
+    let stack_bounty = pair(
+        terminated(parse_amount, tag(" in chips")),
+        opt(delimited(tag(", "), parse_amount, tag(" bounty"))),
+    );
+    let (input, (seat_number, player_name, (stack, bounty), _)) = tuple((
+        delimited(tag("Seat "), nom::character::complete::u32, tag(": ")),
+        take_until(" ("),
+        delimited(tag(" ("), stack_bounty, char(')')),
+        not_line_ending,
+    ))(input)?;
+    Ok((
+        input,
+        Seat {
+            seat_number,
+            player_name: player_name.to_owned(),
+            stack,
+            bounty,
+        },
+    ))
+}
+
+fn parse_action_type(input: &str) -> IResult<&str, ActionType> {
+    alt((
+        map(tag("checks"), |_| ActionType::Check),
+        map(tag("folds"), |_| ActionType::Fold),
+        map(preceded(tag("calls "), parse_amount), |amount| {
+            ActionType::Call { amount }
+        }),
+        map(preceded(tag("bets "), parse_amount), |amount| {
+            ActionType::Bet { amount }
+        }),
+        map(
+            preceded(
+                tag("raises "),
+                separated_pair(parse_amount, tag(" to "), parse_amount),
+            ),
+            |(to_call, amount)| ActionType::Raise { to_call, amount },
+        ),
+        // the description of the shown hand is not kept
+        map(
+            delimited(
+                tag("shows ["),
+                HoleCards::parse,
+                pair(char(']'), not_line_ending),
+            ),
+            |cards| ActionType::Shows { cards },
+        ),
+        map(
+            delimited(tag("Pays Cashout Risk ("), parse_amount, char(')')),
+            |amount| ActionType::CashoutRisk { amount },
+        ),
+        map(
+            delimited(tag("Receives Cashout ("), parse_amount, char(')')),
+            |amount| ActionType::Cashout { amount },
+        ),
+    ))(input)
+}
+
+/// Parses "Player: raises 60 to 90 and is all-in", "Player collected 150 from pot" and
+/// "Uncalled bet (30) returned to Player"
+fn parse_action(input: &str) -> IResult<&str, Action> {
+    let uncalled_bet = map(
+        tuple((
+            delimited(tag("Uncalled bet ("), parse_amount, tag(") returned to ")),
+            not_line_ending,
+            line_ending,
+        )),
+        |(amount, player_name, _): (Amount, &str, &str)| Action {
+            player_name: player_name.to_owned(),
+            action: ActionType::UncalledBet { amount },
+            is_all_in: false,
+        },
+    );
+    let action = preceded(
+        tag(": "),
+        pair(parse_action_type, opt(tag(" and is all-in"))),
+    );
+    let collect = map(
+        preceded(
+            tag(" collected "),
+            separated_pair(parse_amount, tag(" from "), Pot::parse),
+        ),
+        |(amount, pot)| (ActionType::Collect { amount, pot }, None),
+    );
+    let player_action = map(
+        terminated(
+            many_till(none_of("\n"), alt((action, collect))),
+            line_ending,
+        ),
+        |(player_name, (action, all_in))| Action {
+            player_name: player_name.into_iter().collect(),
+            action,
+            is_all_in: all_in.is_some(),
+        },
+    );
+    alt((uncalled_bet, player_action))(input)
+}
+
+/// Parses a post preceding the hole cards, as in "Player: posts small blind $0.01"
+///
+/// "posts small & big blinds" gives the big blind of the table and a dead blind for the rest.
+fn parse_posts(big_blind: Amount) -> impl Fn(&str) -> IResult<&str, Vec<Action>> {
+    move |input| {
+        let post = alt((
+            map(preceded(tag("small blind "), parse_amount), |amount| {
+                vec![PostType::SmallBlind(amount)]
+            }),
+            map(preceded(tag("big blind "), parse_amount), |amount| {
+                vec![PostType::BigBlind(amount)]
+            }),
+            map(preceded(tag("the ante "), parse_amount), |amount| {
+                vec![PostType::Ante(amount)]
+            }),
+            map(
+                preceded(tag("small & big blinds "), parse_amount),
+                |amount: Amount| {
+                    let live = amount.min(big_blind);
+                    vec![PostType::BigBlind(live), PostType::Dead(amount - live)]
+                },
+            ),
+        ));
+        map(
+            terminated(
+                many_till(
+                    none_of("\n"),
+                    pair(preceded(tag(": posts "), post), opt(tag(" and is all-in"))),
+                ),
+                line_ending,
+            ),
+            |(player_name, (posts, all_in))| {
+                let player_name: String = player_name.into_iter().collect();
+                posts
+                    .into_iter()
+                    .map(|post| Action {
+                        player_name: player_name.clone(),
+                        action: ActionType::Post(post),
+                        is_all_in: all_in.is_some(),
+                    })
+                    .collect()
+            },
+        )(input)
+    }
+}
+
+/// Recognizes lines that can appear between actions but are not actions themselves
+fn parse_informational_line(input: &str) -> IResult<&str, &str> {
+    let (rest, line) = terminated(not_line_ending, line_ending)(input)?;
+    let informational = line.starts_with("*** SECOND ")
+        || line.contains(" said, \"")
+        || line.contains(": Chooses to EV Cashout")
+        || line.contains(" joins the table at seat #")
+        || [
+            ": doesn't show hand",
+            ": mucks hand",
+            " is disconnected",
+            " is connected",
+            " has timed out",
+            " has timed out while disconnected",
+            " has returned",
+            " is sitting out",
+            " leaves the table",
+        ]
+        .iter()
+        .any(|suffix| line.ends_with(suffix));
+    if informational {
+        Ok((rest, line))
+    } else {
+        Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )))
+    }
+}
+
+/// Parses the hero's hole cards, among the lines of the players whose cards are hidden
+fn parse_dealt_cards(input: &str) -> IResult<&str, DealtToHero> {
+    let hidden_cards = || {
+        many0(delimited(
+            tag("Dealt to "),
+            verify(not_line_ending, |name: &str| !name.contains('[')),
+            line_ending,
+        ))
+    };
+    map(
+        delimited(hidden_cards(), DealtToHero::parse, hidden_cards()),
+        DealtToHero::into_owned,
+    )(input)
+}
+
+fn parse_actions(input: &str) -> IResult<&str, Vec<Action>> {
+    let (input, actions) = many0(alt((
+        map(parse_action, Some),
+        map(parse_informational_line, |_| None),
+    )))(input)?;
+    Ok((input, actions.into_iter().flatten().collect()))
+}
+
+fn parse_street(input: &str) -> IResult<&str, Street> {
+    let street_type = alt((
+        map(tag("FLOP ***"), |_| StreetType::Flop),
+        map(tag("TURN ***"), |_| StreetType::Turn),
+        map(tag("RIVER ***"), |_| StreetType::River),
+        map(alt((tag("SHOW DOWN ***"), tag("SHOWDOWN ***"))), |_| {
+            StreetType::Showdown
+        }),
+    ));
+    // the second run of a hand run twice is skipped as an informational line, its board is
+    // read from the summary
+    let (input, (street_type, cards, _, actions)) = tuple((
+        preceded(pair(tag("*** "), opt(tag("FIRST "))), street_type),
+        parse_street_cards,
+        pair(not_line_ending, line_ending),
+        parse_actions,
+    ))(input)?;
+    Ok((
+        input,
+        Street {
+            street_type,
+            cards,
+            actions,
+        },
+    ))
+}
+
+fn parse_rank_name(input: &str) -> IResult<&str, Rank> {
+    alt((
+        map(alt((tag("Deuces"), tag("Deuce"))), |_| Rank::Two),
+        map(alt((tag("Treys"), tag("Trey"))), |_| Rank::Three),
+        map(alt((tag("Fours"), tag("Four"))), |_| Rank::Four),
+        map(alt((tag("Fives"), tag("Five"))), |_| Rank::Five),
+        map(alt((tag("Sixes"), tag("Six"))), |_| Rank::Six),
+        map(alt((tag("Sevens"), tag("Seven"))), |_| Rank::Seven),
+        map(alt((tag("Eights"), tag("Eight"))), |_| Rank::Eight),
+        map(alt((tag("Nines"), tag("Nine"))), |_| Rank::Nine),
+        map(alt((tag("Tens"), tag("Ten"))), |_| Rank::Ten),
+        map(alt((tag("Jacks"), tag("Jack"))), |_| Rank::Jack),
+        map(alt((tag("Queens"), tag("Queen"))), |_| Rank::Queen),
+        map(alt((tag("Kings"), tag("King"))), |_| Rank::King),
+        map(alt((tag("Aces"), tag("Ace"))), |_| Rank::Ace),
+    ))(input)
+}
+
+fn parse_hand_category(input: &str) -> IResult<&str, HandCategory> {
+    let two_ranks = |separator| separated_pair(parse_rank_name, tag(separator), parse_rank_name);
+    alt((
+        map(
+            preceded(tag("high card "), parse_rank_name),
+            HandCategory::HighCard,
+        ),
+        map(
+            preceded(tag("a pair of "), parse_rank_name),
+            HandCategory::Pair,
+        ),
+        map(
+            preceded(tag("two pair, "), two_ranks(" and ")),
+            |(r1, r2)| HandCategory::TwoPair(r1, r2),
+        ),
+        map(
+            preceded(tag("three of a kind, "), parse_rank_name),
+            HandCategory::ThreeOfAKind,
+        ),
+        map(
+            preceded(tag("a straight flush, "), two_ranks(" to ")),
+            |(_, high)| HandCategory::StraightFlush(high),
+        ),
+        map(tag("a Royal Flush"), |_| {
+            HandCategory::StraightFlush(Rank::Ace)
+        }),
+        map(
+            preceded(tag("a straight, "), two_ranks(" to ")),
+            |(_, high)| HandCategory::Straight(high),
+        ),
+        map(
+            delimited(tag("a flush, "), parse_rank_name, tag(" high")),
+            HandCategory::Flush,
+        ),
+        map(
+            preceded(tag("a full house, "), two_ranks(" full of ")),
+            |(r1, r2)| HandCategory::Full(r1, r2),
+        ),
+        map(
+            preceded(tag("four of a kind, "), parse_rank_name),
+            HandCategory::FourOfAKind,
+        ),
+    ))(input)
+}
+
+/// Parses "Seat 4: Player (small blind) showed [Ah Kd] and lost with a pair of Aces"
+fn parse_summary_player(input: &str) -> IResult<&str, SummaryPlayer> {
+    let position = many0(delimited(
+        tag(" ("),
+        alt((tag("button"), tag("small blind"), tag("big blind"))),
+        char(')'),
+    ));
+    let cards = || delimited(tag(" ["), HoleCards::parse, char(']'));
+    let category = || opt(preceded(tag(" with "), parse_hand_category));
+    let won = map(
+        tuple((
+            preceded(tag(" showed"), cards()),
+            delimited(tag(" and won ("), parse_amount, char(')')),
+            category(),
+        )),
+        |(cards, amount, category)| (Some(cards), SummaryResult::Won(amount), category),
+    );
+    let lost = map(
+        pair(
+            preceded(tag(" showed"), cards()),
+            preceded(tag(" and lost"), category()),
+        ),
+        |(cards, category)| (Some(cards), SummaryResult::Lost, category),
+    );
+    let collected = map(
+        delimited(
+            alt((tag(" collected ("), tag(" won ("))),
+            parse_amount,
+            char(')'),
+        ),
+        |amount| (None, SummaryResult::Won(amount), None),
+    );
+    let mucked = map(preceded(tag(" mucked"), opt(cards())), |cards| {
+        (cards, SummaryResult::Lost, None)
+    });
+    let folded = map(tag(" folded"), |_| (None, SummaryResult::Lost, None));
+    let outcome = preceded(position, alt((won, lost, collected, mucked, folded)));
+
+    let (input, (seat, (name, (hole_cards, result, hand_category)), rest)) = tuple((
+        delimited(tag("Seat "), nom::character::complete::u32, tag(": ")),
+        many_till(anychar, outcome),
+        terminated(not_line_ending, alt((line_ending, eof))),
+    ))(input)?;
+    // the result of the second run of a hand run twice follows the first one
+    let second_run = rest
+        .split_once(", and won (")
+        .and_then(|(_, amount)| parse_amount(amount).ok());
+    let result = match (result, second_run) {
+        (SummaryResult::Won(first), Some((_, second))) => SummaryResult::Won(first + second),
+        (SummaryResult::Lost, Some((_, second))) => SummaryResult::Won(second),
+        (result, None) => result,
+    };
+    Ok((
+        input,
+        SummaryPlayer {
+            name: name.into_iter().collect(),
+            seat,
+            hole_cards,
+            result,
+            hand_category,
+        },
+    ))
+}
+
+fn parse_summary(input: &str) -> IResult<&str, Summary> {
+    let rake = preceded(tag(" | Rake "), parse_amount);
+    let board = |prefix| {
+        delimited(
+            tag(prefix),
+            separated_list0(char(' '), Card::parse),
+            pair(char(']'), line_ending),
+        )
+    };
+    let run_twice = tuple((
+        tag("Hand was run "),
+        pair(not_line_ending, line_ending),
+        board("FIRST Board ["),
+        board("SECOND Board ["),
+    ));
+    let (input, (pot, rake, _, boards, players)) = tuple((
+        preceded(tag("Total pot "), parse_amount),
+        // side pots are listed between the total pot and the rake
+        preceded(take_until(" | Rake "), rake),
+        pair(not_line_ending, line_ending),
+        opt(alt((
+            map(board("Board ["), |board| vec![board]),
+            map(run_twice, |(_, _, first, second)| vec![first, second]),
+        ))),
+        many1(parse_summary_player),
+    ))(input)?;
+    Ok((
+        input,
+        Summary {
+            pot,
+            rake: Some(rake).filter(|rake| *rake > Amount::ZERO),
+            players,
+            boards: boards
+                .unwrap_or_default()
+                .into_iter()
+                .map(|cards| {
+                    let mut cards: Vec<Option<Card>> = cards.into_iter().map(Some).collect();
+                    cards.resize_with(5, || None);
+                    Board { cards }
+                })
+                .collect(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_roman_numeral() {
+        assert_eq!(parse_roman_numeral("II ("), Ok((" (", 2)));
+        assert_eq!(parse_roman_numeral("XIV"), Ok(("", 14)));
+        assert_eq!(parse_roman_numeral("XL"), Ok(("", 40)));
+    }
+
+    #[test]
+    fn test_parse_header_tournament() {
+        let input = "PokerStars Hand #245781234567: Tournament #3456789012, $0.98+$0.12 USD \
+        Hold'em No Limit - Level II (15/30) - 2023/05/21 21:49:44 CET [2023/05/21 15:49:44 ET]\n";
+        let (_, header) = parse_header(input).unwrap();
+        assert_eq!(header.hand_id, "245781234567");
+        assert_eq!(header.tournament_id, Some(3456789012));
+        assert_eq!(
+            header.game_info,
+            GameInfo::Tournament(TournamentInfo {
+                name: String::from("3456789012"),
+                buy_in: Amount::from_cents(98),
+                rake: Amount::from_cents(12),
+                level: 2,
+            })
+        );
+        assert_eq!(
+            header.blinds,
+            Blinds {
+                ante: None,
+                small_blind: Amount::from_units(15),
+                big_blind: Amount::from_units(30),
+            }
+        );
+        assert_eq!(
+            header.datetime,
+            Utc.with_ymd_and_hms(2023, 5, 21, 19, 49, 44).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_header_zoom() {
+        let input = "PokerStars Zoom Hand #245781234568:  Hold'em No Limit ($0.05/$0.10) - \
+        2023/05/21 15:49:44 ET\n";
+        let (_, header) = parse_header(input).unwrap();
+        assert_eq!(header.game_info, GameInfo::HoldUp(String::from("Zoom")));
+        assert_eq!(header.tournament_id, None);
+        assert_eq!(header.blinds.big_blind, Amount::from_cents(10));
+    }
+
+    #[test]
+    fn test_parse_header_omaha() {
+        let input = "PokerStars Hand #245781234569:  5 Card Omaha Pot Limit ($0.10/$0.25 USD) - \
+        2023/05/21 15:49:44 ET\n";
+        let (_, header) = parse_header(input).unwrap();
+        assert_eq!(header.poker_type, PokerType::Omaha5PotLimit);
+        assert_eq!(header.blinds.small_blind, Amount::from_cents(10));
+    }
+
+    #[test]
+    fn test_parse_seat() {
+        let input = "Seat 6: Villain 6 (1500 in chips, $0.50 bounty) is sitting out\n";
+        let expected = Seat {
+            seat_number: 6,
+            player_name: String::from("Villain 6"),
+            stack: Amount::from_units(1500),
+            bounty: Some(Amount::from_cents(50)),
+        };
+        assert_eq!(parse_seat(input).unwrap(), ("\n", expected));
+    }
+
+    #[test]
+    fn test_parse_action() {
+        let input = "Villain: 1: raises 765 to 1165 and is all-in\n";
+        let expected = Action {
+            player_name: String::from("Villain: 1"),
+            action: ActionType::Raise {
+                to_call: Amount::from_units(765),
+                amount: Amount::from_units(1165),
+            },
+            is_all_in: true,
+        };
+        assert_eq!(parse_action(input).unwrap(), ("", expected));
+    }
+
+    #[test]
+    fn test_parse_cashout() {
+        let input = concat!(
+            "Hero: Chooses to EV Cashout\n",
+            "Hero: Receives Cashout ($7.21)\n",
+            "*** FLOP *** [Ad 9h 4s]\n",
+        );
+        let (input, actions) = parse_actions(input).unwrap();
+        assert_eq!(input, "*** FLOP *** [Ad 9h 4s]\n");
+        assert_eq!(
+            actions,
+            vec![Action {
+                player_name: String::from("Hero"),
+                action: ActionType::Cashout {
+                    amount: Amount::from_cents(721)
+                },
+                is_all_in: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_posts() {
+        let post = |player_name: &str, post| Action {
+            player_name: String::from(player_name),
+            action: ActionType::Post(post),
+            is_all_in: false,
+        };
+        let parse = parse_posts(Amount::from_cents(2));
+        assert_eq!(
+            parse("Villain 2: posts small blind $0.01\n"),
+            Ok((
+                "",
+                vec![post(
+                    "Villain 2",
+                    PostType::SmallBlind(Amount::from_cents(1))
+                )]
+            ))
+        );
+        assert_eq!(
+            parse("Villain5: posts small & big blinds $0.03\n"),
+            Ok((
+                "",
+                vec![
+                    post("Villain5", PostType::BigBlind(Amount::from_cents(2))),
+                    post("Villain5", PostType::Dead(Amount::from_cents(1))),
+                ]
+            ))
+        );
+        let (_, posts) = parse("Hero: posts the ante $0.01 and is all-in\n").unwrap();
+        assert!(posts[0].is_all_in);
+        assert!(parse("Villain5: sits out\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_actions_skips_informational_lines() {
+        let input = concat!(
+            "Villain5: folds\n",
+            "Uncalled bet ($0.08) returned to WinterSound\n",
+            "WinterSound collected $0.13 from pot\n",
+            "WinterSound: doesn't show hand\n",
+            "*** SUMMARY ***\n",
+        );
+        let (input, actions) = parse_actions(input).unwrap();
+        assert_eq!(input, "*** SUMMARY ***\n");
+        assert_eq!(actions.len(), 3);
+        assert_eq!(actions[1].player_name, "WinterSound");
+        assert_eq!(
+            actions[1].action,
+            ActionType::UncalledBet {
+                amount: Amount::from_cents(8)
+            }
+        );
+        assert_eq!(
+            actions[2].action,
+            ActionType::Collect {
+                amount: Amount::from_cents(13),
+                pot: Pot::Main,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_summary_player_showdown() {
+        let input =
+            "Seat 4: WinterSound (small blind) showed [Ah Kd] and lost with a pair of Aces\n";
+        let (_, player) = parse_summary_player(input).unwrap();
+        assert_eq!(player.name, "WinterSound");
+        assert_eq!(player.result, SummaryResult::Lost);
+        assert_eq!(player.hand_category, Some(HandCategory::Pair(Rank::Ace)));
+        assert!(player.hole_cards.is_some());
+    }
+
+    #[test]
+    fn test_parse_hand_category() {
+        assert_eq!(
+            parse_hand_category("a full house, Kings full of Deuces"),
+            Ok(("", HandCategory::Full(Rank::King, Rank::Two)))
+        );
+        assert_eq!(
+            parse_hand_category("a straight, Six to Ten"),
+            Ok(("", HandCategory::Straight(Rank::Ten)))
+        );
+    }
+
+    #[test]
+    fn test_parse_hands() {
+        let data = include_str!("../samples/pokerstars.txt");
+        let hands = PokerStarsParser.parse_hands(data).unwrap();
+        assert_eq!(hands.len(), 2);
+
+        let tournament = &hands[0];
+        assert_eq!(
+            tournament.hand_info.blinds.ante,
+            Some(Amount::from_units(5))
+        );
+        assert_eq!(
+            tournament.table_info.table_name,
+            TableName::Tournament(String::from("3456789012"), 3456789012, 1)
+        );
+        assert_eq!(tournament.seats.len(), 4);
+        assert_eq!(tournament.dealt_cards.player_name, "WinterSound");
+        assert_eq!(tournament.streets.len(), 5);
+        assert_eq!(tournament.streets[0].actions.len(), 11);
+        assert_eq!(
+            tournament.streets[0].actions[5],
+            Action {
+                player_name: String::from("Villain6"),
+                action: ActionType::Post(PostType::BigBlind(Amount::from_units(30))),
+                is_all_in: false,
+            }
+        );
+        assert!(tournament.streets[1].actions[1].is_all_in);
+        assert_eq!(tournament.summary.pot, Amount::from_units(2980));
+        assert_eq!(tournament.summary.rake, None);
+        assert_eq!(tournament.summary.players.len(), 4);
+        assert_eq!(
+            tournament.summary.players[0].result,
+            SummaryResult::Won(Amount::from_units(2980))
+        );
+
+        let cash_game = &hands[1];
+        assert_eq!(cash_game.hand_info.game_info, GameInfo::CashGame);
+        assert_eq!(
+            cash_game.hand_info.blinds.small_blind,
+            Amount::from_cents(1)
+        );
+        assert_eq!(
+            cash_game.table_info.table_name,
+            TableName::CashGame(String::from("Aase III"))
+        );
+        assert_eq!(cash_game.seats[1].stack, Amount::from_cents(185));
+        assert_eq!(cash_game.streets[1].actions.len(), 5);
+        assert_eq!(
+            cash_game.streets[1].actions[3].action,
+            ActionType::UncalledBet {
+                amount: Amount::from_cents(8)
+            }
+        );
+        assert_eq!(
+            cash_game.summary.players[0].result,
+            SummaryResult::Won(Amount::from_cents(13))
+        );
+    }
+}
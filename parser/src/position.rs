@@ -0,0 +1,243 @@
+//! Positions of the players at the table, from the button and the occupied seats
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{Hand, Seat};
+
+/// Position of a seat, in the order players act before the flop
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Position {
+    SmallBlind,
+    BigBlind,
+    UnderTheGun,
+    UnderTheGun1,
+    UnderTheGun2,
+    UnderTheGun3,
+    Lojack,
+    Hijack,
+    Cutoff,
+    /// Button, which posts the small blind heads-up
+    Button,
+}
+
+/// Positions between the big blind and the button, from first to act
+const EARLY: [Position; 4] = [
+    Position::UnderTheGun,
+    Position::UnderTheGun1,
+    Position::UnderTheGun2,
+    Position::UnderTheGun3,
+];
+/// Positions before the button, from last to act
+const LATE: [Position; 3] = [Position::Cutoff, Position::Hijack, Position::Lojack];
+
+impl Position {
+    /// Position of the `index`th of `count` players acting between the big blind and the button
+    ///
+    /// The first of them is under the gun, the last ones are named from the cutoff backwards.
+    fn between_blinds_and_button(index: usize, count: usize) -> Position {
+        let late = (count - 1).min(LATE.len());
+        let from_last = count - 1 - index;
+        if from_last < late {
+            LATE[from_last]
+        } else {
+            EARLY[index.min(EARLY.len() - 1)]
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Position::SmallBlind => "SB",
+                Position::BigBlind => "BB",
+                Position::UnderTheGun => "UTG",
+                Position::UnderTheGun1 => "UTG+1",
+                Position::UnderTheGun2 => "UTG+2",
+                Position::UnderTheGun3 => "UTG+3",
+                Position::Lojack => "LJ",
+                Position::Hijack => "HJ",
+                Position::Cutoff => "CO",
+                Position::Button => "BTN",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParsePositionError;
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid position")
+    }
+}
+
+impl std::error::Error for ParsePositionError {}
+
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Position::SmallBlind,
+            Position::BigBlind,
+            Position::UnderTheGun,
+            Position::UnderTheGun1,
+            Position::UnderTheGun2,
+            Position::UnderTheGun3,
+            Position::Lojack,
+            Position::Hijack,
+            Position::Cutoff,
+            Position::Button,
+        ]
+        .into_iter()
+        .find(|position| position.to_string() == s)
+        .ok_or(ParsePositionError)
+    }
+}
+
+impl<S> Hand<S> {
+    /// Positions of the players, in the order of `seats`
+    ///
+    /// Empty seats are skipped: the small blind is the first player after the button. When the
+    /// button is on an empty seat, no one is on the button and the player before it acts last.
+    /// Heads-up, the button posts the small blind.
+    pub fn positions(&self) -> Vec<Position> {
+        let button = self.table_info.button;
+        let mut order: Vec<usize> = (0..self.seats.len()).collect();
+        order.sort_by_key(|&index| {
+            let seat_number = self.seats[index].seat_number;
+            (seat_number <= button, seat_number)
+        });
+        let mut positions = vec![Position::Button; self.seats.len()];
+        if self.seats.iter().any(|seat| seat.seat_number == button) {
+            // the button comes last in the order
+            order.pop();
+            if order.len() == 1 {
+                positions[order[0]] = Position::BigBlind;
+                return positions;
+            }
+        }
+        let count = order.len().saturating_sub(2);
+        for (rank, &index) in order.iter().enumerate() {
+            positions[index] = match rank {
+                0 => Position::SmallBlind,
+                1 => Position::BigBlind,
+                _ => Position::between_blinds_and_button(rank - 2, count),
+            };
+        }
+        positions
+    }
+
+    /// Small blind and big blind seats, the button posting the small blind heads-up
+    pub fn blind_seats(&self) -> (Option<&Seat<S>>, Option<&Seat<S>>) {
+        let positions = self.positions();
+        let seat = |position| {
+            self.seats
+                .iter()
+                .zip(&positions)
+                .find_map(|(seat, p)| (*p == position).then_some(seat))
+        };
+        let small_blind = seat(Position::SmallBlind).or_else(|| seat(Position::Button));
+        (small_blind, seat(Position::BigBlind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_hands, Seat};
+    use Position::*;
+
+    /// Sample hand with players on `seat_numbers` and the button on `button`
+    fn hand_at(seat_numbers: &[u32], button: u32) -> Hand {
+        let data = include_str!("../samples/sample1.txt");
+        let mut hand = parse_hands(data).unwrap().remove(0);
+        let seat = hand.seats[0].clone();
+        hand.seats = seat_numbers
+            .iter()
+            .map(|&seat_number| Seat {
+                seat_number,
+                player_name: format!("Player {}", seat_number),
+                ..seat.clone()
+            })
+            .collect();
+        hand.table_info.button = button;
+        hand
+    }
+
+    #[test]
+    fn test_positions_full_table() {
+        let data = include_str!("../samples/sample1.txt");
+        let hand = &parse_hands(data).unwrap()[0];
+        assert_eq!(
+            hand.positions(),
+            vec![Hijack, Cutoff, Button, SmallBlind, BigBlind, UnderTheGun]
+        );
+        assert_eq!(
+            hand_at(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 9).positions(),
+            vec![
+                SmallBlind,
+                BigBlind,
+                UnderTheGun,
+                UnderTheGun1,
+                UnderTheGun2,
+                Lojack,
+                Hijack,
+                Cutoff,
+                Button
+            ]
+        );
+        assert_eq!(
+            hand_at(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 1).positions()[1..4],
+            [SmallBlind, BigBlind, UnderTheGun]
+        );
+        assert_eq!(
+            hand_at(&[1, 2, 3, 4], 1).positions(),
+            vec![Button, SmallBlind, BigBlind, UnderTheGun]
+        );
+    }
+
+    #[test]
+    fn test_positions_empty_seats() {
+        assert_eq!(
+            hand_at(&[1, 3, 6], 6).positions(),
+            vec![SmallBlind, BigBlind, Button]
+        );
+        assert_eq!(
+            hand_at(&[2, 4, 5, 8, 9], 4).positions(),
+            vec![Cutoff, Button, SmallBlind, BigBlind, UnderTheGun]
+        );
+    }
+
+    #[test]
+    fn test_positions_dead_button() {
+        assert_eq!(
+            hand_at(&[1, 3, 4, 5], 2).positions(),
+            vec![Cutoff, SmallBlind, BigBlind, UnderTheGun]
+        );
+        assert_eq!(hand_at(&[2, 5], 3).positions(), vec![BigBlind, SmallBlind]);
+    }
+
+    #[test]
+    fn test_positions_heads_up() {
+        let hand = hand_at(&[2, 5], 5);
+        assert_eq!(hand.positions(), vec![BigBlind, Button]);
+        assert_eq!(hand_at(&[2, 5], 2).positions(), vec![Button, BigBlind]);
+    }
+
+    #[test]
+    fn test_position_names() {
+        for position in hand_at(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 10).positions() {
+            assert_eq!(position.to_string().parse(), Ok(position));
+        }
+        assert_eq!(UnderTheGun1.to_string(), "UTG+1");
+        assert_eq!("MP".parse::<Position>(), Err(ParsePositionError));
+    }
+}
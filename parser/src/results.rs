@@ -0,0 +1,185 @@
+//! Results of the players of a hand, as played and as expected from their equity at an all-in
+
+use crate::amount::Amount;
+use crate::equity::{EquityCalculator, EquityError, Holding};
+use crate::parser::Hand;
+use crate::timeline::HandTimeline;
+
+/// Chips won or lost by a player in a hand
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PlayerResult {
+    pub net: Amount,
+    /// Result the player could expect, the pots of an all-in being shared by the equity of the
+    /// players when the last chips went in
+    pub ev_net: Amount,
+}
+
+impl<S: AsRef<str>> Hand<S> {
+    /// Index of hero in `seats`
+    pub fn hero_index(&self) -> Option<usize> {
+        let hero = self.dealt_cards.player_name.as_ref();
+        self.seats
+            .iter()
+            .position(|seat| seat.player_name.as_ref() == hero)
+    }
+
+    /// Result of the player at `index` in `seats`
+    ///
+    /// The expected result differs from the actual one when a player left was all-in before
+    /// the board was complete and the cards of every player left are known. Each pot is then
+    /// shared among the players who could win it, once raked like the pots of the hand.
+    pub fn result(
+        &self,
+        index: usize,
+        calculator: &EquityCalculator,
+    ) -> Result<PlayerResult, EquityError> {
+        let timeline = HandTimeline::new(self);
+        let settlement = timeline.settlement(self);
+        let net = settlement.net(index);
+        let played = PlayerResult { net, ev_net: net };
+
+        // the equity is only worked out once an all-in is known to have been called
+        let Some((players, holdings)) = self.showdown_holdings(&timeline) else {
+            return Ok(played);
+        };
+        let contributions = settlement.contributions();
+        let is_all_in = players
+            .iter()
+            .any(|&player| contributions[player] == self.seats[player].stack);
+        let board: Vec<_> = self
+            .board_at(self.last_money_street())
+            .into_iter()
+            .cloned()
+            .collect();
+        if !is_all_in || !players.contains(&index) || board.len() >= 5 {
+            return Ok(played);
+        }
+        let equity = calculator.equity(&self.hand_info.poker_type, &holdings, &board)?;
+
+        // pots from the smallest all-in, each one open to the players who matched it
+        let mut levels: Vec<Amount> = players.iter().map(|&p| contributions[p]).collect();
+        levels.sort();
+        levels.dedup();
+        let mut previous = Amount::ZERO;
+        let mut pots = vec![];
+        for level in levels {
+            let pot: Amount = contributions
+                .iter()
+                .map(|&c| c.min(level) - c.min(previous))
+                .sum();
+            previous = level;
+            let eligible: Vec<usize> = players
+                .iter()
+                .copied()
+                .filter(|&p| contributions[p] >= level)
+                .collect();
+            pots.push((pot, eligible));
+        }
+
+        // the rake is only taken from the pots several players can win
+        let (contested, uncontested): (Vec<_>, Vec<_>) =
+            pots.iter().partition(|(_, eligible)| eligible.len() > 1);
+        let contested: Amount = contested.iter().map(|(pot, _)| *pot).sum();
+        let uncontested: Amount = uncontested.iter().map(|(pot, _)| *pot).sum();
+        let collected: Amount = settlement.collected.iter().sum();
+        if contested == Amount::ZERO {
+            return Ok(played);
+        }
+        let raked = (collected - uncontested).to_f64() / contested.to_f64();
+
+        let mut expected = 0.0;
+        for (pot, eligible) in &pots {
+            let Some(position) = eligible.iter().position(|&p| p == index) else {
+                continue;
+            };
+            let share = match eligible.len() {
+                1 => {
+                    expected += pot.cents() as f64;
+                    continue;
+                }
+                _ if *eligible == players => equity.equities[position],
+                _ => {
+                    let holdings: Vec<Holding> = eligible
+                        .iter()
+                        .map(|&p| Holding::Cards(self.known_cards(p).unwrap_or_default().to_vec()))
+                        .collect();
+                    calculator
+                        .equity(&self.hand_info.poker_type, &holdings, &board)?
+                        .equities[position]
+                }
+            };
+            expected += pot.cents() as f64 * share * raked;
+        }
+        Ok(PlayerResult {
+            net,
+            ev_net: Amount::from_cents(expected.round() as i64) - contributions[index]
+                + settlement.cashouts[index],
+        })
+    }
+
+    /// Result of hero, `None` when they are not seated
+    pub fn hero_result(
+        &self,
+        calculator: &EquityCalculator,
+    ) -> Result<Option<PlayerResult>, EquityError> {
+        self.hero_index()
+            .map(|index| self.result(index, calculator))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_hands;
+
+    fn calculator() -> EquityCalculator {
+        EquityCalculator {
+            samples: 20_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_result_without_all_in() {
+        let hand = &parse_hands(include_str!("../samples/sample1.txt")).unwrap()[0];
+        let result = hand.hero_result(&calculator()).unwrap().unwrap();
+        assert_eq!(result.net, Amount::from_units(-2975));
+        assert_eq!(result.ev_net, result.net);
+    }
+
+    #[test]
+    fn test_chip_ev() {
+        let hand = &parse_hands(include_str!("../samples/ggpoker.txt")).unwrap()[0];
+        let result = hand.hero_result(&calculator()).unwrap().unwrap();
+        // queens called all-in by ace-jack, and held
+        assert_eq!(result.net, Amount::from_units(11125));
+        let expected = 21000.0 * 0.7147 - 9875.0;
+        assert!((result.ev_net.to_f64() - expected).abs() < 150.0);
+    }
+
+    #[test]
+    fn test_ev_run_twice() {
+        let hand = &parse_hands(include_str!("../samples/ggpoker.txt")).unwrap()[1];
+        let hero = hand.hero_result(&calculator()).unwrap().unwrap();
+        assert_eq!(hero.net, Amount::from_cents(-10));
+        // 91.2% of the pot once raked, on the flop
+        assert_eq!(hero.ev_net, Amount::from_cents(376));
+        let villain = hand.result(2, &calculator()).unwrap();
+        assert_eq!(villain.net, Amount::from_cents(-11));
+        // both players share the rake
+        assert_eq!(hero.ev_net + villain.ev_net, hero.net + villain.net);
+    }
+
+    #[test]
+    fn test_ev_side_pot() {
+        let hand = &parse_hands(include_str!("../samples/sample2.txt")).unwrap()[0];
+        let result = hand.hero_result(&calculator()).unwrap().unwrap();
+        assert_eq!(result.net, Amount::from_cents(50));
+        // the unmatched 0.34€ is not raked, 41.2% of the main pot of 1.05€ is expected
+        assert!((result.ev_net.cents() + 12).abs() <= 1);
+        let opponent = hand.result(3, &calculator()).unwrap();
+        assert_eq!(opponent.net, Amount::from_cents(-55));
+        assert!((opponent.ev_net.cents() - 7).abs() <= 1);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::{ParseError, ParseReport, SkippedHand};
+use crate::ggpoker::GGPokerParser;
+use crate::parser::{Hand, WinamaxParser};
+use crate::pokerstars::PokerStarsParser;
+
+/// Hand history parser for a poker site, producing the common `Hand` model.
+pub trait SiteParser {
+    /// Text starting the first line of every hand
+    fn hand_header(&self) -> &'static str;
+
+    /// Parses the hand at the beginning of `input`, locating failures in `origin`.
+    fn parse_hand<'a>(&self, input: &'a str, origin: &str) -> Result<(&'a str, Hand), ParseError>;
+
+    /// Parses every hand of a hand history file, failing on the first hand that cannot be parsed.
+    fn parse_hands(&self, input: &str) -> Result<Vec<Hand>, ParseError> {
+        let mut hands = vec![];
+        let mut remaining = input;
+        while !remaining.trim().is_empty() {
+            let (rest, hand) = self.parse_hand(remaining, input)?;
+            hands.push(hand);
+            remaining = rest;
+        }
+        Ok(hands)
+    }
+
+    /// Parses every hand of a hand history file, skipping the hands that cannot be parsed.
+    ///
+    /// After a failure, parsing resumes at the next hand header; the skipped blocks are
+    /// listed in the returned report.
+    fn parse_hands_recovering(&self, input: &str) -> (Vec<Hand>, ParseReport) {
+        let header = format!("\n{}", self.hand_header());
+        let mut hands = vec![];
+        let mut report = ParseReport::default();
+        let mut remaining = input;
+        while !remaining.trim().is_empty() {
+            match self.parse_hand(remaining, input) {
+                Ok((rest, hand)) => {
+                    hands.push(hand);
+                    remaining = rest;
+                }
+                Err(error) => {
+                    let start = input.len() - remaining.trim_start().len();
+                    let end = input[start..]
+                        .find(&header)
+                        .map_or(input.len(), |i| start + i + 1);
+                    report.skipped.push(SkippedHand {
+                        span: start..end,
+                        error,
+                    });
+                    remaining = &input[end..];
+                }
+            }
+        }
+        (hands, report)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Site {
+    Winamax,
+    PokerStars,
+    GGPoker,
+}
+
+impl Site {
+    /// Detects the site a hand history comes from, using the header of its first hand.
+    pub fn detect(input: &str) -> Option<Site> {
+        let header = input.trim_start_matches(|c: char| !c.is_alphabetic());
+        [Site::Winamax, Site::PokerStars, Site::GGPoker]
+            .into_iter()
+            .find(|site| header.starts_with(site.parser().hand_header()))
+    }
+
+    /// Name of the site, as kept in `HandInfo::site`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Site::Winamax => "Winamax",
+            Site::PokerStars => "PokerStars",
+            Site::GGPoker => "GGPoker",
+        }
+    }
+
+    /// Site named `name`, as kept in `HandInfo::site`
+    pub fn from_name(name: &str) -> Option<Site> {
+        [Site::Winamax, Site::PokerStars, Site::GGPoker]
+            .into_iter()
+            .find(|site| site.name() == name)
+    }
+
+    /// Name of the network sharing the tables of the site
+    pub fn network(&self) -> &'static str {
+        match self {
+            Site::GGPoker => "GGNetwork",
+            _ => self.name(),
+        }
+    }
+
+    /// Currency code of the real money games of the site, or of its hands that do not tell
+    pub fn currency_code(&self) -> &'static str {
+        match self {
+            Site::Winamax => "EUR",
+            Site::PokerStars | Site::GGPoker => "USD",
+        }
+    }
+
+    pub fn parser(&self) -> &'static dyn SiteParser {
+        match self {
+            Site::Winamax => &WinamaxParser,
+            Site::PokerStars => &PokerStarsParser,
+            Site::GGPoker => &GGPokerParser,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_site() {
+        let winamax = include_str!("../samples/sample1.txt");
+        assert_eq!(Site::detect(winamax), Some(Site::Winamax));
+        let pokerstars = include_str!("../samples/pokerstars.txt");
+        assert_eq!(Site::detect(pokerstars), Some(Site::PokerStars));
+        assert_eq!(
+            Site::detect("\u{feff}PokerStars Zoom Hand #1"),
+            Some(Site::PokerStars)
+        );
+        let ggpoker = include_str!("../samples/ggpoker.txt");
+        assert_eq!(Site::detect(ggpoker), Some(Site::GGPoker));
+        assert_eq!(Site::detect("Full Tilt Poker Game #1"), None);
+    }
+}
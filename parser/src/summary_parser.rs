@@ -2,39 +2,36 @@ use chrono::prelude::*;
 
 use nom;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::{line_ending, not_line_ending};
-use nom::combinator::{map, map_res, opt};
-use nom::multi::separated_list1;
-use nom::number::complete::double;
+use nom::bytes::complete::{tag, take_until, take_while1};
+use nom::character::complete::{anychar, char, line_ending};
+use nom::combinator::{map, not, opt, recognize};
+use nom::multi::{many1_count, separated_list1};
 use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
-enum PokerType {
-    HoldemNoLimit,
-    OmahaPotLimit,
-}
+use crate::amount::Amount;
+use crate::errors::{ParseError, Section};
+use crate::parser::PokerType;
+use crate::timezone::parse_datetime;
 
-impl PokerType {
-    fn parse(input: &str) -> IResult<&str, PokerType> {
-        let (input, _) = alt((
-            map(tag("holdem-no-limit"), |_| PokerType::HoldemNoLimit),
-            map(tag("omaha-pot-limit"), |_| PokerType::OmahaPotLimit),
-        ))(input)?;
-        Ok((input, PokerType::HoldemNoLimit))
-    }
+fn parse_poker_type(input: &str) -> IResult<&str, PokerType> {
+    alt((
+        map(tag("holdem-no-limit"), |_| PokerType::HoldemNoLimit),
+        map(tag("omaha-pot-limit"), |_| PokerType::OmahaPotLimit),
+        map(tag("omaha5-pot-limit"), |_| PokerType::Omaha5PotLimit),
+    ))(input)
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Level {
-    ante: u32,
-    small_blind: u32,
-    big_blind: u32,
-    seconds: u32,
-    poker_type: PokerType,
+    pub ante: u32,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub seconds: u32,
+    pub poker_type: PokerType,
 }
 
 impl Level {
@@ -45,7 +42,7 @@ impl Level {
             nom::character::complete::u32,
             preceded(tag(":"), nom::character::complete::u32),
             preceded(tag(":"), nom::character::complete::u32),
-            preceded(tag(":"), PokerType::parse),
+            preceded(tag(":"), parse_poker_type),
         ))(input)?;
 
         Ok((
@@ -61,9 +58,10 @@ impl Level {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum TournamentType {
     DoubleOrNothing,
+    Expresso,
     Freeroll100k,
     HitnRun,
     Knockout,
@@ -78,6 +76,7 @@ impl Display for TournamentType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TournamentType::DoubleOrNothing => write!(f, "don"),
+            TournamentType::Expresso => write!(f, "expresso"),
             TournamentType::Freeroll100k => write!(f, "freeroll100k"),
             TournamentType::HitnRun => write!(f, "hitnrun"),
             TournamentType::Knockout => write!(f, "knockout"),
@@ -94,6 +93,7 @@ impl TournamentType {
     fn parse(input: &str) -> IResult<&str, TournamentType> {
         let (input, tournament_type) = alt((
             map(tag("doubleornothing"), |_| TournamentType::DoubleOrNothing),
+            map(tag("expresso"), |_| TournamentType::Expresso),
             map(tag("freeroll100k"), |_| TournamentType::Freeroll100k),
             map(tag("hitnrun"), |_| TournamentType::HitnRun),
             map(tag("knockout"), |_| TournamentType::Knockout),
@@ -109,7 +109,7 @@ impl TournamentType {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct TournamentSummary {
     pub name: String,
     pub hero: String,
@@ -121,22 +121,135 @@ pub struct TournamentSummary {
     pub speed: String,
     pub flight_id: u32,
     pub levels: Vec<Level>,
-    pub prizepool: f64,
+    pub prizepool: Amount,
     pub date: DateTime<Utc>,
     pub play_time: String,
     pub finish_place: u32,
-    pub won: Option<f64>,
+    /// Cash prize, bounties excluded
+    pub won: Option<Amount>,
+    pub expresso: Option<Expresso>,
+    pub rebuy_cost: Option<BuyIn>,
+    pub addon_cost: Option<BuyIn>,
+    pub re_entries: u32,
+    pub rebuys: u32,
+    pub addons: u32,
+    pub bounties_won: Option<Amount>,
+    /// Names of the tickets won
+    pub tickets: Vec<String>,
+}
+
+impl TournamentSummary {
+    /// Number of times the tournament was entered, re-entries included
+    pub fn nb_entries(&self) -> u32 {
+        1 + self.re_entries
+    }
+
+    /// Total cost of the entries, rebuys and add-ons, rake included
+    pub fn total_cost(&self) -> Amount {
+        let rebuy_cost = self.rebuy_cost.as_ref().unwrap_or(&self.buy_in).total();
+        let addon_cost = self.addon_cost.as_ref().unwrap_or(&self.buy_in).total();
+        self.buy_in.total() * self.nb_entries()
+            + rebuy_cost * self.rebuys
+            + addon_cost * self.addons
+    }
+}
+
+/// One of the prizes listed after "You won "
+#[derive(Debug, PartialEq, Clone)]
+enum Prize {
+    Cash(Amount),
+    Bounty(Amount),
+    Ticket(String),
+}
+
+impl Prize {
+    fn parse(input: &str) -> IResult<&str, Prize> {
+        alt((
+            map(preceded(tag("Bounty "), parse_amount), Prize::Bounty),
+            // the name of a ticket runs until the next prize or the end of the line
+            map(
+                preceded(
+                    tag("Ticket "),
+                    recognize(many1_count(preceded(
+                        not(alt((tag(" + "), line_ending))),
+                        anychar,
+                    ))),
+                ),
+                |name: &str| Prize::Ticket(name.to_owned()),
+            ),
+            map(parse_amount, Prize::Cash),
+        ))(input)
+    }
+}
+
+/// Parses "You made 2 re-entries" and "You made 3 rebuys and 1 addons", returning the number
+/// of re-entries, rebuys and add-ons
+fn parse_entries(input: &str) -> IResult<&str, (u32, u32, u32)> {
+    let count = separated_pair(
+        nom::character::complete::u32,
+        char(' '),
+        take_while1(|c: char| c.is_alphabetic() || c == '-'),
+    );
+    let (input, counts) = delimited(
+        tag("You made "),
+        separated_list1(alt((tag(", "), tag(" and "))), count),
+        opt(char('.')),
+    )(input)?;
+    let mut entries = (0, 0, 0);
+    for (n, name) in counts {
+        match name {
+            "re-entry" | "re-entries" => entries.0 += n,
+            "rebuy" | "rebuys" => entries.1 += n,
+            "addon" | "addons" | "add-on" | "add-ons" => entries.2 += n,
+            _ => {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )))
+            }
+        }
+    }
+    Ok((input, entries))
+}
+
+/// Jackpot drawn for an Expresso (spin-and-go) tournament
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Expresso {
+    /// Prizepool as a multiple of the total buy-in, unknown for free tournaments
+    pub multiplier: Option<f64>,
+    pub prize: Amount,
+    /// Number of players, usually 3
+    pub players: u32,
 }
 
-#[derive(Debug, PartialEq)]
+impl Expresso {
+    fn new(buy_in: &BuyIn, prizepool: Amount, players: u32) -> Expresso {
+        let total_buy_in = buy_in.total();
+        Expresso {
+            multiplier: (total_buy_in > Amount::ZERO)
+                .then(|| (prizepool.to_f64() / total_buy_in.to_f64() * 100.0).round() / 100.0),
+            prize: prizepool,
+            players,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct BuyIn {
-    pub buy_in: f64,
-    pub rake: f64,
-    pub bounty: Option<f64>,
+    pub buy_in: Amount,
+    pub rake: Amount,
+    pub bounty: Option<Amount>,
 }
 
-fn parse_amount(input: &str) -> IResult<&str, f64> {
-    let (input, amount) = terminated(double, opt(tag("€")))(input)?;
+impl BuyIn {
+    /// Amount paid, rake and bounty included
+    pub fn total(&self) -> Amount {
+        self.buy_in + self.rake + self.bounty.unwrap_or_default()
+    }
+}
+
+fn parse_amount(input: &str) -> IResult<&str, Amount> {
+    let (input, amount) = terminated(Amount::parse, opt(tag("€")))(input)?;
     Ok((input, amount))
 }
 
@@ -158,13 +271,7 @@ impl BuyIn {
 
 impl TournamentSummary {
     pub fn parse(input: &str) -> IResult<&str, TournamentSummary> {
-        let tournament_start = delimited(
-            tag("Tournament started "),
-            map_res(not_line_ending, |s: &str| {
-                Utc.datetime_from_str(s, "%Y/%m/%d %H:%M:%S %Z")
-            }),
-            line_ending,
-        );
+        let tournament_start = delimited(tag("Tournament started "), parse_datetime, line_ending);
         let (
             input,
             (
@@ -174,6 +281,8 @@ impl TournamentSummary {
                 _,
                 hero,
                 buy_in,
+                rebuy_cost,
+                addon_cost,
                 entries,
                 mode,
                 tournament_type,
@@ -184,7 +293,7 @@ impl TournamentSummary {
                 start,
                 play_time,
                 finish_place,
-                won,
+                prizes,
             ),
         ) = tuple((
             tag("Winamax Poker - Tournament summary : "),
@@ -193,6 +302,8 @@ impl TournamentSummary {
             terminated(take_until("\n"), line_ending),
             delimited(tag("Player : "), take_until("\n"), line_ending),
             delimited(tag("Buy-In : "), BuyIn::parse, line_ending),
+            opt(delimited(tag("Rebuy cost : "), BuyIn::parse, line_ending)),
+            opt(delimited(tag("Addon cost : "), BuyIn::parse, line_ending)),
             delimited(
                 tag("Registered players : "),
                 nom::character::complete::u32,
@@ -211,11 +322,7 @@ impl TournamentSummary {
                 separated_list1(tag(","), Level::parse),
                 tag("]\n"),
             ),
-            delimited(
-                tag("Prizepool : "),
-                terminated(double, opt(tag("€"))),
-                line_ending,
-            ),
+            delimited(tag("Prizepool : "), parse_amount, line_ending),
             tournament_start,
             delimited(tag("You played "), take_until("\n"), line_ending),
             delimited(
@@ -228,8 +335,32 @@ impl TournamentSummary {
                     tag("rd place\n"),
                 )),
             ),
-            opt(preceded(tag("You won "), parse_amount)),
+            opt(preceded(
+                tag("You won "),
+                separated_list1(tag(" + "), Prize::parse),
+            )),
         ))(input)?;
+        let (input, entries_made) = opt(preceded(opt(line_ending), parse_entries))(input)?;
+        let (re_entries, rebuys, addons) = entries_made.unwrap_or_default();
+
+        let mut won = None;
+        let mut bounties_won = None;
+        let mut tickets = vec![];
+        for prize in prizes.unwrap_or_default() {
+            match prize {
+                Prize::Cash(amount) => *won.get_or_insert(Amount::ZERO) += amount,
+                Prize::Bounty(amount) => *bounties_won.get_or_insert(Amount::ZERO) += amount,
+                Prize::Ticket(name) => tickets.push(name),
+            }
+        }
+
+        // Expressos are not always given their own type
+        let tournament_type = match tournament_type {
+            _ if name.starts_with("Expresso") => TournamentType::Expresso,
+            tournament_type => tournament_type,
+        };
+        let expresso = (tournament_type == TournamentType::Expresso)
+            .then(|| Expresso::new(&buy_in, prizepool, entries));
 
         Ok((
             input,
@@ -249,11 +380,26 @@ impl TournamentSummary {
                 play_time: play_time.to_owned(),
                 finish_place,
                 won,
+                expresso,
+                rebuy_cost,
+                addon_cost,
+                re_entries,
+                rebuys,
+                addons,
+                bounties_won,
+                tickets,
             },
         ))
     }
 }
 
+/// Parses a tournament summary file, locating the failure if it cannot be parsed.
+pub fn parse_summary(input: &str) -> Result<TournamentSummary, ParseError> {
+    TournamentSummary::parse(input)
+        .map(|(_, summary)| summary)
+        .map_err(|e| ParseError::new(input, Section::TournamentSummary, None, e))
+}
+
 // add unit tests for TournamentSummary::parse, Level::parse, PokerType::parse, TournamentType::parse
 #[cfg(test)]
 mod tests {
@@ -262,9 +408,17 @@ mod tests {
     #[test]
     fn test_parse_poker_type() {
         assert_eq!(
-            PokerType::parse("holdem-no-limit"),
+            parse_poker_type("holdem-no-limit"),
             Ok(("", PokerType::HoldemNoLimit))
         );
+        assert_eq!(
+            parse_poker_type("omaha-pot-limit"),
+            Ok(("", PokerType::OmahaPotLimit))
+        );
+        assert_eq!(
+            parse_poker_type("omaha5-pot-limit"),
+            Ok(("", PokerType::Omaha5PotLimit))
+        );
     }
 
     #[test]
@@ -291,8 +445,8 @@ mod tests {
         assert_eq!(
             buy_in,
             BuyIn {
-                buy_in: 0.45,
-                rake: 0.05,
+                buy_in: Amount::from_cents(45),
+                rake: Amount::from_cents(5),
                 bounty: None,
             }
         );
@@ -305,9 +459,9 @@ mod tests {
         assert_eq!(
             buy_in,
             BuyIn {
-                buy_in: 0.60,
-                rake: 0.30,
-                bounty: Some(0.10),
+                buy_in: Amount::from_cents(60),
+                rake: Amount::from_cents(30),
+                bounty: Some(Amount::from_cents(10)),
             }
         );
     }
@@ -333,9 +487,9 @@ mod tests {
             id: 669464094,
             hero: String::from("WinterSound"),
             buy_in: BuyIn {
-                buy_in: 0.60,
-                rake: 0.30,
-                bounty: Some(0.10),
+                buy_in: Amount::from_cents(60),
+                rake: Amount::from_cents(30),
+                bounty: Some(Amount::from_cents(10)),
             },
             entries: 160,
             mode: String::from("tt"),
@@ -358,12 +512,127 @@ mod tests {
                     poker_type: PokerType::HoldemNoLimit,
                 },
             ],
-            prizepool: 198.70,
+            prizepool: Amount::from_cents(19870),
             date: Utc.with_ymd_and_hms(2023, 7, 8, 11, 30, 0).unwrap(),
             play_time: String::from("20min 52s "),
             finish_place: 145,
-            won: Some(1.0),
+            won: Some(Amount::from_units(1)),
+            expresso: None,
+            rebuy_cost: None,
+            addon_cost: None,
+            re_entries: 0,
+            rebuys: 0,
+            addons: 0,
+            bounties_won: None,
+            tickets: vec![],
         };
         assert_eq!(tournament_summary, expected);
     }
+
+    #[test]
+    fn test_parse_expresso_summary() {
+        let input = include_str!("../samples/expresso_summary.txt");
+        let (_, summary) = TournamentSummary::parse(input).unwrap();
+        assert_eq!(summary.tournament_type, TournamentType::Expresso);
+        assert_eq!(summary.finish_place, 1);
+        assert_eq!(
+            summary.expresso,
+            Some(Expresso {
+                multiplier: Some(5.0),
+                prize: Amount::from_units(5),
+                players: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_expresso_summary_by_name() {
+        let input = include_str!("../samples/expresso_summary.txt")
+            .replace("Type : expresso", "Type : sitngo")
+            .replace("0.92€ + 0.08€", "0 + 0");
+        let (_, summary) = TournamentSummary::parse(&input).unwrap();
+        assert_eq!(summary.tournament_type, TournamentType::Expresso);
+        assert_eq!(summary.expresso.unwrap().multiplier, None);
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        assert_eq!(parse_entries("You made 2 re-entries"), Ok(("", (2, 0, 0))));
+        assert_eq!(
+            parse_entries("You made 3 rebuys and 1 addons."),
+            Ok(("", (0, 3, 1)))
+        );
+    }
+
+    #[test]
+    fn test_parse_prizes() {
+        assert_eq!(
+            Prize::parse("12.50€"),
+            Ok(("", Prize::Cash(Amount::from_cents(1250))))
+        );
+        assert_eq!(
+            Prize::parse("Bounty 3.75€"),
+            Ok(("", Prize::Bounty(Amount::from_cents(375))))
+        );
+        assert_eq!(
+            Prize::parse("Ticket Freeroll 100K"),
+            Ok(("", Prize::Ticket(String::from("Freeroll 100K"))))
+        );
+        assert_eq!(
+            separated_list1(tag(" + "), Prize::parse)(
+                "Ticket Freeroll 100K + 5€ + Ticket Sunday Special\n"
+            ),
+            Ok((
+                "\n",
+                vec![
+                    Prize::Ticket(String::from("Freeroll 100K")),
+                    Prize::Cash(Amount::from_units(5)),
+                    Prize::Ticket(String::from("Sunday Special")),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_rebuy_summary() {
+        let input = include_str!("../samples/tournament_summary_rebuy.txt");
+        let (_, summary) = TournamentSummary::parse(input).unwrap();
+        assert_eq!(summary.re_entries, 1);
+        assert_eq!(summary.nb_entries(), 2);
+        assert_eq!(summary.rebuys, 2);
+        assert_eq!(summary.addons, 1);
+        assert_eq!(
+            summary.addon_cost,
+            Some(BuyIn {
+                buy_in: Amount::from_cents(450),
+                rake: Amount::from_cents(50),
+                bounty: None,
+            })
+        );
+        assert_eq!(summary.total_cost(), Amount::from_units(2 * 5 + 2 * 5 + 5));
+        assert_eq!(summary.won, Some(Amount::from_cents(1494)));
+        assert_eq!(summary.bounties_won, Some(Amount::from_cents(250)));
+        assert_eq!(summary.tickets, vec![String::from("Freeroll 100K")]);
+    }
+
+    #[test]
+    fn test_parse_summary_error() {
+        let input = include_str!("../samples/tournament_summary.txt")
+            .replace("Registered players : 160", "Registered players : many");
+        let error = parse_summary(&input).unwrap_err();
+        assert_eq!(error.section, Section::TournamentSummary);
+        assert_eq!(error.line, 4);
+        assert_eq!(error.context, "Registered players : many");
+    }
+
+    #[test]
+    fn test_summary_json_round_trip() {
+        let summary =
+            parse_summary(include_str!("../samples/tournament_summary_rebuy.txt")).unwrap();
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TournamentSummary>(&json).unwrap(),
+            summary
+        );
+    }
 }
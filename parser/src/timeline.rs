@@ -0,0 +1,365 @@
+//! Replay of a hand, giving the state of the table before each action
+
+use crate::amount::Amount;
+use crate::parser::{ActionType, Hand, PostType, StreetType};
+
+/// State of the table before an action, players being given by their index in `Hand::seats`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Snapshot {
+    pub street_type: StreetType,
+    /// Indices of the street in `Hand::streets` and of the action among its actions, `None` once
+    /// every action is played
+    pub action: Option<(usize, usize)>,
+    /// Player about to act, `None` when the action is not from a seated player
+    pub player: Option<usize>,
+    /// Chips in the pot, bets of the current street included
+    pub pot: Amount,
+    /// Chips left in front of each player
+    pub stacks: Vec<Amount>,
+    /// Chips put in by each player on the current street, antes excluded
+    pub committed: Vec<Amount>,
+    pub folded: Vec<bool>,
+}
+
+impl Snapshot {
+    /// Largest amount put in by a player on the current street
+    pub fn bet_level(&self) -> Amount {
+        self.committed.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Amount `player` has to put in to call, limited by their stack
+    pub fn to_call(&self, player: usize) -> Amount {
+        (self.bet_level() - self.committed[player]).min(self.stacks[player])
+    }
+
+    /// Stack of `player` that can be won or lost, the largest stack among their opponents
+    /// still in the hand bounding it
+    pub fn effective_stack(&self, player: usize) -> Amount {
+        let opponents = self
+            .stacks
+            .iter()
+            .zip(&self.folded)
+            .enumerate()
+            .filter(|&(index, (_, folded))| index != player && !folded)
+            .map(|(_, (stack, _))| *stack)
+            .max()
+            .unwrap_or_default();
+        self.stacks[player].min(opponents)
+    }
+
+    /// Stack-to-pot ratio of `player`, with their effective stack
+    pub fn spr(&self, player: usize) -> Option<f64> {
+        (self.pot > Amount::ZERO).then(|| self.effective_stack(player).to_f64() / self.pot.to_f64())
+    }
+
+    fn apply(&mut self, player: usize, action: &ActionType) {
+        let put_in = match action {
+            ActionType::Post(PostType::Ante(amount) | PostType::Dead(amount)) => {
+                self.stacks[player] -= *amount;
+                self.pot += *amount;
+                return;
+            }
+            ActionType::Post(PostType::SmallBlind(amount) | PostType::BigBlind(amount))
+            | ActionType::Bet { amount }
+            | ActionType::Call { amount } => *amount,
+            // raises give the amount raised to
+            ActionType::Raise { amount, .. } => *amount - self.committed[player],
+            ActionType::UncalledBet { amount } => -*amount,
+            ActionType::Collect { amount, .. } => {
+                self.stacks[player] += *amount;
+                self.pot -= *amount;
+                return;
+            }
+            ActionType::Fold => {
+                self.folded[player] = true;
+                return;
+            }
+            // cash-outs are settled outside of the pots
+            ActionType::Check
+            | ActionType::Shows { .. }
+            | ActionType::CashoutRisk { .. }
+            | ActionType::Cashout { .. } => return,
+        };
+        self.stacks[player] -= put_in;
+        self.committed[player] += put_in;
+        self.pot += put_in;
+    }
+}
+
+/// States of the table through a hand, from the blinds to the pots being collected
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandTimeline {
+    /// State before each action of each street, followed by the state at the end of the hand
+    pub snapshots: Vec<Snapshot>,
+}
+
+impl HandTimeline {
+    /// Replays the actions of `hand` from the stacks of its seats, its posts included
+    ///
+    /// Hands holding no post at all, such as the ones stored before posts were kept, have them
+    /// guessed by `guess_posts`.
+    pub fn new<S: AsRef<str>>(hand: &Hand<S>) -> HandTimeline {
+        let seat_index = |name: &str| {
+            hand.seats
+                .iter()
+                .position(|seat| seat.player_name.as_ref() == name)
+        };
+        let nb_seats = hand.seats.len();
+        let mut state = Snapshot {
+            street_type: StreetType::Preflop,
+            action: None,
+            player: None,
+            pot: Amount::ZERO,
+            stacks: hand.seats.iter().map(|seat| seat.stack).collect(),
+            committed: vec![Amount::ZERO; nb_seats],
+            folded: vec![false; nb_seats],
+        };
+
+        let lists_posts = hand
+            .streets
+            .iter()
+            .flat_map(|street| street.actions.iter())
+            .any(|action| matches!(action.action, ActionType::Post(_)));
+        if !lists_posts {
+            Self::guess_posts(hand, &mut state);
+        }
+
+        let mut snapshots = vec![];
+        for (street_index, street) in hand.streets.iter().enumerate() {
+            state.street_type = street.street_type.clone();
+            if street.street_type != StreetType::Preflop {
+                state.committed.fill(Amount::ZERO);
+            }
+            for (action_index, action) in street.actions.iter().enumerate() {
+                state.action = Some((street_index, action_index));
+                state.player = seat_index(action.player_name.as_ref());
+                snapshots.push(state.clone());
+                if let Some(player) = state.player {
+                    state.apply(player, &action.action);
+                }
+            }
+        }
+        state.action = None;
+        state.player = None;
+        snapshots.push(state);
+        HandTimeline { snapshots }
+    }
+
+    /// Posts the antes of every seat and the blinds from the blind seats, all-in when a stack
+    /// is too short
+    ///
+    /// This is a fallback for sources that do not list the posts: missed blinds, dead blinds
+    /// and players sitting out cannot be told from the button position.
+    fn guess_posts<S: AsRef<str>>(hand: &Hand<S>, state: &mut Snapshot) {
+        let blinds = &hand.hand_info.blinds;
+        if let Some(ante) = blinds.ante {
+            for player in 0..hand.seats.len() {
+                let ante = ante.min(state.stacks[player]);
+                state.apply(player, &ActionType::Post(PostType::Ante(ante)));
+            }
+        }
+        let (small_blind, big_blind) = hand.blind_seats();
+        for (seat, blind, post) in [
+            (
+                small_blind,
+                blinds.small_blind,
+                PostType::SmallBlind as fn(Amount) -> PostType,
+            ),
+            (big_blind, blinds.big_blind, PostType::BigBlind),
+        ] {
+            let player = seat.and_then(|seat| {
+                hand.seats
+                    .iter()
+                    .position(|other| other.seat_number == seat.seat_number)
+            });
+            if let Some(player) = player {
+                let blind = blind.min(state.stacks[player]);
+                state.apply(player, &ActionType::Post(post(blind)));
+            }
+        }
+    }
+
+    /// State at the end of the hand, once the pots are collected
+    pub fn end(&self) -> &Snapshot {
+        self.snapshots
+            .last()
+            .expect("a timeline ends with the final state")
+    }
+
+    /// Chips put in and collected by each player of `hand`, the hand this timeline replays
+    pub fn settlement<S: AsRef<str>>(&self, hand: &Hand<S>) -> Settlement {
+        let mut collected = vec![Amount::ZERO; hand.seats.len()];
+        let mut cashouts = vec![Amount::ZERO; hand.seats.len()];
+        for snapshot in &self.snapshots {
+            let (Some((street, action)), Some(player)) = (snapshot.action, snapshot.player) else {
+                continue;
+            };
+            match hand.streets[street].actions[action].action {
+                ActionType::Collect { amount, .. } => collected[player] += amount,
+                ActionType::Cashout { amount } => cashouts[player] += amount,
+                ActionType::CashoutRisk { amount } => cashouts[player] -= amount,
+                _ => {}
+            }
+        }
+        let put_in: Vec<Amount> = hand
+            .seats
+            .iter()
+            .zip(&self.end().stacks)
+            .zip(&collected)
+            .map(|((seat, stack), collected)| seat.stack - *stack + *collected)
+            .collect();
+
+        // the part of the largest contribution that no one matched is either collected by its
+        // player or given back without being listed among the actions
+        let mut returned = None;
+        let mut order: Vec<usize> = (0..put_in.len()).collect();
+        order.sort_by_key(|&player| std::cmp::Reverse(put_in[player]));
+        if let [first, second, ..] = order[..] {
+            let unmatched = put_in[first] - put_in[second];
+            let total: Amount = put_in.iter().sum();
+            let collected_total: Amount = collected.iter().sum();
+            let rake = hand.summary.rake.unwrap_or_default();
+            if unmatched > Amount::ZERO && collected_total + rake == total - unmatched {
+                returned = Some((first, unmatched));
+            }
+        }
+        Settlement {
+            put_in,
+            collected,
+            returned,
+            cashouts,
+        }
+    }
+}
+
+/// Chips put in and collected by the players of a hand, by their index in `Hand::seats`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Settlement {
+    /// Chips put in by each player, uncalled bets listed among the actions excluded
+    pub put_in: Vec<Amount>,
+    pub collected: Vec<Amount>,
+    /// Player given back the unmatched part of their bet without it being listed, and the
+    /// amount given back
+    pub returned: Option<(usize, Amount)>,
+    /// Chips received less chips paid by each player cashing out an all-in, outside of the pots
+    pub cashouts: Vec<Amount>,
+}
+
+impl Settlement {
+    /// Chips each player lost in the pot, what was given back excluded
+    pub fn contributions(&self) -> Vec<Amount> {
+        let mut contributions = self.put_in.clone();
+        if let Some((player, amount)) = self.returned {
+            contributions[player] -= amount;
+        }
+        contributions
+    }
+
+    /// Chips won or lost by `player`, cash-outs included
+    pub fn net(&self, player: usize) -> Amount {
+        self.collected[player] - self.contributions()[player] + self.cashouts[player]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_hands;
+
+    #[test]
+    fn test_timeline() {
+        let data = include_str!("../samples/sample1.txt");
+        let hand = &parse_hands(data).unwrap()[0];
+        let timeline = HandTimeline::new(hand);
+        let nb_actions: usize = hand.streets.iter().map(|street| street.actions.len()).sum();
+        assert_eq!(timeline.snapshots.len(), nb_actions + 1);
+
+        // the antes and blinds are posted first
+        assert_eq!(timeline.snapshots[0].pot, Amount::ZERO);
+        assert_eq!(timeline.snapshots[0].player, Some(3));
+
+        // antes and blinds are in, Anonymous 5 is the first to act
+        let first = &timeline.snapshots[8];
+        assert_eq!(first.action, Some((0, 8)));
+        assert_eq!(first.player, Some(5));
+        assert_eq!(first.pot, Amount::from_units(1110));
+        assert_eq!(first.to_call(5), Amount::from_units(500));
+        assert_eq!(first.stacks[3], Amount::from_units(15485));
+        assert_eq!(first.stacks[4], Amount::from_units(19440));
+
+        // WinterSound is first to act on the flop, against Anonymous 1 only
+        let flop = &timeline.snapshots[14];
+        assert_eq!(flop.street_type, StreetType::Flop);
+        assert_eq!(flop.player, Some(4));
+        assert_eq!(flop.pot, Amount::from_units(1610));
+        assert_eq!(flop.bet_level(), Amount::ZERO);
+        assert_eq!(flop.effective_stack(4), Amount::from_units(19440));
+        assert_eq!(flop.spr(4), Some(19440.0 / 1610.0));
+        // WinterSound facing the bet of 805
+        assert_eq!(timeline.snapshots[16].to_call(4), Amount::from_units(805));
+        assert_eq!(timeline.snapshots[16].pot, Amount::from_units(2415));
+
+        let end = timeline.end();
+        assert_eq!(end.action, None);
+        assert_eq!(end.pot, Amount::ZERO);
+        assert_eq!(end.stacks[0], Amount::from_units(24000));
+        assert_eq!(end.stacks[4], Amount::from_units(17025));
+        assert!(end.folded[5] && !end.folded[0]);
+    }
+
+    #[test]
+    fn test_guessed_posts() {
+        let data = include_str!("../samples/sample1.txt");
+        let hand = &parse_hands(data).unwrap()[0];
+        let mut without_posts = hand.clone();
+        without_posts.streets[0]
+            .actions
+            .retain(|action| !matches!(action.action, ActionType::Post(_)));
+        // the antes and blinds are guessed from the button
+        let guessed = HandTimeline::new(&without_posts);
+        let posted = HandTimeline::new(hand);
+        assert_eq!(guessed.snapshots[0].pot, Amount::from_units(1110));
+        assert_eq!(guessed.snapshots[0].stacks, posted.snapshots[8].stacks);
+        assert_eq!(guessed.end(), posted.end());
+
+        // posts are taken as they are, even when the guess would differ
+        let mut missed_blind = hand.clone();
+        missed_blind.streets[0].actions[7].action =
+            ActionType::Post(PostType::BigBlind(Amount::from_units(250)));
+        let timeline = HandTimeline::new(&missed_blind);
+        assert_eq!(timeline.snapshots[8].pot, Amount::from_units(860));
+    }
+
+    #[test]
+    fn test_short_stacks() {
+        let snapshot = Snapshot {
+            street_type: StreetType::Turn,
+            action: Some((2, 1)),
+            player: Some(1),
+            pot: Amount::from_units(300),
+            stacks: vec![
+                Amount::from_units(900),
+                Amount::from_units(50),
+                Amount::from_units(2000),
+            ],
+            committed: vec![Amount::from_units(100), Amount::ZERO, Amount::ZERO],
+            folded: vec![false, false, true],
+        };
+        assert_eq!(snapshot.to_call(1), Amount::from_units(50));
+        assert_eq!(snapshot.to_call(0), Amount::ZERO);
+        // the folded player's stack does not count
+        assert_eq!(snapshot.effective_stack(0), Amount::from_units(50));
+        assert_eq!(snapshot.spr(1), Some(50.0 / 300.0));
+    }
+
+    #[test]
+    fn test_uncalled_bets_given_back() {
+        let data = include_str!("../samples/pokerstars.txt");
+        for hand in parse_hands(data).unwrap() {
+            let timeline = HandTimeline::new(&hand);
+            let end = timeline.end();
+            // only the rake is left in the pot
+            assert_eq!(end.pot, hand.summary.rake.unwrap_or_default());
+        }
+    }
+}
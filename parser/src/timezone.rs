@@ -0,0 +1,201 @@
+//! Dates of hand histories and tournament summaries, printed in the timezone of the site
+
+use chrono::prelude::*;
+use chrono::LocalResult;
+use chrono_tz::Tz;
+use nom::bytes::complete::{take, take_while1};
+use nom::character::complete::char;
+use nom::combinator::{map_res, opt};
+use nom::sequence::{pair, preceded};
+use nom::IResult;
+
+/// Timezone named by a label such as "CET" or "ET", daylight saving time included
+///
+/// Sites print the same label all year round, "CET" standing for Paris time in summer too.
+pub fn zone_for_label(label: &str) -> Option<Tz> {
+    let zone = match label {
+        "UTC" | "GMT" | "Z" => Tz::UTC,
+        "WET" | "WEST" => Tz::Europe__Lisbon,
+        "CET" | "CEST" => Tz::Europe__Paris,
+        "EET" | "EEST" => Tz::Europe__Athens,
+        "MSK" => Tz::Europe__Moscow,
+        "ET" | "EST" | "EDT" => Tz::America__New_York,
+        "CT" | "CST" | "CDT" => Tz::America__Chicago,
+        "MT" | "MST" | "MDT" => Tz::America__Denver,
+        "PT" | "PST" | "PDT" => Tz::America__Los_Angeles,
+        "BRT" | "BRST" => Tz::America__Sao_Paulo,
+        "AET" | "AEST" | "AEDT" => Tz::Australia__Sydney,
+        _ => return None,
+    };
+    Some(zone)
+}
+
+/// Offset named by a label such as "EDT", generic labels such as "ET" naming none
+///
+/// "CET" and "CEST" name none either, sites printing "CET" for Paris time all year round.
+pub fn offset_for_label(label: &str) -> Option<FixedOffset> {
+    let hours = match label {
+        "UTC" | "GMT" | "Z" | "WET" => 0,
+        "WEST" => 1,
+        "EET" => 2,
+        "EEST" | "MSK" => 3,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        "BRT" => -3,
+        "BRST" => -2,
+        "AEST" => 10,
+        "AEDT" => 11,
+        _ => return None,
+    };
+    FixedOffset::east_opt(hours * 3600)
+}
+
+/// Instant at which a wall clock of `zone` showed `datetime`
+///
+/// When clocks are turned back, the repeated hour is taken as the first one. Times skipped
+/// when clocks are turned forward do not exist and give `None`.
+pub fn to_utc(datetime: NaiveDateTime, zone: Tz) -> Option<DateTime<Utc>> {
+    zone.from_local_datetime(&datetime)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+/// Instant at which a wall clock of `zone` labelled `offset` showed `datetime`
+///
+/// The offset named by the label settles the hour repeated or skipped when clocks change,
+/// "01:30 EST" being the second 01:30 in New York. Without an offset, the hour is read as by
+/// [`to_utc`].
+pub fn labelled_to_utc(
+    datetime: NaiveDateTime,
+    zone: Tz,
+    offset: Option<FixedOffset>,
+) -> Option<DateTime<Utc>> {
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return to_utc(datetime, zone),
+    };
+    let labelled = match zone.from_local_datetime(&datetime) {
+        LocalResult::Single(datetime) => return Some(datetime.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, latest) => [earliest, latest]
+            .into_iter()
+            .find(|datetime| datetime.offset().fix() == offset)
+            .map(|datetime| datetime.with_timezone(&Utc)),
+        LocalResult::None => None,
+    };
+    labelled.or_else(|| {
+        offset
+            .from_local_datetime(&datetime)
+            .single()
+            .map(|datetime| datetime.with_timezone(&Utc))
+    })
+}
+
+/// Parses "2023/05/21 21:49:44 CET", dates without a label being in UTC
+pub(crate) fn parse_datetime(input: &str) -> IResult<&str, DateTime<Utc>> {
+    map_res(
+        pair(
+            map_res(take(19usize), |s: &str| {
+                NaiveDateTime::parse_from_str(s, "%Y/%m/%d %H:%M:%S")
+            }),
+            opt(preceded(
+                char(' '),
+                take_while1(|c: char| c.is_ascii_uppercase()),
+            )),
+        ),
+        |(datetime, label)| {
+            let (zone, offset) = match label {
+                Some(label) => (
+                    zone_for_label(label).ok_or("unknown timezone")?,
+                    offset_for_label(label),
+                ),
+                None => (Tz::UTC, None),
+            };
+            labelled_to_utc(datetime, zone, offset).ok_or("nonexistent local time")
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime() {
+        assert_eq!(
+            parse_datetime("2023/05/21 19:49:44 UTC\n"),
+            Ok(("\n", Utc.with_ymd_and_hms(2023, 5, 21, 19, 49, 44).unwrap()))
+        );
+        assert_eq!(
+            parse_datetime("2023/05/21 22:10:05"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 5, 21, 22, 10, 5).unwrap()))
+        );
+        // summer time in Paris and New York
+        assert_eq!(
+            parse_datetime("2023/05/21 21:49:44 CET [2023/05/21 15:49:44 ET]"),
+            Ok((
+                " [2023/05/21 15:49:44 ET]",
+                Utc.with_ymd_and_hms(2023, 5, 21, 19, 49, 44).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_datetime("2023/01/21 15:49:44 ET"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 1, 21, 20, 49, 44).unwrap()))
+        );
+        assert!(parse_datetime("2023/05/21 19:49:44 XYZ").is_err());
+    }
+
+    #[test]
+    fn test_daylight_saving_time_changes() {
+        let local = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+        // clocks went back from 3:00 to 2:00 in Paris
+        assert_eq!(
+            to_utc(local("2023-10-29 02:30:00"), Tz::Europe__Paris),
+            Some(Utc.with_ymd_and_hms(2023, 10, 29, 0, 30, 0).unwrap())
+        );
+        assert_eq!(
+            to_utc(local("2023-10-29 03:30:00"), Tz::Europe__Paris),
+            Some(Utc.with_ymd_and_hms(2023, 10, 29, 2, 30, 0).unwrap())
+        );
+        // and forward from 2:00 to 3:00
+        assert_eq!(
+            to_utc(local("2023-03-26 02:30:00"), Tz::Europe__Paris),
+            None
+        );
+    }
+
+    #[test]
+    fn test_labelled_daylight_saving_time_changes() {
+        // the repeated hour is told apart by the label
+        assert_eq!(
+            parse_datetime("2023/11/05 01:30:00 EDT"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 11, 5, 5, 30, 0).unwrap()))
+        );
+        assert_eq!(
+            parse_datetime("2023/11/05 01:30:00 EST"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 11, 5, 6, 30, 0).unwrap()))
+        );
+        // a generic label keeps the first one, "CET" included since it is printed in summer too
+        assert_eq!(
+            parse_datetime("2023/11/05 01:30:00 ET"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 11, 5, 5, 30, 0).unwrap()))
+        );
+        assert_eq!(
+            parse_datetime("2023/10/29 02:30:00 CET"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 10, 29, 0, 30, 0).unwrap()))
+        );
+        assert_eq!(
+            parse_datetime("2023/10/29 02:30:00 CEST"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 10, 29, 0, 30, 0).unwrap()))
+        );
+        // the skipped hour is read at the labelled offset
+        assert_eq!(
+            parse_datetime("2023/03/12 02:30:00 EST"),
+            Ok(("", Utc.with_ymd_and_hms(2023, 3, 12, 7, 30, 0).unwrap()))
+        );
+        assert!(parse_datetime("2023/03/12 02:30:00 ET").is_err());
+        assert!(parse_datetime("2023/03/26 02:30:00 CET").is_err());
+    }
+}
@@ -0,0 +1,431 @@
+//! Accounting checks of parsed hands, catching hands that parse but do not add up
+
+use std::fmt;
+
+use crate::amount::Amount;
+use crate::evaluator::best_hand;
+use crate::parser::{ActionType, Card, Hand, StreetType, SummaryResult};
+use crate::timeline::HandTimeline;
+
+/// Inconsistency between the stacks, actions and summary of a hand
+#[derive(Debug, PartialEq, Clone)]
+pub enum Discrepancy {
+    /// Action of a player who is not seated at the table
+    UnknownPlayer { player: String },
+    /// Player putting more chips in the pot than their stack
+    OverStack {
+        player: String,
+        stack: Amount,
+        put_in: Amount,
+    },
+    /// Call of another amount than the one left to call, without being all-in
+    WrongCall {
+        street: StreetType,
+        player: String,
+        amount: Amount,
+        expected: Amount,
+    },
+    /// Raise whose increment and total do not match the bet it raises
+    WrongRaise {
+        street: StreetType,
+        player: String,
+        raise: Amount,
+        to: Amount,
+        expected_to: Amount,
+    },
+    /// Total pot of the summary differing from the chips put in by the players, with or
+    /// without the rake
+    PotMismatch { summary: Amount, put_in: Amount },
+    /// Collected chips and rake not adding up to the chips put in by the players
+    CollectedMismatch {
+        put_in: Amount,
+        rake: Amount,
+        collected: Amount,
+    },
+    /// Amount won in the summary differing from the chips collected by the player
+    ResultMismatch {
+        player: String,
+        summary: Amount,
+        collected: Amount,
+    },
+    /// Player winning at showdown against a better hand that put in as many chips
+    WrongWinner { player: String, beaten_by: String },
+    /// Best hand at showdown not winning anything
+    MissingWinner { player: String },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::UnknownPlayer { player } => {
+                write!(f, "{} acts without being seated", player)
+            }
+            Discrepancy::OverStack {
+                player,
+                stack,
+                put_in,
+            } => write!(f, "{} puts in {} with a stack of {}", player, put_in, stack),
+            Discrepancy::WrongCall {
+                street,
+                player,
+                amount,
+                expected,
+            } => write!(
+                f,
+                "{} calls {} on the {} instead of {}",
+                player, amount, street, expected
+            ),
+            Discrepancy::WrongRaise {
+                street,
+                player,
+                raise,
+                to,
+                expected_to,
+            } => write!(
+                f,
+                "{} raises {} to {} on the {} instead of to {}",
+                player, raise, to, street, expected_to
+            ),
+            Discrepancy::PotMismatch { summary, put_in } => write!(
+                f,
+                "total pot of {} while the players put in {}",
+                summary, put_in
+            ),
+            Discrepancy::CollectedMismatch {
+                put_in,
+                rake,
+                collected,
+            } => write!(
+                f,
+                "{} collected with a rake of {} while the players put in {}",
+                collected, rake, put_in
+            ),
+            Discrepancy::ResultMismatch {
+                player,
+                summary,
+                collected,
+            } => write!(
+                f,
+                "{} wins {} in the summary but collects {}",
+                player, summary, collected
+            ),
+            Discrepancy::WrongWinner { player, beaten_by } => write!(
+                f,
+                "{} wins at showdown against the better hand of {}",
+                player, beaten_by
+            ),
+            Discrepancy::MissingWinner { player } => {
+                write!(
+                    f,
+                    "{} has the best hand at showdown but does not win",
+                    player
+                )
+            }
+        }
+    }
+}
+
+impl<S: AsRef<str>> Hand<S> {
+    /// Checks that the chips put in by the players, the pot and the amounts collected add up,
+    /// and that the best hands at showdown win.
+    ///
+    /// The hand is replayed by a `HandTimeline`, which posts the blinds from the blind seats
+    /// when they are not listed among the actions.
+    pub fn validate(&self) -> Vec<Discrepancy> {
+        let mut discrepancies = vec![];
+        let timeline = HandTimeline::new(self);
+        let mut lowest_stacks: Vec<Amount> = self.seats.iter().map(|seat| seat.stack).collect();
+
+        for snapshot in &timeline.snapshots {
+            for (lowest, stack) in lowest_stacks.iter_mut().zip(&snapshot.stacks) {
+                *lowest = (*lowest).min(*stack);
+            }
+            let Some((street_index, action_index)) = snapshot.action else {
+                continue;
+            };
+            let action = &self.streets[street_index].actions[action_index];
+            let player = action.player_name.as_ref();
+            let Some(index) = snapshot.player else {
+                discrepancies.push(Discrepancy::UnknownPlayer {
+                    player: player.to_owned(),
+                });
+                continue;
+            };
+            let level = snapshot.bet_level();
+            match &action.action {
+                ActionType::Call { amount } => {
+                    let expected = level - snapshot.committed[index];
+                    let all_in = action.is_all_in || *amount == snapshot.stacks[index];
+                    if *amount != expected && !(all_in && *amount < expected) {
+                        discrepancies.push(Discrepancy::WrongCall {
+                            street: snapshot.street_type.clone(),
+                            player: player.to_owned(),
+                            amount: *amount,
+                            expected,
+                        });
+                    }
+                }
+                ActionType::Raise { to_call, amount } if *amount != level + *to_call => {
+                    discrepancies.push(Discrepancy::WrongRaise {
+                        street: snapshot.street_type.clone(),
+                        player: player.to_owned(),
+                        raise: *to_call,
+                        to: *amount,
+                        expected_to: level + *to_call,
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        for (seat, lowest) in self.seats.iter().zip(&lowest_stacks) {
+            if *lowest < Amount::ZERO {
+                discrepancies.push(Discrepancy::OverStack {
+                    player: seat.player_name.as_ref().to_owned(),
+                    stack: seat.stack,
+                    put_in: seat.stack - *lowest,
+                });
+            }
+        }
+
+        // chips lost by each player, bets given back excluded
+        let settlement = timeline.settlement(self);
+        let contributions = settlement.contributions();
+        let put_in: Amount = contributions.iter().sum();
+        let collected = &settlement.collected;
+        let summary = &self.summary;
+        let rake = summary.rake.unwrap_or_default();
+        let collected_total: Amount = collected.iter().sum();
+
+        // some sites count the rake in the total pot, others take it out first
+        if summary.pot != put_in && summary.pot + rake != put_in {
+            discrepancies.push(Discrepancy::PotMismatch {
+                summary: summary.pot,
+                put_in,
+            });
+        }
+        if collected_total + rake != put_in {
+            discrepancies.push(Discrepancy::CollectedMismatch {
+                put_in,
+                rake,
+                collected: collected_total,
+            });
+        }
+
+        for player in &summary.players {
+            let won = match player.result {
+                SummaryResult::Won(amount) => amount,
+                SummaryResult::Lost => Amount::ZERO,
+            };
+            let collected = self
+                .seats
+                .iter()
+                .position(|seat| seat.player_name.as_ref() == player.name.as_ref())
+                .map(|index| collected[index])
+                .unwrap_or_default();
+            if won != collected {
+                discrepancies.push(Discrepancy::ResultMismatch {
+                    player: player.name.as_ref().to_owned(),
+                    summary: won,
+                    collected,
+                });
+            }
+        }
+
+        discrepancies.extend(self.check_showdown(&timeline.end().folded, &contributions));
+        discrepancies
+    }
+
+    /// Checks the winners of the summary against the hands of the players still in at the end,
+    /// `contributions` giving the chips each seat put in
+    ///
+    /// A player may win with a worse hand than someone who put in fewer chips, through a side
+    /// pot. Boards run more than once are not checked.
+    fn check_showdown(&self, folded: &[bool], contributions: &[Amount]) -> Vec<Discrepancy> {
+        let mut discrepancies = vec![];
+        if self.summary.boards.len() > 1 {
+            return discrepancies;
+        }
+        let board: Vec<Card> = self
+            .board_at(self.streets.len().saturating_sub(1))
+            .into_iter()
+            .cloned()
+            .collect();
+        let poker_type = &self.hand_info.poker_type;
+        // name, hand value, chips put in and whether the player wins, for each shown hand
+        let showdown: Vec<_> = self
+            .summary
+            .players
+            .iter()
+            .filter_map(|player| {
+                let index = self
+                    .seats
+                    .iter()
+                    .position(|seat| seat.player_name.as_ref() == player.name.as_ref())?;
+                if folded[index] {
+                    return None;
+                }
+                let value = best_hand(poker_type, &player.hole_cards.as_ref()?.cards, &board)?;
+                let won = matches!(player.result, SummaryResult::Won(_));
+                Some((player.name.as_ref(), value, contributions[index], won))
+            })
+            .collect();
+        if showdown.len() < 2 {
+            return discrepancies;
+        }
+
+        for &(player, value, put_in, won) in &showdown {
+            if !won {
+                continue;
+            }
+            let better = showdown
+                .iter()
+                .find(|other| other.1 > value && other.2 >= put_in);
+            if let Some(&(beaten_by, ..)) = better {
+                discrepancies.push(Discrepancy::WrongWinner {
+                    player: player.to_owned(),
+                    beaten_by: beaten_by.to_owned(),
+                });
+            }
+        }
+        let best = showdown.iter().map(|player| player.1).max();
+        for &(player, value, _, won) in &showdown {
+            if Some(value) == best && !won {
+                discrepancies.push(Discrepancy::MissingWinner {
+                    player: player.to_owned(),
+                });
+            }
+        }
+        discrepancies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_hands, parse_hands_borrowed};
+
+    fn sample_hands(data: &str) -> Vec<Hand> {
+        parse_hands(data).unwrap()
+    }
+
+    #[test]
+    fn test_validate_samples() {
+        for data in [
+            include_str!("../samples/sample1.txt"),
+            include_str!("../samples/sample2.txt"),
+            include_str!("../samples/sample_cash_play_money.txt"),
+            include_str!("../samples/sample_expresso_play_money.txt"),
+            include_str!("../samples/sample_omaha.txt"),
+            include_str!("../samples/pokerstars.txt"),
+            include_str!("../samples/ggpoker.txt"),
+        ] {
+            for hand in sample_hands(data) {
+                assert_eq!(hand.validate(), vec![], "hand {}", hand.hand_info.hand_id);
+            }
+        }
+        for hand in parse_hands_borrowed(include_str!("../samples/sample1.txt")).unwrap() {
+            assert_eq!(hand.validate(), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_validate_misread_raise() {
+        let mut hand = sample_hands(include_str!("../samples/sample_omaha.txt")).remove(0);
+        // "WinterSound raises 0.25€ to 0.35€" read as a raise to 0.45€
+        hand.streets[0].actions[3].action = ActionType::Raise {
+            to_call: Amount::from_cents(25),
+            amount: Amount::from_cents(45),
+        };
+        let discrepancies = hand.validate();
+        assert_eq!(
+            discrepancies[0],
+            Discrepancy::WrongRaise {
+                street: StreetType::Preflop,
+                player: String::from("WinterSound"),
+                raise: Amount::from_cents(25),
+                to: Amount::from_cents(45),
+                expected_to: Amount::from_cents(35),
+            }
+        );
+        assert_eq!(
+            discrepancies[0].to_string(),
+            "WinterSound raises 0.25 to 0.45 on the preflop instead of to 0.35"
+        );
+        // the call that follows no longer matches
+        assert_eq!(
+            discrepancies[1..],
+            [Discrepancy::WrongCall {
+                street: StreetType::Preflop,
+                player: String::from("Anonymous 2"),
+                amount: Amount::from_cents(25),
+                expected: Amount::from_cents(35),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_summary() {
+        let mut hand = sample_hands(include_str!("../samples/sample1.txt")).remove(0);
+        hand.summary.pot = Amount::from_units(6000);
+        hand.summary.players[0].result = SummaryResult::Won(Amount::from_units(6000));
+        assert_eq!(
+            hand.validate(),
+            vec![
+                Discrepancy::PotMismatch {
+                    summary: Amount::from_units(6000),
+                    put_in: Amount::from_units(6440),
+                },
+                Discrepancy::ResultMismatch {
+                    player: String::from("Anonymous 1"),
+                    summary: Amount::from_units(6000),
+                    collected: Amount::from_units(6440),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_stacks() {
+        let mut hand = sample_hands(include_str!("../samples/sample1.txt")).remove(0);
+        hand.seats[4].stack = Amount::from_units(2000);
+        assert_eq!(
+            hand.validate(),
+            vec![Discrepancy::OverStack {
+                player: String::from("WinterSound"),
+                stack: Amount::from_units(2000),
+                put_in: Amount::from_units(2975),
+            }]
+        );
+        hand.streets[1].actions[0].player_name = String::from("Nobody");
+        assert!(hand.validate().contains(&Discrepancy::UnknownPlayer {
+            player: String::from("Nobody")
+        }));
+    }
+
+    #[test]
+    fn test_validate_showdown() {
+        let mut hand = sample_hands(include_str!("../samples/sample1.txt")).remove(0);
+        // two pairs of Queens and Tens recorded as beating Aces and Tens
+        let pot = Amount::from_units(6440);
+        for player in &mut hand.summary.players {
+            player.result = match player.name.as_str() {
+                "WinterSound" => SummaryResult::Won(pot),
+                _ => SummaryResult::Lost,
+            };
+        }
+        let discrepancies = hand.validate();
+        let wrong_winner = Discrepancy::WrongWinner {
+            player: String::from("WinterSound"),
+            beaten_by: String::from("Anonymous 1"),
+        };
+        assert!(discrepancies.contains(&wrong_winner));
+        assert!(discrepancies.contains(&Discrepancy::MissingWinner {
+            player: String::from("Anonymous 1")
+        }));
+        assert_eq!(
+            wrong_winner.to_string(),
+            "WinterSound wins at showdown against the better hand of Anonymous 1"
+        );
+    }
+}
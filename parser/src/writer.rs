@@ -0,0 +1,1672 @@
+use crate::amount::Amount;
+use crate::parser::{
+    Action, ActionType, Board, GameInfo, Hand, HandCategory, MoneyType, PokerType, PostType, Pot,
+    Rank, Seat, Street, StreetType, SummaryPlayer, SummaryResult, TableName,
+};
+use crate::summary_parser::{BuyIn, TournamentSummary, TournamentType};
+
+/// Hand history writer for a poker site, the reverse of its `SiteParser`
+pub trait SiteWriter {
+    /// Writes a single hand, ending with a line break
+    fn write_hand(&self, hand: &Hand) -> String;
+
+    /// Writes a hand history file, separating the hands with a blank line
+    fn write_hands(&self, hands: &[Hand]) -> String {
+        hands
+            .iter()
+            .map(|hand| self.write_hand(hand))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Writer for Winamax hand histories and tournament summaries
+///
+/// Parsing a written hand gives back the same `Hand`. What the hand does not hold is rebuilt
+/// when possible: hands without posts have the blinds posted by the players following the
+/// button, and shown hands are described with the category of their summary line.
+pub struct WinamaxWriter;
+
+impl SiteWriter for WinamaxWriter {
+    fn write_hand(&self, hand: &Hand) -> String {
+        let currency = currency(hand);
+        let mut lines = vec![
+            write_header(hand),
+            format!(
+                "Table: {} {}-max ({}) Seat #{} is the button",
+                write_table_name(&hand.table_info.table_name),
+                hand.table_info.max_players,
+                match hand.table_info.currency {
+                    MoneyType::RealMoney => "real money",
+                    MoneyType::PlayMoney => "play money",
+                },
+                hand.table_info.button
+            ),
+        ];
+        let bounty_currency = money(&hand.table_info.currency);
+        for seat in &hand.seats {
+            let bounty = seat
+                .bounty
+                .map(|bounty| format!(", {}{} bounty", bounty, bounty_currency))
+                .unwrap_or_default();
+            lines.push(format!(
+                "Seat {}: {} ({}{}{})",
+                seat.seat_number, seat.player_name, seat.stack, currency, bounty
+            ));
+        }
+        lines.push(String::from("*** ANTE/BLINDS ***"));
+        let posted = hand.streets.iter().any(|street| {
+            street
+                .actions
+                .iter()
+                .any(|action| matches!(action.action, ActionType::Post(_)))
+        });
+        // the posts opening the pre-flop street are written in their own section
+        let leading_posts = match hand.streets.first() {
+            Some(street) if street.street_type == StreetType::Preflop => street
+                .actions
+                .iter()
+                .take_while(|action| matches!(action.action, ActionType::Post(_)))
+                .count(),
+            _ => 0,
+        };
+        if posted {
+            lines.extend(
+                hand.streets
+                    .iter()
+                    .flat_map(|street| street.actions.iter())
+                    .take(leading_posts)
+                    .filter_map(|action| write_action(hand, action, false, currency)),
+            );
+        } else {
+            lines.extend(write_blind_posts(hand, currency));
+        }
+        lines.push(format!(
+            "Dealt to {} [{}]",
+            hand.dealt_cards.player_name,
+            join_cards(hand.dealt_cards.hole_cards.cards.iter())
+        ));
+        for (index, street) in hand.streets.iter().enumerate() {
+            lines.push(write_street_header(hand, index, street));
+            let side_pots = street.actions.iter().any(|action| {
+                matches!(
+                    action.action,
+                    ActionType::Collect {
+                        pot: Pot::Side(_),
+                        ..
+                    }
+                )
+            });
+            let skipped = if index == 0 { leading_posts } else { 0 };
+            lines.extend(
+                street
+                    .actions
+                    .iter()
+                    .skip(skipped)
+                    .filter_map(|action| write_action(hand, action, side_pots, currency)),
+            );
+        }
+        lines.push(String::from("*** SUMMARY ***"));
+        lines.push(format!(
+            "Total pot {}{} | {}",
+            hand.summary.pot,
+            currency,
+            match hand.summary.rake {
+                Some(rake) => format!("Rake {}{}", rake, currency),
+                None => String::from("No rake"),
+            }
+        ));
+        for board in &hand.summary.boards {
+            lines.push(format!(
+                "Board: [{}]",
+                join_cards(board.cards.iter().flatten())
+            ));
+        }
+        for player in &hand.summary.players {
+            lines.push(write_summary_player(hand, player, currency));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+impl WinamaxWriter {
+    /// Writes a tournament summary file
+    pub fn write_summary(&self, summary: &TournamentSummary) -> String {
+        let mut lines = vec![
+            format!(
+                "Winamax Poker - Tournament summary : {}({})",
+                summary.name, summary.id
+            ),
+            format!("Player : {}", summary.hero),
+            format!("Buy-In : {}", write_buy_in(&summary.buy_in)),
+        ];
+        if let Some(rebuy_cost) = &summary.rebuy_cost {
+            lines.push(format!("Rebuy cost : {}", write_buy_in(rebuy_cost)));
+        }
+        if let Some(addon_cost) = &summary.addon_cost {
+            lines.push(format!("Addon cost : {}", write_buy_in(addon_cost)));
+        }
+        let levels: Vec<String> = summary
+            .levels
+            .iter()
+            .map(|level| {
+                format!(
+                    "{}-{}:{}:{}:{}",
+                    level.small_blind, level.big_blind, level.ante, level.seconds, level.poker_type
+                )
+            })
+            .collect();
+        lines.extend([
+            format!("Registered players : {}", summary.entries),
+            format!("Mode : {}", summary.mode),
+            format!("Type : {}", write_tournament_type(&summary.tournament_type)),
+            format!("Speed : {}", summary.speed),
+            format!("Flight ID : {}", summary.flight_id),
+            format!("Levels : Levels : [{}]", levels.join(",")),
+            format!("Prizepool : {}€", summary.prizepool),
+            format!(
+                "Tournament started {}",
+                summary.date.format("%Y/%m/%d %H:%M:%S UTC")
+            ),
+            format!("You played {}", summary.play_time),
+            format!(
+                "You finished in {}{} place",
+                summary.finish_place,
+                ordinal_suffix(summary.finish_place)
+            ),
+        ]);
+        let prizes: Vec<String> = summary
+            .won
+            .map(|won| format!("{}€", won))
+            .into_iter()
+            .chain(
+                summary
+                    .bounties_won
+                    .map(|bounties| format!("Bounty {}€", bounties)),
+            )
+            .chain(
+                summary
+                    .tickets
+                    .iter()
+                    .map(|name| format!("Ticket {}", name)),
+            )
+            .collect();
+        if !prizes.is_empty() {
+            lines.push(format!("You won {}", prizes.join(" + ")));
+        }
+        let entries: Vec<String> = [
+            (summary.re_entries, "re-entry", "re-entries"),
+            (summary.rebuys, "rebuy", "rebuys"),
+            (summary.addons, "addon", "addons"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| format!("{} {}", count, if count == 1 { one } else { many }))
+        .collect();
+        if let Some((last, first)) = entries.split_last() {
+            let entries = match first.is_empty() {
+                true => last.to_owned(),
+                false => format!("{} and {}", first.join(", "), last),
+            };
+            lines.push(format!("You made {}.", entries));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Writer for PokerStars hand histories, read by most third-party poker tools
+///
+/// Amounts are written in the currency of the hand and tournament levels in roman numerals.
+/// Hand ids are 64-bit numbers on PokerStars, so Winamax ids are replaced by a hash of them, and
+/// tournament tables are named after the tournament id and table number. Hold-up hands are
+/// written as Zoom hands.
+pub struct PokerStarsWriter;
+
+impl SiteWriter for PokerStarsWriter {
+    fn write_hand(&self, hand: &Hand) -> String {
+        let currency = Self::currency(hand);
+        let table_name = match &hand.table_info.table_name {
+            TableName::Tournament(_, tournament_id, table_id) => {
+                format!("{} {}", tournament_id, table_id)
+            }
+            TableName::CashGame(name) => name.to_owned(),
+        };
+        let mut lines = vec![
+            Self::header(hand),
+            format!(
+                "Table '{}' {}-max {}Seat #{} is the button",
+                table_name,
+                hand.table_info.max_players,
+                match hand.table_info.currency {
+                    MoneyType::RealMoney => "",
+                    MoneyType::PlayMoney => "(Play Money) ",
+                },
+                hand.table_info.button
+            ),
+        ];
+        let bounty_currency = Self::sign(hand);
+        for seat in &hand.seats {
+            let bounty = seat
+                .bounty
+                .map(|bounty| format!(", {}{} bounty", bounty_currency, bounty))
+                .unwrap_or_default();
+            lines.push(format!(
+                "Seat {}: {} ({}{} in chips{})",
+                seat.seat_number, seat.player_name, currency, seat.stack, bounty
+            ));
+        }
+        lines.extend(Self::posts(hand, currency));
+        lines.push(String::from("*** HOLE CARDS ***"));
+        lines.push(format!(
+            "Dealt to {} [{}]",
+            hand.dealt_cards.player_name,
+            join_cards(hand.dealt_cards.hole_cards.cards.iter())
+        ));
+        for (index, street) in hand.streets.iter().enumerate() {
+            // pre-flop actions follow the hole cards
+            if street.street_type != StreetType::Preflop {
+                lines.push(Self::street_header(hand, index, street));
+            }
+            let side_pots = street.actions.iter().any(|action| {
+                matches!(
+                    action.action,
+                    ActionType::Collect {
+                        pot: Pot::Side(_),
+                        ..
+                    }
+                )
+            });
+            lines.extend(
+                street
+                    .actions
+                    .iter()
+                    .filter(|action| !matches!(action.action, ActionType::Post(_)))
+                    .map(|action| Self::action(hand, action, side_pots, currency)),
+            );
+        }
+        lines.push(String::from("*** SUMMARY ***"));
+        lines.push(format!(
+            "Total pot {}{} | Rake {}{}",
+            currency,
+            hand.summary.pot,
+            currency,
+            hand.summary.rake.unwrap_or_default()
+        ));
+        let board = |board: &Board| join_cards(board.cards.iter().flatten());
+        match hand.summary.boards.as_slice() {
+            [] => {}
+            [single] => lines.push(format!("Board [{}]", board(single))),
+            [first, second, ..] => lines.extend([
+                String::from("Hand was run twice"),
+                format!("FIRST Board [{}]", board(first)),
+                format!("SECOND Board [{}]", board(second)),
+            ]),
+        }
+        for player in &hand.summary.players {
+            lines.push(Self::summary_player(hand, player, currency));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Writes a hand history file, separating the hands with three blank lines as PokerStars
+    fn write_hands(&self, hands: &[Hand]) -> String {
+        hands
+            .iter()
+            .map(|hand| self.write_hand(hand))
+            .collect::<Vec<String>>()
+            .join("\n\n\n")
+    }
+}
+
+impl PokerStarsWriter {
+    /// Sign written before the real money amounts of `hand`, empty for play money and for the
+    /// currencies without one
+    fn sign(hand: &Hand) -> &'static str {
+        match (
+            &hand.table_info.currency,
+            hand.hand_info.currency_code.as_str(),
+        ) {
+            (MoneyType::PlayMoney, _) => "",
+            (_, "EUR") => "€",
+            (_, "USD") => "$",
+            (_, "GBP") => "£",
+            _ => "",
+        }
+    }
+
+    /// Sign written before the chips in play, which are money in cash games only
+    fn currency(hand: &Hand) -> &'static str {
+        match hand.hand_info.game_info {
+            GameInfo::Tournament(_) => "",
+            _ => Self::sign(hand),
+        }
+    }
+
+    fn header(hand: &Hand) -> String {
+        let info = &hand.hand_info;
+        let currency = Self::currency(hand);
+        let poker_type = match info.poker_type {
+            PokerType::HoldemNoLimit => "Hold'em No Limit",
+            PokerType::OmahaPotLimit => "Omaha Pot Limit",
+            PokerType::Omaha5PotLimit => "5 Card Omaha Pot Limit",
+        };
+        let blinds = format!(
+            "({}{}/{}{}{})",
+            currency,
+            info.blinds.small_blind,
+            currency,
+            info.blinds.big_blind,
+            match (&info.game_info, &hand.table_info.currency) {
+                (GameInfo::Tournament(_), _) | (_, MoneyType::PlayMoney) => String::new(),
+                _ => format!(" {}", info.currency_code),
+            }
+        );
+        let game = match &info.game_info {
+            GameInfo::Tournament(tournament) => {
+                let tournament_id = match &hand.table_info.table_name {
+                    TableName::Tournament(_, tournament_id, _) => *tournament_id,
+                    TableName::CashGame(_) => tournament.name.parse().unwrap_or_default(),
+                };
+                let money = Self::sign(hand);
+                let buy_in = if tournament.buy_in == Amount::ZERO && tournament.rake == Amount::ZERO
+                {
+                    String::from("Freeroll")
+                } else if hand.table_info.currency == MoneyType::PlayMoney {
+                    format!("{}+{}", tournament.buy_in, tournament.rake)
+                } else {
+                    format!(
+                        "{}{}+{}{} {}",
+                        money, tournament.buy_in, money, tournament.rake, info.currency_code
+                    )
+                };
+                format!(
+                    "Tournament #{}, {} {} - Level {} {}",
+                    tournament_id,
+                    buy_in,
+                    poker_type,
+                    roman_numeral(tournament.level),
+                    blinds
+                )
+            }
+            _ => format!(" {} {}", poker_type, blinds),
+        };
+        format!(
+            "PokerStars {}Hand #{}: {} - {}",
+            match info.game_info {
+                GameInfo::HoldUp(_) => "Zoom ",
+                _ => "",
+            },
+            pokerstars_hand_id(&info.hand_id),
+            game,
+            info.datetime.format("%Y/%m/%d %H:%M:%S UTC")
+        )
+    }
+
+    /// Writes the posts preceding the hole cards, rebuilding the antes and blinds when the hand
+    /// has no such posts
+    fn posts(hand: &Hand, currency: &str) -> Vec<String> {
+        let posts: Vec<&Action> = hand
+            .streets
+            .iter()
+            .flat_map(|street| street.actions.iter())
+            .filter(|action| matches!(action.action, ActionType::Post(_)))
+            .collect();
+        let mut lines = vec![];
+        let ante_posted = posts
+            .iter()
+            .any(|action| matches!(action.action, ActionType::Post(PostType::Ante(_))));
+        if let (false, Some(ante)) = (ante_posted, hand.hand_info.blinds.ante) {
+            for seat in &hand.seats {
+                lines.push(format!(
+                    "{}: {}",
+                    seat.player_name,
+                    Self::post(&PostType::Ante(ante), currency)
+                ));
+            }
+        }
+        if posts.is_empty() {
+            let blinds = &hand.hand_info.blinds;
+            let (small_blind, big_blind) = hand.blind_seats();
+            for (seat, post) in [
+                (small_blind, PostType::SmallBlind(blinds.small_blind)),
+                (big_blind, PostType::BigBlind(blinds.big_blind)),
+            ] {
+                if let Some(seat) = seat {
+                    lines.push(format!(
+                        "{}: {}",
+                        seat.player_name,
+                        Self::post(&post, currency)
+                    ));
+                }
+            }
+        }
+        let dead_blind = |player_name: &str| {
+            posts.iter().find_map(|action| match action.action {
+                ActionType::Post(PostType::Dead(amount)) if action.player_name == player_name => {
+                    Some(amount)
+                }
+                _ => None,
+            })
+        };
+        for action in &posts {
+            let post = match &action.action {
+                // dead blinds are only written along with the big blind of their player
+                ActionType::Post(PostType::Dead(_)) => continue,
+                ActionType::Post(PostType::BigBlind(amount)) => {
+                    match dead_blind(&action.player_name) {
+                        Some(dead) => {
+                            format!("posts small & big blinds {}{}", currency, *amount + dead)
+                        }
+                        None => Self::post(&PostType::BigBlind(*amount), currency),
+                    }
+                }
+                ActionType::Post(post) => Self::post(post, currency),
+                _ => continue,
+            };
+            let all_in = if action.is_all_in {
+                " and is all-in"
+            } else {
+                ""
+            };
+            lines.push(format!("{}: {}{}", action.player_name, post, all_in));
+        }
+        lines
+    }
+
+    fn post(post: &PostType, currency: &str) -> String {
+        match post {
+            PostType::Ante(amount) => format!("posts the ante {}{}", currency, amount),
+            PostType::SmallBlind(amount) => format!("posts small blind {}{}", currency, amount),
+            PostType::BigBlind(amount) => format!("posts big blind {}{}", currency, amount),
+            PostType::Dead(amount) => format!("posts small & big blinds {}{}", currency, amount),
+        }
+    }
+
+    fn street_header(hand: &Hand, index: usize, street: &Street) -> String {
+        let name = match street.street_type {
+            StreetType::Preflop => "HOLE CARDS",
+            StreetType::Flop => "FLOP",
+            StreetType::Turn => "TURN",
+            StreetType::River => "RIVER",
+            StreetType::Showdown => "SHOW DOWN",
+        };
+        if street.cards.is_empty() {
+            return format!("*** {} ***", name);
+        }
+        let previous = match index {
+            0 => vec![],
+            _ => hand.board_at(index - 1),
+        };
+        let previous = match previous.is_empty() {
+            true => String::new(),
+            false => format!("[{}] ", join_cards(previous.into_iter())),
+        };
+        format!(
+            "*** {} *** {}[{}]",
+            name,
+            previous,
+            join_cards(street.cards.iter())
+        )
+    }
+
+    fn action(hand: &Hand, action: &Action, side_pots: bool, currency: &str) -> String {
+        let name = &action.player_name;
+        let action_type = match &action.action {
+            ActionType::Bet { amount } => format!("bets {}{}", currency, amount),
+            ActionType::Call { amount } => format!("calls {}{}", currency, amount),
+            ActionType::Check => String::from("checks"),
+            ActionType::Fold => String::from("folds"),
+            ActionType::Post(post) => Self::post(post, currency),
+            ActionType::Raise { to_call, amount } => {
+                format!("raises {}{} to {}{}", currency, to_call, currency, amount)
+            }
+            ActionType::Shows { cards } => {
+                let category = hand
+                    .summary
+                    .players
+                    .iter()
+                    .find(|player| &player.name == name)
+                    .and_then(|player| player.hand_category.as_ref())
+                    .map(|category| format!(" ({})", Self::hand_category(category)))
+                    .unwrap_or_default();
+                format!("shows [{}]{}", join_cards(cards.cards.iter()), category)
+            }
+            ActionType::Collect { amount, pot } => {
+                let pot = match pot {
+                    Pot::Main if side_pots => String::from("main pot"),
+                    Pot::Main => String::from("pot"),
+                    Pot::Side(n) => format!("side pot-{}", n),
+                };
+                return format!("{} collected {}{} from {}", name, currency, amount, pot);
+            }
+            ActionType::UncalledBet { amount } => {
+                return format!("Uncalled bet ({}{}) returned to {}", currency, amount, name);
+            }
+            ActionType::CashoutRisk { amount } => {
+                format!("Pays Cashout Risk ({}{})", currency, amount)
+            }
+            ActionType::Cashout { amount } => format!("Receives Cashout ({}{})", currency, amount),
+        };
+        let all_in = match action.is_all_in {
+            true => " and is all-in",
+            false => "",
+        };
+        format!("{}: {}{}", name, action_type, all_in)
+    }
+
+    /// Name of a rank in a hand description, in the plural for a pair or more
+    fn rank_name(rank: &Rank, plural: bool) -> String {
+        let name = match rank {
+            Rank::Two => "Deuce",
+            Rank::Three => "Trey",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        };
+        match (plural, rank) {
+            (false, _) => name.to_owned(),
+            (true, Rank::Six) => String::from("Sixes"),
+            (true, _) => format!("{}s", name),
+        }
+    }
+
+    fn hand_category(category: &HandCategory) -> String {
+        let rank_name = Self::rank_name;
+        match category {
+            HandCategory::HighCard(rank) => format!("high card {}", rank_name(rank, false)),
+            HandCategory::Pair(rank) => format!("a pair of {}", rank_name(rank, true)),
+            HandCategory::TwoPair(high, low) => format!(
+                "two pair, {} and {}",
+                rank_name(high, true),
+                rank_name(low, true)
+            ),
+            HandCategory::ThreeOfAKind(rank) => {
+                format!("three of a kind, {}", rank_name(rank, true))
+            }
+            HandCategory::Straight(rank) => format!(
+                "a straight, {} to {}",
+                rank_name(&straight_low(rank), false),
+                rank_name(rank, false)
+            ),
+            HandCategory::Flush(rank) => format!("a flush, {} high", rank_name(rank, false)),
+            HandCategory::Full(three, two) => format!(
+                "a full house, {} full of {}",
+                rank_name(three, true),
+                rank_name(two, true)
+            ),
+            HandCategory::FourOfAKind(rank) => {
+                format!("four of a kind, {}", rank_name(rank, true))
+            }
+            HandCategory::StraightFlush(Rank::Ace) => String::from("a Royal Flush"),
+            HandCategory::StraightFlush(rank) => format!(
+                "a straight flush, {} to {}",
+                rank_name(&straight_low(rank), false),
+                rank_name(rank, false)
+            ),
+        }
+    }
+
+    fn summary_player(hand: &Hand, player: &SummaryPlayer, currency: &str) -> String {
+        let (small_blind, big_blind) = hand.blind_seats();
+        let is_seat = |seat: Option<&Seat>| seat.map(|seat| seat.seat_number) == Some(player.seat);
+        let position: String = [
+            (player.seat == hand.table_info.button, " (button)"),
+            (is_seat(small_blind), " (small blind)"),
+            (is_seat(big_blind), " (big blind)"),
+        ]
+        .into_iter()
+        .filter_map(|(is_position, position)| is_position.then_some(position))
+        .collect();
+        let category = player
+            .hand_category
+            .as_ref()
+            .map(|category| format!(" with {}", Self::hand_category(category)))
+            .unwrap_or_default();
+        let outcome = match (&player.hole_cards, &player.result) {
+            (Some(cards), SummaryResult::Won(amount)) => format!(
+                "showed [{}] and won ({}{}){}",
+                join_cards(cards.cards.iter()),
+                currency,
+                amount,
+                category
+            ),
+            (Some(cards), SummaryResult::Lost) => format!(
+                "showed [{}] and lost{}",
+                join_cards(cards.cards.iter()),
+                category
+            ),
+            (None, SummaryResult::Won(amount)) => format!("collected ({}{})", currency, amount),
+            (None, SummaryResult::Lost) => {
+                let folded = hand.streets.iter().find(|street| {
+                    street.actions.iter().any(|action| {
+                        action.player_name == player.name && action.action == ActionType::Fold
+                    })
+                });
+                String::from(match folded.map(|street| &street.street_type) {
+                    Some(StreetType::Preflop) => "folded before Flop",
+                    Some(StreetType::Flop) => "folded on the Flop",
+                    Some(StreetType::Turn) => "folded on the Turn",
+                    Some(StreetType::River) => "folded on the River",
+                    Some(StreetType::Showdown) | None => "mucked",
+                })
+            }
+        };
+        format!(
+            "Seat {}: {}{} {}",
+            player.seat, player.name, position, outcome
+        )
+    }
+}
+
+/// Currency sign following the chips in play, which are euros in real money cash games
+fn currency(hand: &Hand) -> &'static str {
+    match hand.hand_info.game_info {
+        GameInfo::Tournament(_) => "",
+        _ => money(&hand.table_info.currency),
+    }
+}
+
+/// Currency sign following buy-ins and bounties
+fn money(money_type: &MoneyType) -> &'static str {
+    match money_type {
+        MoneyType::RealMoney => "€",
+        MoneyType::PlayMoney => "",
+    }
+}
+
+fn join_cards<'a, T: ToString + 'a>(cards: impl Iterator<Item = &'a T>) -> String {
+    cards
+        .map(|card| card.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn write_header(hand: &Hand) -> String {
+    let info = &hand.hand_info;
+    let currency = currency(hand);
+    let game = match &info.game_info {
+        GameInfo::Tournament(tournament) => {
+            let money = money(&hand.table_info.currency);
+            format!(
+                "Tournament \"{}\" buyIn: {}{} + {}{} level: {}",
+                tournament.name, tournament.buy_in, money, tournament.rake, money, tournament.level
+            )
+        }
+        GameInfo::CashGame => String::from("CashGame"),
+        GameInfo::HoldUp(pool) => format!("HOLD-UP \"{}\"", pool),
+    };
+    let ante = info
+        .blinds
+        .ante
+        .map(|ante| format!("{}{}/", ante, currency))
+        .unwrap_or_default();
+    format!(
+        "Winamax Poker - {} - HandId: #{} - {} ({}{}{}/{}{}) - {}",
+        game,
+        info.hand_id,
+        match info.poker_type {
+            PokerType::HoldemNoLimit => "Holdem no limit",
+            PokerType::OmahaPotLimit => "Omaha pot limit",
+            PokerType::Omaha5PotLimit => "Omaha5 pot limit",
+        },
+        ante,
+        info.blinds.small_blind,
+        currency,
+        info.blinds.big_blind,
+        currency,
+        info.datetime.format("%Y/%m/%d %H:%M:%S UTC")
+    )
+}
+
+fn write_table_name(table_name: &TableName) -> String {
+    match table_name {
+        TableName::Tournament(name, tournament_id, table_id) => {
+            format!("'{}({})#{:03}'", name, tournament_id, table_id)
+        }
+        TableName::CashGame(name) => format!("'{}'", name),
+    }
+}
+
+fn write_blind_posts(hand: &Hand, currency: &str) -> Vec<String> {
+    let blinds = &hand.hand_info.blinds;
+    let (small_blind, big_blind) = hand.blind_seats();
+    let mut posts = vec![];
+    if let Some(ante) = blinds.ante {
+        for seat in &hand.seats {
+            posts.push(format!(
+                "{} posts ante {}{}",
+                seat.player_name, ante, currency
+            ));
+        }
+    }
+    for (seat, name, amount) in [
+        (small_blind, "small blind", blinds.small_blind),
+        (big_blind, "big blind", blinds.big_blind),
+    ] {
+        if let Some(seat) = seat {
+            posts.push(format!(
+                "{} posts {} {}{}",
+                seat.player_name, name, amount, currency
+            ));
+        }
+    }
+    posts
+}
+
+fn write_street_header(hand: &Hand, index: usize, street: &Street) -> String {
+    let name = match street.street_type {
+        StreetType::Preflop => "PRE-FLOP",
+        StreetType::Flop => "FLOP",
+        StreetType::Turn => "TURN",
+        StreetType::River => "RIVER",
+        StreetType::Showdown => "SHOW DOWN",
+    };
+    if street.cards.is_empty() {
+        return format!("*** {} ***", name);
+    }
+    let previous = match index {
+        0 => vec![],
+        _ => hand.board_at(index - 1),
+    };
+    let previous = match previous.is_empty() {
+        true => String::new(),
+        false => format!("[{}]", join_cards(previous.into_iter())),
+    };
+    format!(
+        "*** {} *** {}[{}]",
+        name,
+        previous,
+        join_cards(street.cards.iter())
+    )
+}
+
+/// Writes an action line, uncalled bets having no line in Winamax hands
+fn write_action(hand: &Hand, action: &Action, side_pots: bool, currency: &str) -> Option<String> {
+    let action_type = match &action.action {
+        ActionType::Bet { amount } => format!("bets {}{}", amount, currency),
+        ActionType::Call { amount } => format!("calls {}{}", amount, currency),
+        ActionType::Check => String::from("checks"),
+        ActionType::Fold => String::from("folds"),
+        ActionType::Post(post) => match post {
+            PostType::SmallBlind(amount) => format!("posts small blind {}{}", amount, currency),
+            PostType::BigBlind(amount) => format!("posts big blind {}{}", amount, currency),
+            PostType::Ante(amount) => format!("posts ante {}{}", amount, currency),
+            // Winamax has no dead blinds
+            PostType::Dead(_) => return None,
+        },
+        ActionType::Raise { to_call, amount } => {
+            format!("raises {}{} to {}{}", to_call, currency, amount, currency)
+        }
+        ActionType::Collect { amount, pot } => format!(
+            "collected {}{} from {}",
+            amount,
+            currency,
+            match pot {
+                Pot::Main if side_pots => String::from("main pot"),
+                Pot::Main => String::from("pot"),
+                Pot::Side(n) => format!("side pot {}", n),
+            }
+        ),
+        ActionType::Shows { cards } => {
+            let category = hand
+                .summary
+                .players
+                .iter()
+                .find(|player| player.name == action.player_name)
+                .and_then(|player| player.hand_category.as_ref())
+                .map(|category| format!(" ({})", write_hand_category(category)))
+                .unwrap_or_default();
+            format!("shows [{}]{}", join_cards(cards.cards.iter()), category)
+        }
+        ActionType::UncalledBet { .. } => return None,
+        // Winamax has no cash-outs
+        ActionType::CashoutRisk { .. } | ActionType::Cashout { .. } => return None,
+    };
+    let all_in = match action.is_all_in {
+        true => " and is all-in",
+        false => "",
+    };
+    Some(format!("{} {}{}", action.player_name, action_type, all_in))
+}
+
+/// Name of a rank in a hand description, in the plural for a pair or more
+fn rank_name(rank: &Rank, plural: bool) -> String {
+    let name = match rank {
+        Rank::Ten => "Ten",
+        Rank::Jack => "Jack",
+        Rank::Queen => "Queen",
+        Rank::King => "King",
+        Rank::Ace => "Ace",
+        rank => return rank.to_string(),
+    };
+    match plural {
+        true => format!("{}s", name),
+        false => name.to_owned(),
+    }
+}
+
+fn write_hand_category(category: &HandCategory) -> String {
+    match category {
+        HandCategory::HighCard(rank) => format!("High card : {}", rank_name(rank, false)),
+        HandCategory::Pair(rank) => format!("One pair : {}", rank_name(rank, true)),
+        HandCategory::TwoPair(high, low) => format!(
+            "Two pairs : {} and {}",
+            rank_name(high, true),
+            rank_name(low, true)
+        ),
+        HandCategory::ThreeOfAKind(rank) => format!("Trips of {}", rank_name(rank, true)),
+        HandCategory::Straight(rank) => format!("Straight {} high", rank_name(rank, false)),
+        HandCategory::Flush(rank) => format!("Flush {}", rank_name(rank, false)),
+        HandCategory::Full(three, two) => format!(
+            "Full of {} and {}",
+            rank_name(three, true),
+            rank_name(two, true)
+        ),
+        HandCategory::FourOfAKind(rank) => {
+            format!("Four of a kind : {}", rank_name(rank, true))
+        }
+        HandCategory::StraightFlush(rank) => {
+            format!("Straight flush {}", rank_name(rank, false))
+        }
+    }
+}
+
+fn write_summary_player(hand: &Hand, player: &SummaryPlayer, currency: &str) -> String {
+    let (small_blind, big_blind) = hand.blind_seats();
+    let is_seat = |seat: Option<&Seat>| seat.map(|seat| seat.seat_number) == Some(player.seat);
+    let position = if is_seat(big_blind) {
+        " (big blind)"
+    } else if is_seat(small_blind) {
+        " (small blind)"
+    } else if player.seat == hand.table_info.button {
+        " (button)"
+    } else {
+        ""
+    };
+    let showed = player
+        .hole_cards
+        .as_ref()
+        .map(|cards| format!(" showed [{}] and", join_cards(cards.cards.iter())))
+        .unwrap_or_default();
+    let result = match player.result {
+        SummaryResult::Won(amount) => format!(" won {}{}", amount, currency),
+        SummaryResult::Lost => String::from(" lost"),
+    };
+    let category = player
+        .hand_category
+        .as_ref()
+        .map(|category| format!(" with {}", write_hand_category(category)))
+        .unwrap_or_default();
+    format!(
+        "Seat {}: {}{}{}{}{}",
+        player.seat, player.name, position, showed, result, category
+    )
+}
+
+fn write_buy_in(buy_in: &BuyIn) -> String {
+    [buy_in.buy_in, buy_in.rake]
+        .into_iter()
+        .chain(buy_in.bounty)
+        .map(|amount| format!("{}€", amount))
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+/// Name of a tournament type in summaries, the reverse of `TournamentType::parse`
+fn write_tournament_type(tournament_type: &TournamentType) -> &str {
+    match tournament_type {
+        TournamentType::DoubleOrNothing => "doubleornothing",
+        TournamentType::Expresso => "expresso",
+        TournamentType::Freeroll100k => "freeroll100k",
+        TournamentType::HitnRun => "hitnrun",
+        TournamentType::Knockout => "knockout",
+        TournamentType::MadTilt => "madtilt",
+        TournamentType::Normal => "normal",
+        TournamentType::Qualifier => "qualifier",
+        TournamentType::Sitngo => "sitngo",
+        TournamentType::Unknown(name) => name,
+    }
+}
+
+/// Lowest rank of a straight, the Ace starting the wheel
+fn straight_low(high: &Rank) -> Rank {
+    const RANKS: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+    let index = RANKS
+        .iter()
+        .position(|rank| rank == high)
+        .unwrap_or_default();
+    match index.checked_sub(4) {
+        Some(low) => RANKS[low].clone(),
+        None => Rank::Ace,
+    }
+}
+
+/// Hand id as written by PokerStars: the digits of the id when they make a 64-bit number, or
+/// else a hash of the id
+///
+/// The FNV-1a hash stays the same from one build to the next, and is kept below 2^63 for the
+/// tools storing ids as signed numbers.
+fn pokerstars_hand_id(hand_id: &str) -> u64 {
+    let digits: String = hand_id.chars().filter(char::is_ascii_digit).collect();
+    if let Ok(id) = digits.parse() {
+        return id;
+    }
+    let hash = hand_id
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    hash & i64::MAX as u64
+}
+
+/// Tournament level as written by PokerStars, the reverse of `parse_roman_numeral`
+fn roman_numeral(mut n: u32) -> String {
+    let mut numeral = String::new();
+    for (value, digits) in [
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ] {
+        while n >= value {
+            numeral.push_str(digits);
+            n -= value;
+        }
+    }
+    numeral
+}
+
+fn ordinal_suffix(n: u32) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ggpoker::GGPokerParser;
+    use crate::parser::{
+        Blinds, Card, DealtToHero, HandInfo, HoleCards, Suit, Summary, TableInfo, TournamentInfo,
+        WinamaxParser,
+    };
+    use crate::pokerstars::PokerStarsParser;
+    use crate::site::SiteParser;
+    use crate::summary_parser::{parse_summary, Level};
+    use chrono::prelude::*;
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+    fn assert_round_trip(data: &str) {
+        let hands = WinamaxParser.parse_hands(data).unwrap();
+        let written = WinamaxWriter.write_hands(&hands);
+        assert_eq!(hands, WinamaxParser.parse_hands(&written).unwrap());
+    }
+
+    #[test]
+    fn test_write_hand() {
+        let data = include_str!("../samples/sample_omaha.txt");
+        let hands = WinamaxParser.parse_hands(data).unwrap();
+        let expected = data.split("\n\n").next().unwrap().to_owned() + "\n";
+        assert_eq!(WinamaxWriter.write_hand(&hands[0]), expected);
+    }
+
+    #[test]
+    fn test_write_sample_hands() {
+        assert_round_trip(include_str!("../samples/sample1.txt"));
+        assert_round_trip(include_str!("../samples/sample2.txt"));
+        assert_round_trip(include_str!("../samples/sample_omaha.txt"));
+    }
+
+    #[test]
+    fn test_write_summary() {
+        for data in [
+            include_str!("../samples/tournament_summary.txt"),
+            include_str!("../samples/tournament_summary_rebuy.txt"),
+            include_str!("../samples/expresso_summary.txt"),
+        ] {
+            let summary = parse_summary(data).unwrap();
+            let written = WinamaxWriter.write_summary(&summary);
+            assert_eq!(summary, parse_summary(&written).unwrap());
+        }
+        let summary = parse_summary(include_str!("../samples/tournament_summary_rebuy.txt"));
+        let written = WinamaxWriter.write_summary(&summary.unwrap());
+        assert!(written.ends_with(
+            "You won 14.94€ + Bounty 2.50€ + Ticket Freeroll 100K\n\
+            You made 1 re-entry, 2 rebuys and 1 addon.\n"
+        ));
+    }
+
+    /// What is left of a hand once written by PokerStars and parsed back
+    fn pokerstars_view(hand: &Hand) -> Hand {
+        let mut hand = hand.clone();
+        let info = &mut hand.hand_info;
+        info.hand_id = pokerstars_hand_id(&info.hand_id).to_string();
+        info.site = String::from("PokerStars");
+        // the currency is only written with amounts of money
+        let freeroll = matches!(&info.game_info, GameInfo::Tournament(tournament)
+            if tournament.buy_in == Amount::ZERO && tournament.rake == Amount::ZERO);
+        if hand.table_info.currency == MoneyType::PlayMoney || freeroll {
+            info.currency_code = String::from("USD");
+        }
+        if let TableName::Tournament(name, tournament_id, _) = &mut hand.table_info.table_name {
+            *name = tournament_id.to_string();
+            if let GameInfo::Tournament(tournament) = &mut info.game_info {
+                tournament.name = tournament_id.to_string();
+            }
+        }
+        if let GameInfo::HoldUp(pool) = &mut info.game_info {
+            *pool = String::from("Zoom");
+        }
+        let posted_ante = hand.streets.iter().find_map(|street| {
+            street
+                .actions
+                .iter()
+                .find_map(|action| match action.action {
+                    ActionType::Post(PostType::Ante(ante)) => Some(ante),
+                    _ => None,
+                })
+        });
+        info.blinds.ante = posted_ante.or(info.blinds.ante);
+        let blinds = info.blinds.clone();
+        // every post is written before the hole cards, the antes and blinds being rebuilt
+        // when the hand has no such posts
+        let post = |player_name: &str, post: PostType| Action {
+            player_name: player_name.to_owned(),
+            action: ActionType::Post(post),
+            is_all_in: false,
+        };
+        let mut posts = vec![];
+        let ante_posted = hand.streets.iter().any(|street| {
+            street
+                .actions
+                .iter()
+                .any(|action| matches!(action.action, ActionType::Post(PostType::Ante(_))))
+        });
+        if let (false, Some(ante)) = (ante_posted, blinds.ante) {
+            for seat in &hand.seats {
+                posts.push(post(&seat.player_name, PostType::Ante(ante)));
+            }
+        }
+        let mut posted = vec![];
+        for street in &mut hand.streets {
+            posted.extend(
+                street
+                    .actions
+                    .iter()
+                    .filter(|action| matches!(action.action, ActionType::Post(_)))
+                    .cloned(),
+            );
+            street
+                .actions
+                .retain(|action| !matches!(action.action, ActionType::Post(_)));
+        }
+        if posted.is_empty() {
+            let (small_blind, big_blind) = hand.blind_seats();
+            for (seat, blind) in [
+                (small_blind, PostType::SmallBlind(blinds.small_blind)),
+                (big_blind, PostType::BigBlind(blinds.big_blind)),
+            ] {
+                if let Some(seat) = seat {
+                    posts.push(post(&seat.player_name, blind));
+                }
+            }
+        }
+        posts.extend(posted);
+        hand.streets[0].actions.splice(0..0, posts);
+        hand.summary.rake = hand.summary.rake.filter(|rake| *rake > Amount::ZERO);
+        hand
+    }
+
+    #[test]
+    fn test_write_pokerstars_hands() {
+        for data in [
+            include_str!("../samples/sample1.txt"),
+            include_str!("../samples/sample2.txt"),
+            include_str!("../samples/sample_omaha.txt"),
+        ] {
+            let hands = WinamaxParser.parse_hands(data).unwrap();
+            let written = PokerStarsWriter.write_hands(&hands);
+            let expected: Vec<Hand> = hands.iter().map(pokerstars_view).collect();
+            assert_eq!(PokerStarsParser.parse_hands(&written).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_write_pokerstars_currency() {
+        let data = include_str!("../samples/ggpoker.txt");
+        let hand = &GGPokerParser.parse_hands(data).unwrap()[2];
+        let written = PokerStarsWriter.write_hand(hand);
+        assert!(
+            written.starts_with("PokerStars Hand #1234567999:  Hold'em No Limit ($0.05/$0.10 USD)")
+        );
+        assert!(written.contains("Seat 2: Hero ($10 in chips)"));
+        let parsed = PokerStarsParser.parse_hands(&written).unwrap();
+        assert_eq!(parsed[0].hand_info.currency_code, "USD");
+    }
+
+    #[test]
+    fn test_pokerstars_hand_id() {
+        let hands = WinamaxParser
+            .parse_hands(include_str!("../samples/sample1.txt"))
+            .unwrap();
+        let ids: Vec<String> = hands
+            .iter()
+            .map(|hand| pokerstars_hand_id(&hand.hand_info.hand_id).to_string())
+            .collect();
+        for id in &ids {
+            assert!(id.parse::<u64>().is_ok());
+        }
+        // consecutive hands of a table get different ids
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[1], ids[2]);
+        // "1-23-4" and "12-3-4" have the same digits
+        assert_ne!(
+            pokerstars_hand_id("2815488303912976462-1-231684698584"),
+            pokerstars_hand_id("2815488303912976462-12-31684698584")
+        );
+        assert_eq!(pokerstars_hand_id("RC1234567890"), 1234567890);
+    }
+
+    #[test]
+    fn test_write_pokerstars_tournament_hand() {
+        let data = include_str!("../samples/sample1.txt");
+        let hands = WinamaxParser.parse_hands(data).unwrap();
+        let written = PokerStarsWriter.write_hand(&hands[0]);
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(
+            lines[0],
+            "PokerStars Hand #1804454731742674660: Tournament #655531954, \
+            €0.90+€0.10 EUR Hold'em No Limit - Level VI (250/500) - 2023/05/21 19:49:44 UTC"
+        );
+        assert_eq!(lines[1], "Table '655531954 77' 6-max Seat #3 is the button");
+        assert_eq!(
+            lines[2],
+            "Seat 1: Anonymous 1 (20535 in chips, €0.45 bounty)"
+        );
+        assert_eq!(lines[8], "Anonymous 4: posts the ante 60");
+        assert!(lines.contains(&"*** HOLE CARDS ***"));
+    }
+
+    #[test]
+    fn test_pokerstars_hand_category() {
+        let categories: Vec<String> = [
+            HandCategory::Pair(Rank::Six),
+            HandCategory::TwoPair(Rank::Ace, Rank::Two),
+            HandCategory::Straight(Rank::Five),
+            HandCategory::Straight(Rank::Ace),
+            HandCategory::Full(Rank::King, Rank::Three),
+            HandCategory::StraightFlush(Rank::Nine),
+            HandCategory::StraightFlush(Rank::Ace),
+        ]
+        .iter()
+        .map(PokerStarsWriter::hand_category)
+        .collect();
+        assert_eq!(
+            categories,
+            [
+                "a pair of Sixes",
+                "two pair, Aces and Deuces",
+                "a straight, Ace to Five",
+                "a straight, Ten to Ace",
+                "a full house, Kings full of Treys",
+                "a straight flush, Five to Nine",
+                "a Royal Flush",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roman_numeral() {
+        let numerals: Vec<String> = [1, 4, 6, 9, 14, 40, 49, 99].map(roman_numeral).into();
+        assert_eq!(
+            numerals,
+            ["I", "IV", "VI", "IX", "XIV", "XL", "XLIX", "XCIX"]
+        );
+    }
+
+    #[test]
+    fn test_ordinal_suffix() {
+        let suffixes: Vec<&str> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 111, 145]
+            .into_iter()
+            .map(ordinal_suffix)
+            .collect();
+        assert_eq!(
+            suffixes,
+            ["st", "nd", "rd", "th", "th", "th", "th", "st", "nd", "th", "th"]
+        );
+    }
+
+    fn choose<T: Clone>(g: &mut Gen, values: &[T]) -> T {
+        g.choose(values).unwrap().clone()
+    }
+
+    fn amount(g: &mut Gen) -> Amount {
+        Amount::from_cents(i64::from(u32::arbitrary(g) % 1_000_000))
+    }
+
+    fn card(g: &mut Gen) -> Card {
+        let rank = choose(
+            g,
+            &[
+                Rank::Two,
+                Rank::Three,
+                Rank::Four,
+                Rank::Five,
+                Rank::Six,
+                Rank::Seven,
+                Rank::Eight,
+                Rank::Nine,
+                Rank::Ten,
+                Rank::Jack,
+                Rank::Queen,
+                Rank::King,
+                Rank::Ace,
+            ],
+        );
+        let suit = choose(
+            g,
+            &[Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs],
+        );
+        Card { rank, suit }
+    }
+
+    fn cards(g: &mut Gen, n: usize) -> Vec<Card> {
+        (0..n).map(|_| card(g)).collect()
+    }
+
+    fn hand_category(g: &mut Gen) -> HandCategory {
+        let (high, low) = (card(g).rank, card(g).rank);
+        match u8::arbitrary(g) % 9 {
+            0 => HandCategory::HighCard(high),
+            1 => HandCategory::Pair(high),
+            2 => HandCategory::TwoPair(high, low),
+            3 => HandCategory::ThreeOfAKind(high),
+            4 => HandCategory::Straight(high),
+            5 => HandCategory::Flush(high),
+            6 => HandCategory::Full(high, low),
+            7 => HandCategory::FourOfAKind(high),
+            _ => HandCategory::StraightFlush(high),
+        }
+    }
+
+    fn action(
+        g: &mut Gen,
+        player_name: &str,
+        street_type: &StreetType,
+        hole_cards: usize,
+    ) -> Action {
+        let mut action_types = vec![0, 1, 2, 3, 4, 5, 6];
+        if *street_type == StreetType::Preflop {
+            action_types.push(7);
+        }
+        let action = match choose(g, &action_types) {
+            0 => ActionType::Check,
+            1 => ActionType::Fold,
+            2 => ActionType::Call { amount: amount(g) },
+            3 => ActionType::Bet { amount: amount(g) },
+            4 => ActionType::Raise {
+                to_call: amount(g),
+                amount: amount(g),
+            },
+            5 => ActionType::Collect {
+                amount: amount(g),
+                pot: match u8::arbitrary(g) % 3 {
+                    0 => Pot::Main,
+                    n => Pot::Side(u32::from(n)),
+                },
+            },
+            6 => ActionType::Shows {
+                cards: HoleCards {
+                    cards: cards(g, hole_cards),
+                },
+            },
+            _ => {
+                let amount = amount(g);
+                ActionType::Post(match u8::arbitrary(g) % 3 {
+                    0 => PostType::Ante(amount),
+                    1 => PostType::SmallBlind(amount),
+                    _ => PostType::BigBlind(amount),
+                })
+            }
+        };
+        let is_all_in = matches!(
+            action,
+            ActionType::Bet { .. } | ActionType::Call { .. } | ActionType::Raise { .. }
+        ) && bool::arbitrary(g);
+        Action {
+            player_name: player_name.to_owned(),
+            action,
+            is_all_in,
+        }
+    }
+
+    /// Hand made of anything the Winamax format can hold
+    #[derive(Debug, Clone)]
+    struct WinamaxHand(Hand);
+
+    impl Arbitrary for WinamaxHand {
+        fn arbitrary(g: &mut Gen) -> WinamaxHand {
+            let poker_type = choose(
+                g,
+                &[
+                    PokerType::HoldemNoLimit,
+                    PokerType::OmahaPotLimit,
+                    PokerType::Omaha5PotLimit,
+                ],
+            );
+            let tournament_name = choose(g, &["WESTERN", "MYSTERY KO", "Expresso"]);
+            let table_name = choose(g, &["Nice 04", "Colorado", "Wichita 05"]);
+            let (game_info, table_name) = match u8::arbitrary(g) % 3 {
+                0 => (
+                    GameInfo::Tournament(TournamentInfo {
+                        name: tournament_name.to_owned(),
+                        buy_in: amount(g),
+                        rake: amount(g),
+                        level: 1 + u32::arbitrary(g) % 40,
+                    }),
+                    TableName::Tournament(
+                        tournament_name.to_owned(),
+                        u32::arbitrary(g),
+                        u32::arbitrary(g) % 1000,
+                    ),
+                ),
+                1 => (
+                    GameInfo::CashGame,
+                    TableName::CashGame(table_name.to_owned()),
+                ),
+                _ => (
+                    GameInfo::HoldUp(table_name.to_owned()),
+                    TableName::CashGame(table_name.to_owned()),
+                ),
+            };
+            let blinds = Blinds {
+                ante: match game_info {
+                    GameInfo::Tournament(_) => Option::arbitrary(g).map(|_: ()| amount(g)),
+                    _ => None,
+                },
+                small_blind: amount(g),
+                big_blind: amount(g),
+            };
+            let max_players = 2 + u32::arbitrary(g) % 9;
+            let nb_seats = 2 + u32::arbitrary(g) % (max_players - 1);
+            let hero_seat = 1 + u32::arbitrary(g) % nb_seats;
+            let seats: Vec<Seat> = (1..=nb_seats)
+                .map(|seat_number| Seat {
+                    seat_number,
+                    player_name: match seat_number == hero_seat {
+                        true => String::from("WinterSound"),
+                        false => format!("Anonymous {}", seat_number),
+                    },
+                    stack: amount(g),
+                    bounty: Option::arbitrary(g).map(|_: ()| amount(g)),
+                })
+                .collect();
+
+            let nb_streets = 1 + usize::arbitrary(g) % 4;
+            let streets: Vec<Street> = [
+                (StreetType::Preflop, 0),
+                (StreetType::Flop, 3),
+                (StreetType::Turn, 1),
+                (StreetType::River, 1),
+            ]
+            .into_iter()
+            .take(nb_streets)
+            .chain(bool::arbitrary(g).then_some((StreetType::Showdown, 0)))
+            .map(|(street_type, nb_cards)| Street {
+                cards: cards(g, nb_cards),
+                actions: (0..usize::arbitrary(g) % 6)
+                    .map(|_| {
+                        let seat = choose(g, &seats);
+                        action(g, &seat.player_name, &street_type, poker_type.hole_cards())
+                    })
+                    .collect(),
+                street_type,
+            })
+            .collect();
+            let mut board: Vec<Option<Card>> = streets
+                .iter()
+                .flat_map(|street| street.cards.iter().cloned().map(Some))
+                .collect();
+            board.resize_with(5, || None);
+
+            let players = (0..1 + usize::arbitrary(g) % 3)
+                .map(|_| {
+                    let seat = choose(g, &seats);
+                    let hole_cards = Option::arbitrary(g).map(|_: ()| HoleCards {
+                        cards: cards(g, poker_type.hole_cards()),
+                    });
+                    SummaryPlayer {
+                        name: seat.player_name.clone(),
+                        seat: seat.seat_number,
+                        hand_category: hole_cards
+                            .as_ref()
+                            .and_then(|_| Option::arbitrary(g))
+                            .map(|_: ()| hand_category(g)),
+                        hole_cards,
+                        result: match bool::arbitrary(g) {
+                            true => SummaryResult::Won(amount(g)),
+                            false => SummaryResult::Lost,
+                        },
+                    }
+                })
+                .collect();
+            WinamaxHand(Hand {
+                hand_info: HandInfo {
+                    game_info,
+                    hand_id: format!(
+                        "{}-{}-{}",
+                        u64::arbitrary(g),
+                        u16::arbitrary(g),
+                        u32::arbitrary(g)
+                    ),
+                    poker_type: poker_type.clone(),
+                    blinds,
+                    datetime: Utc
+                        .timestamp_opt(1_600_000_000 + i64::from(u32::arbitrary(g) % (1 << 28)), 0)
+                        .unwrap(),
+                    site: String::from("Winamax"),
+                    currency_code: String::from("EUR"),
+                },
+                table_info: TableInfo {
+                    table_name,
+                    max_players,
+                    currency: choose(g, &[MoneyType::RealMoney, MoneyType::PlayMoney]),
+                    button: 1 + u32::arbitrary(g) % nb_seats,
+                },
+                dealt_cards: DealtToHero {
+                    player_name: String::from("WinterSound"),
+                    hole_cards: HoleCards {
+                        cards: cards(g, poker_type.hole_cards()),
+                    },
+                },
+                seats,
+                summary: Summary {
+                    pot: amount(g),
+                    rake: Option::arbitrary(g).map(|_: ()| amount(g)),
+                    players,
+                    boards: match nb_streets > 1 {
+                        true => vec![Board { cards: board }],
+                        false => vec![],
+                    },
+                },
+                streets,
+            })
+        }
+    }
+
+    /// What is left of a hand once written by Winamax and parsed back, the antes and blinds
+    /// of a hand without posts being rebuilt
+    fn winamax_view(hand: &Hand) -> Hand {
+        let mut hand = hand.clone();
+        let posted = hand.streets.iter().any(|street| {
+            street
+                .actions
+                .iter()
+                .any(|action| matches!(action.action, ActionType::Post(_)))
+        });
+        if posted {
+            return hand;
+        }
+        let post = |player_name: &str, post: PostType| Action {
+            player_name: player_name.to_owned(),
+            action: ActionType::Post(post),
+            is_all_in: false,
+        };
+        let blinds = &hand.hand_info.blinds;
+        let mut posts = vec![];
+        if let Some(ante) = blinds.ante {
+            for seat in &hand.seats {
+                posts.push(post(&seat.player_name, PostType::Ante(ante)));
+            }
+        }
+        let (small_blind, big_blind) = hand.blind_seats();
+        for (seat, blind) in [
+            (small_blind, PostType::SmallBlind(blinds.small_blind)),
+            (big_blind, PostType::BigBlind(blinds.big_blind)),
+        ] {
+            if let Some(seat) = seat {
+                posts.push(post(&seat.player_name, blind));
+            }
+        }
+        hand.streets[0].actions.splice(0..0, posts);
+        hand
+    }
+
+    #[test]
+    fn test_hand_round_trip() {
+        fn round_trip(hand: WinamaxHand) -> bool {
+            let written = WinamaxWriter.write_hand(&hand.0);
+            WinamaxParser.parse_hands(&written) == Ok(vec![winamax_view(&hand.0)])
+        }
+        QuickCheck::new()
+            .tests(500)
+            .quickcheck(round_trip as fn(WinamaxHand) -> bool);
+    }
+
+    #[test]
+    fn test_pokerstars_round_trip() {
+        fn round_trip(hand: WinamaxHand) -> bool {
+            let written = PokerStarsWriter.write_hand(&hand.0);
+            PokerStarsParser.parse_hands(&written) == Ok(vec![pokerstars_view(&hand.0)])
+        }
+        QuickCheck::new()
+            .tests(500)
+            .quickcheck(round_trip as fn(WinamaxHand) -> bool);
+    }
+
+    /// Tournament summary made of anything the Winamax format can hold
+    #[derive(Debug, Clone)]
+    struct WinamaxSummary(TournamentSummary);
+
+    impl Arbitrary for WinamaxSummary {
+        fn arbitrary(g: &mut Gen) -> WinamaxSummary {
+            let buy_in = |g: &mut Gen| BuyIn {
+                buy_in: amount(g),
+                rake: amount(g),
+                bounty: Option::arbitrary(g).map(|_: ()| amount(g)),
+            };
+            let tournament_type = choose(
+                g,
+                &[
+                    TournamentType::DoubleOrNothing,
+                    TournamentType::Freeroll100k,
+                    TournamentType::HitnRun,
+                    TournamentType::Knockout,
+                    TournamentType::MadTilt,
+                    TournamentType::Normal,
+                    TournamentType::Qualifier,
+                    TournamentType::Sitngo,
+                    TournamentType::Unknown(String::from("lottery")),
+                ],
+            );
+            let levels = (0..1 + usize::arbitrary(g) % 4)
+                .map(|_| Level {
+                    ante: u32::arbitrary(g),
+                    small_blind: u32::arbitrary(g),
+                    big_blind: u32::arbitrary(g),
+                    seconds: u32::arbitrary(g),
+                    poker_type: choose(
+                        g,
+                        &[
+                            PokerType::HoldemNoLimit,
+                            PokerType::OmahaPotLimit,
+                            PokerType::Omaha5PotLimit,
+                        ],
+                    ),
+                })
+                .collect();
+            WinamaxSummary(TournamentSummary {
+                name: choose(g, &["MYSTERY KO", "MONSTER STACK"]).to_owned(),
+                hero: String::from("WinterSound"),
+                id: u32::arbitrary(g),
+                buy_in: buy_in(g),
+                entries: u32::arbitrary(g),
+                mode: choose(g, &["tt", "sng"]).to_owned(),
+                tournament_type,
+                speed: choose(g, &["normal", "turbo", "nitro"]).to_owned(),
+                flight_id: u32::arbitrary(g),
+                levels,
+                prizepool: amount(g),
+                date: Utc
+                    .timestamp_opt(1_600_000_000 + i64::from(u32::arbitrary(g) % (1 << 28)), 0)
+                    .unwrap(),
+                play_time: String::from("2h 31min 43s "),
+                finish_place: 1 + u32::arbitrary(g) % 1000,
+                won: Option::arbitrary(g).map(|_: ()| amount(g)),
+                expresso: None,
+                rebuy_cost: Option::arbitrary(g).map(|_: ()| buy_in(g)),
+                addon_cost: Option::arbitrary(g).map(|_: ()| buy_in(g)),
+                re_entries: u32::arbitrary(g) % 3,
+                rebuys: u32::arbitrary(g) % 3,
+                addons: u32::arbitrary(g) % 3,
+                bounties_won: Option::arbitrary(g).map(|_: ()| amount(g)),
+                tickets: Option::arbitrary(g)
+                    .map(|_: ()| String::from("Freeroll 100K"))
+                    .into_iter()
+                    .collect(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_summary_round_trip() {
+        fn round_trip(summary: WinamaxSummary) -> bool {
+            let written = WinamaxWriter.write_summary(&summary.0);
+            parse_summary(&written) == Ok(summary.0)
+        }
+        QuickCheck::new()
+            .tests(500)
+            .quickcheck(round_trip as fn(WinamaxSummary) -> bool);
+    }
+}